@@ -5,6 +5,7 @@ use crate::parse_xlsx::errors::ExcelDataError;
 use crate::path_operations::errors::PathOpError;
 use crate::read_hcl::errors::ReadHCLError;
 use crate::read_json::errors::ReadJsonError;
+use crate::read_xlsx::errors::ReadXlsxError;
 use crate::create_hcl::errors::CreateHCLError;
 
 #[derive(Debug)]
@@ -19,6 +20,7 @@ pub enum Excel2XmlError {
     WriteHCLError(CreateHCLError),
     ReadHCLError(ReadHCLError),
     ReadJsonError(ReadJsonError),
+    ReadXlsxError(ReadXlsxError),
     PathOpError(PathOpError),
     APICallError(APICallError),
     SerError(Box<dyn StdError>),
@@ -29,6 +31,11 @@ impl From<Box<dyn StdError>> for Excel2XmlError {
     }
 }
 
+impl From<ReadXlsxError> for Excel2XmlError {
+    fn from(error: ReadXlsxError) -> Self {
+        Excel2XmlError::ReadXlsxError(error)
+    }
+}
 impl From<APICallError> for Excel2XmlError {
     fn from(error: APICallError) -> Self {
         Excel2XmlError::APICallError(error)
@@ -1,10 +1,12 @@
-use hcl::{Expression, Number};
+use std::collections::HashMap;
+use hcl::{Expression, Number, ObjectKey};
 use crate::parse_hcl::errors::HCLDataError;
 pub trait ExpressionTransform {
     fn to_string_2(&self) -> Result<String, HCLDataError>;
     fn to_bool(&self) -> Result<bool, HCLDataError>;
     fn to_number(&self) -> Result<Number, HCLDataError>;
     fn to_vec(&self) -> Result<Vec<Expression>, HCLDataError>;
+    fn to_string_map(&self) -> Result<HashMap<String, String>, HCLDataError>;
 }
 
 impl ExpressionTransform for hcl::Expression {
@@ -35,4 +37,23 @@ impl ExpressionTransform for hcl::Expression {
             _ => Err(HCLDataError::ParsingError(format!("cannot parse this hcl::Expression '{:?}' to number, because it is not a number. Did you write a number-value within quotation marks? Everything within quotation marks will be read as string-value.", self)))
         }
     }
-}
\ No newline at end of file
+
+    fn to_string_map(&self) -> Result<HashMap<String, String>, HCLDataError> {
+        let object = match self {
+            Expression::Object(object) => {object}
+            _ => return Err(HCLDataError::ParsingError(format!("cannot parse this hcl::Expression '{:?}' to a map, because it is not an object.", self)))
+        };
+        let mut map = HashMap::new();
+        for (key, value) in object.iter() {
+            let key = match key {
+                ObjectKey::Identifier(identifier) => {identifier.to_string()}
+                ObjectKey::Expression(expression) => {expression.to_string_2()?}
+                _ => return Err(HCLDataError::ParsingError(format!("cannot parse this object-key '{:?}' to string.", key)))
+            };
+            if map.insert(key.to_owned(), value.to_string_2()?).is_some() {
+                return Err(HCLDataError::ParsingError(format!("found duplicate key '{}' in object '{:?}'.", key, self)));
+            }
+        }
+        Ok(map)
+    }
+}
@@ -8,7 +8,9 @@ use crate::parse_dm::domain::data_model::DataModel;
 use crate::errors::Excel2XmlError;
 use crate::parse_hcl::domain::parse_info::ParseInformation;
 use crate::parse_hcl::domain::parse_info_draft::ParseInformationDraft;
-use crate::parse_xlsx::domain::data_container::{DataContainer, DataContainerWrapper};
+use crate::parse_hcl::header_value::HeaderValue;
+use crate::parse_hcl::methods_domain::map_method::MapFile;
+use crate::parse_xlsx::domain::data_container::{merge_data_containers, DataContainer, DataContainerWrapper};
 use crate::parse_xlsx::domain::expanded_data_sheet::{expanded_data_sheets, ExpandedDataSheet};
 use crate::parse_xlsx::domain::intermediate_sheet::{intermediate_sheets, parse_data_to_string, IntermediateSheet};
 use crate::parse_xlsx::errors::ExcelDataError;
@@ -67,7 +69,8 @@ pub fn excel2xml(hcl_path: &PathBuf) {
         }
         Some(parent) => {parent}
     };
-    let parse_info: ParseInformation = parse_hcl_info(&hcl_path, &hcl_folder).unwrap();
+    let mut parse_info: ParseInformation = parse_hcl_info(&hcl_path, &hcl_folder).unwrap();
+    load_map_tables(&mut parse_info).unwrap();
 
     /*
     let folder_path = fs::canonicalize(folder_path).expect("unable to find absolute-path of folder-path");
@@ -121,6 +124,84 @@ fn res_names_iris(transformations: &Vec<&Transformations>, shortcode: &String) -
     }
 }
 
+fn load_map_tables(parse_info: &mut ParseInformation) -> Result<(), Excel2XmlError> {
+    // tables of map-methods that are kept in a csv- or xlsx-file are read from the resources folder,
+    // GeoNames-dumps of to_geoname-methods are only checked here and read when the method is performed
+    for workbook in parse_info.rel_path_to_xlsx_workbooks.values_mut() {
+        for sheet_info in workbook.sheet_infos.values_mut().flatten() {
+            let transformations = match sheet_info.transformations.as_mut() {
+                None => { continue }
+                Some(transformations) => { transformations }
+            };
+            for map_method in transformations.map_methods.iter_mut() {
+                if let Some(map_file) = &map_method.file {
+                    map_method.table = read_map_table(&parse_info.res_folder.join(&map_file.path), map_file)?;
+                }
+            }
+            for to_geoname_method in transformations.to_geoname_methods.iter_mut() {
+                to_geoname_method.path = parse_info.res_folder.join(&to_geoname_method.path);
+                if !to_geoname_method.path.is_file() {
+                    return Err(ExcelDataError::InputError(format!("to_geoname '{}': GeoNames-dump '{:?}' not found.", to_geoname_method.output, to_geoname_method.path)).into());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_map_table(path: &PathBuf, map_file: &MapFile) -> Result<HashMap<String, String>, Excel2XmlError> {
+    let (headers, rows): (Vec<String>, Vec<Vec<String>>) = if map_file.path.ends_with(".csv") {
+        let (headers, rows) = read_as_headers_rows(path.to_owned())?;
+        (headers.iter().map(|header| clean_header_string(&header.to_string())).collect(),
+         rows.iter().map(|row| row.iter().map(|value| value.to_string()).collect()).collect())
+    } else {
+        let worksheets = read_xlsx(path)?;
+        let table = match worksheets.get(map_file.sheet - 1) {
+            None => {
+                return Err(ExcelDataError::InputError(format!("map-table '{:?}' doesn't have a sheet with number {}.", path, map_file.sheet)).into());
+            }
+            Some((_, table)) => { table }
+        };
+        let mut rows = vec![];
+        for row in table.rows() {
+            rows.push(row.iter().map(|value| parse_data_to_string(value)).collect::<Result<Vec<String>, ExcelDataError>>()?);
+        }
+        if rows.is_empty() {
+            return Err(ExcelDataError::InputError(format!("map-table '{:?}' is empty.", path)).into());
+        }
+        let headers = rows.remove(0).iter().map(|header| clean_header_string(header)).collect();
+        (headers, rows)
+    };
+    let key_pos = map_table_column(&map_file.key, &headers, path)?;
+    let value_pos = map_table_column(&map_file.value, &headers, path)?;
+    let mut table: HashMap<String, String> = HashMap::new();
+    for row in rows.iter() {
+        let key = clean_value(row.get(key_pos).map(|key| key.as_str()).unwrap_or(""));
+        if key.is_empty() {
+            continue
+        }
+        let value = clean_value(row.get(value_pos).map(|value| value.as_str()).unwrap_or(""));
+        if let Some(existing) = table.get(&key) {
+            if existing != &value {
+                return Err(ExcelDataError::InputError(format!("map-table '{:?}': key '{}' maps to different values '{}' and '{}'.", path, key, existing, value)).into());
+            }
+        }
+        table.insert(key, value);
+    }
+    Ok(table)
+}
+
+fn map_table_column(column: &HeaderValue, headers: &Vec<String>, path: &PathBuf) -> Result<usize, Excel2XmlError> {
+    let pos = match column {
+        HeaderValue::Name(name) => { headers.iter().position(|header| header == name) }
+        HeaderValue::Number(number) => { if (*number as usize) < headers.len() { Some(*number as usize) } else { None } }
+    };
+    match pos {
+        None => { Err(ExcelDataError::InputError(format!("map-table '{:?}': column '{:?}' not found in headers '{:?}'.", path, column, headers)).into()) }
+        Some(pos) => { Ok(pos) }
+    }
+}
+
 fn res_name_to_label_to_iri(rows: Vec<StringRecord>) -> HashMap<String, HashMap<String, String>>{
     // resource class
     let mut res_name_to_label_iri: HashMap<String, HashMap<String, String>> = HashMap::new();
@@ -177,16 +258,16 @@ fn updated_data_sheets(expanded_data_sheets: Vec<ExpandedDataSheet>, res_name_to
 fn data_containers(data_sheet: &Vec<UpdatedDataSheet>, data_model: &DataModel, parse_info: &ParseInformation) -> Result<Vec<DataContainer>, ExcelDataError> {
     let mut data_containers = vec![];
     for updated_sheet in data_sheet.iter() {
-        data_containers.push(DataContainerWrapper(updated_sheet.to_owned()).to_data_container(data_model, parse_info)?);
+        data_containers.extend(DataContainerWrapper(updated_sheet.to_owned()).to_data_containers(data_model, parse_info)?);
     }
-    Ok(data_containers)
+    merge_data_containers(data_containers)
 }
 
 
 fn parse_hcl_info(hcl_path: &PathBuf, curr_folder: &&Path) -> Result<ParseInformation , Excel2XmlError> {
     let hcl_body:hcl::Body = read_hcl_body(&hcl_path)?;
     let mut hcl_info_draft: ParseInformationDraft = hcl_body.try_into()?;
-    let (res_folder, dm_path) = canonicalize_paths(&hcl_info_draft.dm_path, &hcl_info_draft.res_folder, curr_folder)?;
+    let (res_folder, dm_path) = canonicalize_paths(&hcl_info_draft.dm_path, &hcl_info_draft.res_folder, curr_folder, &hcl_info_draft.shortcode)?;
     let parse_info: ParseInformation = ParseInformation::new(hcl_info_draft, dm_path, res_folder);
     Ok(parse_info)
 }
@@ -0,0 +1,243 @@
+use hcl::{Attribute, Block, Expression};
+use regex::Regex;
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::wrapper_trait::Wrapper;
+
+#[derive(Debug, Clone)]
+pub struct Filter {
+    pub includes: Vec<FilterCondition>,
+    pub excludes: Vec<FilterCondition>,
+}
+
+impl Filter {
+    fn new(transient_filter: TransientFilter) -> Self {
+        Filter {
+            includes: transient_filter.includes,
+            excludes: transient_filter.excludes,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FilterCondition {
+    pub input: HeaderValue,
+    pub condition_type: ConditionType,
+}
+
+#[derive(Debug, Clone)]
+pub enum ConditionType {
+    Equals(String),
+    NotEmpty,
+    Regex(Regex),
+    OneOf(Vec<String>),
+}
+
+impl FilterCondition {
+    pub(crate) fn new(transient_filter_condition: TransientFilterCondition) -> Self {
+        FilterCondition {
+            input: transient_filter_condition.input.unwrap(),
+            condition_type: transient_filter_condition.condition_type.unwrap(),
+        }
+    }
+    pub(crate) fn is_met(&self, values: &Vec<String>) -> bool {
+        // a multi-value field meets the condition if at least one of its values does
+        match &self.condition_type {
+            ConditionType::Equals(expected) => {
+                values.iter().any(|value| value.trim() == expected)
+            }
+            ConditionType::NotEmpty => {
+                values.iter().any(|value| !value.trim().is_empty())
+            }
+            ConditionType::Regex(regex) => {
+                values.iter().any(|value| regex.is_match(value.trim()))
+            }
+            ConditionType::OneOf(expected) => {
+                values.iter().any(|value| expected.contains(&value.trim().to_string()))
+            }
+        }
+    }
+}
+
+struct TransientFilter {
+    includes: Vec<FilterCondition>,
+    excludes: Vec<FilterCondition>,
+}
+
+impl TransientFilter {
+    fn new() -> Self {
+        TransientFilter { includes: vec![], excludes: vec![] }
+    }
+    fn add_include(&mut self, condition: FilterCondition) {
+        self.includes.push(condition);
+    }
+    fn add_exclude(&mut self, condition: FilterCondition) {
+        self.excludes.push(condition);
+    }
+    fn is_complete(&self) -> Result<(), HCLDataError> {
+        if self.includes.is_empty() && self.excludes.is_empty() {
+            return Err(HCLDataError::InputError("Filter: found no 'include'- or 'exclude'-block in filter.".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TransientFilterCondition {
+    input: Option<HeaderValue>,
+    condition_type: Option<ConditionType>,
+}
+
+impl TransientFilterCondition {
+    pub(crate) fn new() -> Self {
+        TransientFilterCondition { input: None, condition_type: None }
+    }
+    fn add_input(&mut self, input: HeaderValue) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::InputError(format!("Filter-condition: multiple inputs. First: '{:?}', Second: '{:?}'", self.input.as_ref().unwrap(), input)));
+        }
+        self.input = Some(input);
+        Ok(())
+    }
+    fn add_condition_type(&mut self, condition_type: ConditionType) -> Result<(), HCLDataError> {
+        if self.condition_type.is_some() {
+            return Err(HCLDataError::InputError(format!("Filter-condition: only one of 'equals', 'not_empty', 'regex' or 'one_of' allowed. First: '{:?}', Second: '{:?}'", self.condition_type.as_ref().unwrap(), condition_type)));
+        }
+        self.condition_type = Some(condition_type);
+        Ok(())
+    }
+    pub(crate) fn add_attribute(&mut self, attribute: &Attribute) -> Result<(), HCLDataError> {
+        match attribute.key.as_str() {
+            "input" => {
+                self.add_input(attribute.expr.to_header_value()?)?;
+            }
+            "equals" => {
+                self.add_condition_type(ConditionType::Equals(attribute.expr.to_string_2()?))?;
+            }
+            "not_empty" => {
+                if !attribute.expr.to_bool()? {
+                    return Err(HCLDataError::InputError("Filter-condition: 'not_empty' can only be set to true; use 'exclude' with 'not_empty' to keep empty values.".to_string()));
+                }
+                self.add_condition_type(ConditionType::NotEmpty)?;
+            }
+            "regex" => {
+                let regex = Regex::new(attribute.expr.to_string_2()?.as_str())?;
+                self.add_condition_type(ConditionType::Regex(regex))?;
+            }
+            "one_of" => {
+                let mut values = vec![];
+                for expr in attribute.expr.to_vec()? {
+                    let value = match expr {
+                        Expression::String(value) => { value }
+                        Expression::Number(number) => { number.to_string() }
+                        _ => {
+                            return Err(HCLDataError::InputError(format!("Filter-condition: values of 'one_of' must be strings or numbers, but found: '{:?}'", expr)));
+                        }
+                    };
+                    values.push(value);
+                }
+                self.add_condition_type(ConditionType::OneOf(values))?;
+            }
+            _ => {
+                return Err(HCLDataError::InputError(format!("Filter-condition: unknown attribute '{}'", attribute.key.as_str())));
+            }
+        }
+        Ok(())
+    }
+    pub(crate) fn is_complete(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::InputError(format!("Filter-condition: missing 'input' in '{:?}'", self)));
+        }
+        if self.condition_type.is_none() {
+            return Err(HCLDataError::InputError(format!("Filter-condition: missing one of 'equals', 'not_empty', 'regex' or 'one_of' in '{:?}'", self)));
+        }
+        Ok(())
+    }
+}
+
+pub(crate) struct FilterWrapper(pub(crate) Block);
+
+impl FilterWrapper {
+    pub(crate) fn to_filter(&self) -> Result<Filter, HCLDataError> {
+        self.0.no_attributes()?;
+        let mut transient_filter = TransientFilter::new();
+        for block in self.0.blocks() {
+            match block.identifier.as_str() {
+                "include" => {
+                    let condition = FilterConditionWrapper(block.to_owned()).to_filter_condition()?;
+                    transient_filter.add_include(condition);
+                }
+                "exclude" => {
+                    let condition = FilterConditionWrapper(block.to_owned()).to_filter_condition()?;
+                    transient_filter.add_exclude(condition);
+                }
+                _ => {
+                    return Err(HCLDataError::InputError(format!("Filter: only 'include' or 'exclude' allowed as block-identifier, but found: '{}'", block.identifier.as_str())));
+                }
+            }
+        }
+        transient_filter.is_complete()?;
+        Ok(Filter::new(transient_filter))
+    }
+}
+
+pub(crate) struct FilterConditionWrapper(pub(crate) Block);
+
+impl FilterConditionWrapper {
+    pub(crate) fn to_filter_condition(&self) -> Result<FilterCondition, HCLDataError> {
+        self.0.no_blocks()?;
+        if !self.0.labels.is_empty() {
+            return Err(HCLDataError::InputError(format!("Filter-condition: labels are not allowed, but found: '{:?}'", self.0.labels)));
+        }
+        let mut transient_condition = TransientFilterCondition::new();
+        for attribute in self.0.attributes() {
+            transient_condition.add_attribute(attribute)?;
+        }
+        transient_condition.is_complete()?;
+        Ok(FilterCondition::new(transient_condition))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::domain::filter::FilterWrapper;
+
+    #[test]
+    fn test_filter() {
+        let block = block!(filter {
+            exclude {
+                input = "status"
+                equals = "do not publish"
+            }
+            include {
+                input = 2
+                not_empty = true
+            }
+            exclude {
+                input = "type"
+                one_of = ["internal note", "draft"]
+            }
+        });
+        let result = FilterWrapper(block).to_filter();
+        assert!(result.is_ok());
+        let filter = result.unwrap();
+        assert_eq!(filter.includes.len(), 1);
+        assert_eq!(filter.excludes.len(), 2);
+        assert!(filter.excludes[0].is_met(&vec!["do not publish".to_string()]));
+        assert!(!filter.includes[0].is_met(&vec!["".to_string()]));
+    }
+    #[test]
+    fn test_filter_two_conditions() {
+        let block = block!(filter {
+            include {
+                input = "status"
+                equals = "published"
+                regex = "^pub"
+            }
+        });
+        let result = FilterWrapper(block).to_filter();
+        assert!(result.is_err());
+    }
+}
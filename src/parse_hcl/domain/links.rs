@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::wrapper_trait::Wrapper;
+
+#[derive(Debug, Clone)]
+pub struct Links {
+    // propname of a ResLinkValue-property to the resource-name of another mapping of the same sheet
+    pub propname_to_res_name: HashMap<String, String>,
+}
+
+impl Links {
+    fn new(transient_links: TransientLinks) -> Self {
+        Links { propname_to_res_name: transient_links.propname_to_res_name }
+    }
+}
+
+struct TransientLinks {
+    propname_to_res_name: HashMap<String, String>,
+}
+
+impl TransientLinks {
+    fn new() -> Self {
+        TransientLinks { propname_to_res_name: Default::default() }
+    }
+    fn add_link(&mut self, propname: String, res_name: String) -> Result<(), HCLDataError> {
+        if self.propname_to_res_name.contains_key(&propname) {
+            return Err(HCLDataError::InputError(format!("duplicate propname '{}' found in links.", propname)));
+        }
+        self.propname_to_res_name.insert(propname, res_name);
+        Ok(())
+    }
+    fn is_complete(&self) -> Result<(), HCLDataError> {
+        if self.propname_to_res_name.is_empty() {
+            return Err(HCLDataError::InputError("links: found no links in 'links'-block.".to_string()));
+        }
+        Ok(())
+    }
+}
+
+pub(crate) struct LinksWrapper(pub(crate) hcl::Block);
+
+impl LinksWrapper {
+    pub(crate) fn to_links(&self) -> Result<Links, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_links = TransientLinks::new();
+        for attribute in self.0.attributes() {
+            transient_links.add_link(attribute.key.to_string(), attribute.expr.to_string_2()?)?;
+        }
+        transient_links.is_complete()?;
+        Ok(Links::new(transient_links))
+    }
+}
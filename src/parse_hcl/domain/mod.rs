@@ -7,4 +7,7 @@ pub(crate) mod command_path;
 pub mod parse_info;
 pub mod supplements;
 pub mod prop_supplement;
-pub mod resource_supplement;
\ No newline at end of file
+pub mod resource_supplement;
+pub mod filter;
+pub mod links;
+pub mod resource_column;
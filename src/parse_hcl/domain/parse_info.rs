@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 use crate::parse_hcl::domain::parse_info_draft::ParseInformationDraft;
+use crate::parse_hcl::domain::resource_column::ResourceColumn;
 use crate::parse_hcl::domain::supplements::Supplements;
 use crate::parse_hcl::domain::xlsx_workbook_info::XLSXWorbookInfo;
+use crate::parse_hcl::header_value::HeaderValue;
 use crate::parse_hcl::transformations::Transformations;
 
 pub struct ParseInformation {
@@ -13,7 +15,11 @@ pub struct ParseInformation {
     pub dm_path: PathBuf,
     pub set_permissions: bool,
     pub res_name_to_updates: HashMap<String, Transformations>,
-    pub res_name_to_supplements: HashMap<String, Supplements>
+    pub res_name_to_supplements: HashMap<String, Supplements>,
+    // the id-column of every resource that is deduplicated, None for the header 'id'
+    pub res_name_to_deduplicate: HashMap<String, Option<HeaderValue>>,
+    pub res_name_to_group_by: HashMap<String, HeaderValue>,
+    pub res_name_to_resource_column: HashMap<String, ResourceColumn>,
 }
 
 impl ParseInformation {
@@ -27,6 +33,9 @@ impl ParseInformation {
             set_permissions: p_i_draft.set_permissions,
             res_name_to_updates: p_i_draft.res_name_to_updates,
             res_name_to_supplements: p_i_draft.res_name_to_supplements,
+            res_name_to_deduplicate: p_i_draft.res_name_to_deduplicate,
+            res_name_to_group_by: p_i_draft.res_name_to_group_by,
+            res_name_to_resource_column: p_i_draft.res_name_to_resource_column,
         }
     }
 }
@@ -1,29 +1,34 @@
-use std::collections::{HashMap};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use hcl::{Body, Expression};
 use crate::parse_hcl::domain::command::{ParseInfoCommandWrapper};
 use crate::parse_hcl::domain::command_path::CommandOrPath;
+use crate::parse_hcl::domain::resource_column::ResourceColumn;
 use crate::parse_hcl::domain::supplements::Supplements;
 use crate::parse_hcl::domain::xlsx_workbook_info::{XLSXWorbookInfo, XLSXWorkbookInfoWrapper};
 use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::HeaderValue;
 use crate::parse_hcl::transformations::Transformations;
 
 pub struct ParseInformationDraft {
-    //pub shortcode: String,
+    pub shortcode: Option<String>,
     pub rel_path_to_xlsx_workbooks:HashMap<String, XLSXWorbookInfo>,
     pub res_folder: PathBuf,
     pub separator: String,
     pub dm_path: CommandOrPath,
     pub set_permissions: bool,
     pub res_name_to_updates: HashMap<String, Transformations>,
-    pub res_name_to_supplements: HashMap<String, Supplements>
+    pub res_name_to_supplements: HashMap<String, Supplements>,
+    pub res_name_to_deduplicate: HashMap<String, Option<HeaderValue>>,
+    pub res_name_to_group_by: HashMap<String, HeaderValue>,
+    pub res_name_to_resource_column: HashMap<String, ResourceColumn>,
 }
 
 
 impl ParseInformationDraft{
     fn new(transient_parse_information: TransientParseInformation) -> Self {
         ParseInformationDraft {
-            //shortcode: transient_parse_information.shortcode.unwrap(),
+            shortcode: transient_parse_information.shortcode,
             rel_path_to_xlsx_workbooks: transient_parse_information.rel_path_to_xlsx_wb_info,
             res_folder: transient_parse_information.res_folder.unwrap(),
             separator: transient_parse_information.separator.unwrap(),
@@ -31,6 +36,9 @@ impl ParseInformationDraft{
             set_permissions: transient_parse_information.permissions_set.unwrap(),
             res_name_to_updates: transient_parse_information.res_name_to_updates,
             res_name_to_supplements: transient_parse_information.res_name_to_supplements,
+            res_name_to_deduplicate: transient_parse_information.res_name_to_deduplicate,
+            res_name_to_group_by: transient_parse_information.res_name_to_group_by,
+            res_name_to_resource_column: transient_parse_information.res_name_to_resource_column,
         }
     }
 }
@@ -43,27 +51,16 @@ impl TryFrom<hcl::Body> for ParseInformationDraft {
         let blocks: Vec<&hcl::Block> = body.blocks().collect();
         for attribute in attributes.iter() {
             match attribute.key.as_str() {
-                /*
                 "shortcode" => {
                     match attribute.expr.to_owned() {
                         Expression::String(shortcode) => {
-                            match shortcode.parse::<i32>(){
-                                Err(_) => {
-                                    HCLDataError::ParseInt(format!("cannot parse shortcode '{}' to usize", shortcode));
-                                }
-                                Ok(_) => {
-                                    // do nothing
-                                }
-                            }
                             transient_parse_info.add_shortcode(shortcode)?;
-
                         }
                         _ => {
-                            return Err(HCLDataError::InputError(format!("parse-info-hcl: shortcode is not a Expression::Number: '{}'", attribute.expr)));
+                            return Err(HCLDataError::InputError(format!("parse-info-hcl: shortcode is not a Expression::String: '{}'", attribute.expr)));
                         }
                     }
                 }
-                 */
                 "set_permissions" => {
                     match attribute.expr.to_owned() {
                         Expression::Bool(set_permissions) => {
@@ -75,7 +72,7 @@ impl TryFrom<hcl::Body> for ParseInformationDraft {
                         }
                     }
                 }
-                "resources_folder_path" => {
+                "resources_folder_path" | "resources_folder" => {
                     match attribute.expr.to_owned() {
                         Expression::String(res_folder) => {
                             transient_parse_info.add_res_folder(PathBuf::from(res_folder))?;
@@ -97,7 +94,7 @@ impl TryFrom<hcl::Body> for ParseInformationDraft {
                         }
                     }
                 }
-                "datamodel_path" => {
+                "datamodel_path" | "datamodel" => {
                     let command_or_path = match attribute.expr.to_owned() {
                         Expression::String(path) => {
                             CommandOrPath::new_path(PathBuf::from(path))
@@ -123,11 +120,33 @@ impl TryFrom<hcl::Body> for ParseInformationDraft {
                     let xlsx_workbook: XLSXWorbookInfo = XLSXWorkbookInfoWrapper {0: block.to_owned().to_owned()}.to_wb_info()?;
 
                     let res_name_to_suppl = xlsx_workbook.sheet_infos
-                        .iter()
-                        .filter(|(sheet_nr, sheet_info)| sheet_info.supplements.is_some())
-                        .map(|(sheet_nr, sheet_info)| (sheet_info.resource_name.to_owned(), sheet_info.supplements.as_ref().unwrap().to_owned()))
+                        .values()
+                        .flatten()
+                        .filter(|sheet_info| sheet_info.supplements.is_some())
+                        .map(|sheet_info| (sheet_info.resource_name.to_owned(), sheet_info.supplements.as_ref().unwrap().to_owned()))
                         .collect::<Vec<(String, Supplements)>>();
                     transient_parse_info.add_res_name_to_suppl(res_name_to_suppl)?;
+                    let res_name_to_deduplicate = xlsx_workbook.sheet_infos
+                        .values()
+                        .flatten()
+                        .filter(|sheet_info| sheet_info.deduplicate)
+                        .map(|sheet_info| (sheet_info.resource_name.to_owned(), sheet_info.id.to_owned()))
+                        .collect::<Vec<(String, Option<HeaderValue>)>>();
+                    transient_parse_info.add_res_name_to_deduplicate(res_name_to_deduplicate)?;
+                    let res_name_to_group_by = xlsx_workbook.sheet_infos
+                        .values()
+                        .flatten()
+                        .filter(|sheet_info| sheet_info.group_by.is_some())
+                        .map(|sheet_info| (sheet_info.resource_name.to_owned(), sheet_info.group_by.as_ref().unwrap().to_owned()))
+                        .collect::<Vec<(String, HeaderValue)>>();
+                    transient_parse_info.add_res_name_to_group_by(res_name_to_group_by)?;
+                    let res_name_to_resource_column = xlsx_workbook.sheet_infos
+                        .values()
+                        .flatten()
+                        .filter(|sheet_info| sheet_info.resource_column.is_some())
+                        .map(|sheet_info| (sheet_info.resource_name.to_owned(), sheet_info.resource_column.as_ref().unwrap().to_owned()))
+                        .collect::<Vec<(String, ResourceColumn)>>();
+                    transient_parse_info.add_res_name_to_resource_column(res_name_to_resource_column)?;
                     transient_parse_info.add_xlsx_workbook(xlsx_workbook)?;
                 }
                 _ => {
@@ -148,7 +167,10 @@ struct TransientParseInformation {
     dm_path: Option<CommandOrPath>,
     permissions_set: Option<bool>,
     res_name_to_updates:  HashMap<String, Transformations>,
-    res_name_to_supplements: HashMap<String, Supplements>
+    res_name_to_supplements: HashMap<String, Supplements>,
+    res_name_to_deduplicate: HashMap<String, Option<HeaderValue>>,
+    res_name_to_group_by: HashMap<String, HeaderValue>,
+    res_name_to_resource_column: HashMap<String, ResourceColumn>,
 }
 
 impl TransientParseInformation {
@@ -156,7 +178,7 @@ impl TransientParseInformation {
 
 impl TransientParseInformation {
     fn new() -> Self {
-        TransientParseInformation { shortcode: None, rel_path_to_xlsx_wb_info: Default::default(), res_folder: None, separator: None, dm_path: None, permissions_set: None, res_name_to_updates: Default::default(), res_name_to_supplements: Default::default() }
+        TransientParseInformation { shortcode: None, rel_path_to_xlsx_wb_info: Default::default(), res_folder: None, separator: None, dm_path: None, permissions_set: None, res_name_to_updates: Default::default(), res_name_to_supplements: Default::default(), res_name_to_deduplicate: Default::default(), res_name_to_group_by: Default::default(), res_name_to_resource_column: Default::default() }
     }
     pub(crate) fn add_res_name_to_suppl(&mut self, res_name_suppl: Vec<(String, Supplements)>) -> Result<(), HCLDataError> {
         for (res_name, supplements) in res_name_suppl {
@@ -168,6 +190,33 @@ impl TransientParseInformation {
         }
         Ok(())
     }
+    pub(crate) fn add_res_name_to_resource_column(&mut self, res_name_resource_column: Vec<(String, ResourceColumn)>) -> Result<(), HCLDataError> {
+        for (res_name, resource_column) in res_name_resource_column {
+            if self.res_name_to_resource_column.contains_key(&res_name) {
+                return Err(HCLDataError::InputError(format!("Found same res-name '{}' multiple times as key for 'res_name_to_resource_column'", res_name)));
+            }
+            self.res_name_to_resource_column.insert(res_name, resource_column);
+        }
+        Ok(())
+    }
+    pub(crate) fn add_res_name_to_group_by(&mut self, res_name_group_by: Vec<(String, HeaderValue)>) -> Result<(), HCLDataError> {
+        for (res_name, group_by) in res_name_group_by {
+            if self.res_name_to_group_by.contains_key(&res_name) {
+                return Err(HCLDataError::InputError(format!("Found same res-name '{}' multiple times as key for 'res_name_to_group_by'", res_name)));
+            }
+            self.res_name_to_group_by.insert(res_name, group_by);
+        }
+        Ok(())
+    }
+    pub(crate) fn add_res_name_to_deduplicate(&mut self, res_name_deduplicate: Vec<(String, Option<HeaderValue>)>) -> Result<(), HCLDataError> {
+        for (res_name, id) in res_name_deduplicate {
+            if self.res_name_to_deduplicate.contains_key(&res_name) {
+                return Err(HCLDataError::InputError(format!("Found same res-name '{}' multiple times as key for 'res_name_to_deduplicate'", res_name)));
+            }
+            self.res_name_to_deduplicate.insert(res_name, id);
+        }
+        Ok(())
+    }
     pub(crate) fn add_shortcode(&mut self, shortcode: String) -> Result<(), HCLDataError> {
         if self.shortcode.is_some() {
             return Err(HCLDataError::InputError("parse-info-hcl: shortcode has a duplicate.".to_string()));
@@ -227,16 +276,17 @@ impl TransientParseInformation {
         if self.separator.is_none() {
             return Err(HCLDataError::InputError("'separator' not found.".to_string()))
         }
-        /*
         if self.dm_path.is_none() {
-            return Err(HCLDataError::InputError("'dm_path' not found.".to_string()))
+            return Err(HCLDataError::InputError("'datamodel' not found. Must be a path or 'cmd.find'.".to_string()))
+        }
+        if matches!(self.dm_path, Some(CommandOrPath::Command(_))) && self.shortcode.is_none() {
+            return Err(HCLDataError::InputError("'datamodel = cmd.find' needs the 'shortcode' of the project to find the datamodel.".to_string()))
         }
-        */
         Ok(())
     }
     pub(crate) fn add_updates(&mut self) {
         for (_, wb_info) in self.rel_path_to_xlsx_wb_info.iter() {
-            for (_, sheet_info) in wb_info.sheet_infos.iter() {
+            for sheet_info in wb_info.sheet_infos.values().flatten() {
                 if sheet_info.transformations.is_some() {
                     self.res_name_to_updates.insert(sheet_info.resource_name.to_owned(), sheet_info.transformations.as_ref().unwrap().to_owned());
                 }
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+use hcl::FuncCall;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+
+#[derive(Debug, Clone)]
+pub struct ResourceColumn {
+    // column that contains the resource-class of each row
+    pub input: HeaderValue,
+    pub value_to_class: HashMap<String, String>,
+}
+
+impl ResourceColumn {
+    pub(crate) fn new(input: HeaderValue, value_to_class: Option<HashMap<String, String>>) -> Self {
+        ResourceColumn { input, value_to_class: value_to_class.unwrap_or_default() }
+    }
+    pub(crate) fn mapping_name(&self) -> String {
+        // resource-mappings are identified by their resource-name, a mapping by column is named after the column
+        match &self.input {
+            HeaderValue::Name(name) => { format!("column({})", name) }
+            HeaderValue::Number(number) => { format!("column({})", number) }
+        }
+    }
+    pub(crate) fn class(&self, value: &str) -> String {
+        // values without entry in the map are taken as class-name
+        match self.value_to_class.get(value) {
+            None => { value.to_string() }
+            Some(class) => { class.to_owned() }
+        }
+    }
+}
+
+pub(crate) struct ResourceColumnWrapper(pub(crate) FuncCall);
+
+impl ResourceColumnWrapper {
+    pub(crate) fn to_input(&self) -> Result<HeaderValue, HCLDataError> {
+        if self.0.name.name.as_str() != "column" {
+            return Err(HCLDataError::InputError(format!("resource: only the function 'column' is allowed, but found: '{}'", self.0.name.name.as_str())));
+        }
+        if self.0.args.len() != 1 {
+            return Err(HCLDataError::InputError(format!("resource: 'column' takes exactly one header as argument, but found: '{:?}'", self.0.args)));
+        }
+        self.0.args.get(0).unwrap().to_header_value()
+    }
+}
@@ -1,25 +1,47 @@
+use std::collections::HashMap;
 use hcl::{BlockLabel, Expression};
 use crate::parse_hcl::domain::assignments::{Assignments, AssignmentsWrapper};
+use crate::parse_hcl::domain::filter::{Filter, FilterWrapper};
+use crate::parse_hcl::domain::links::{Links, LinksWrapper};
+use crate::parse_hcl::domain::resource_column::{ResourceColumn, ResourceColumnWrapper};
 use crate::parse_hcl::domain::supplements::{Supplements, SupplementsWrapper};
+use crate::expression_trait::ExpressionTransform;
 use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::normalize_method::Normalization;
 use crate::parse_hcl::transformations::{Transformations, TransformationsWrapper};
 use crate::parse_hcl::wrapper_trait::Wrapper;
 
 pub struct SheetInfo {
     pub sheet_nr: usize,
     pub resource_name: String,
+    pub resource_column: Option<ResourceColumn>,
     pub assignments: Assignments,
     pub transformations: Option<Transformations>,
-    pub supplements: Option<Supplements>
+    pub supplements: Option<Supplements>,
+    pub filter: Option<Filter>,
+    pub links: Option<Links>,
+    pub deduplicate: bool,
+    pub group_by: Option<HeaderValue>,
+    pub normalize: Option<Normalization>,
+    // column of the resource-id that deduplicate and links use; without it the header 'id'
+    pub id: Option<HeaderValue>,
 }
 impl SheetInfo {
     fn new(transient_sheet_info: TransientSheetInfo) -> Self {
         SheetInfo{
             sheet_nr: transient_sheet_info.sheet_number,
             resource_name: transient_sheet_info.resource_name.unwrap(),
+            resource_column: transient_sheet_info.resource_column,
             assignments: transient_sheet_info.assignments.unwrap(),
             transformations: transient_sheet_info.transformations,
-            supplements: transient_sheet_info.supplements
+            supplements: transient_sheet_info.supplements,
+            filter: transient_sheet_info.filter,
+            links: transient_sheet_info.links,
+            deduplicate: transient_sheet_info.deduplicate.unwrap_or(false),
+            group_by: transient_sheet_info.group_by,
+            normalize: transient_sheet_info.normalize,
+            id: transient_sheet_info.id,
         }
     }
 }
@@ -27,9 +49,17 @@ impl SheetInfo {
 struct TransientSheetInfo {
     sheet_number: usize,
     resource_name: Option<String>,
+    resource_column: Option<ResourceColumn>,
+    resource_map: Option<HashMap<String, String>>,
     assignments: Option<Assignments>,
     transformations: Option<Transformations>,
-    supplements: Option<Supplements>
+    supplements: Option<Supplements>,
+    filter: Option<Filter>,
+    links: Option<Links>,
+    deduplicate: Option<bool>,
+    group_by: Option<HeaderValue>,
+    normalize: Option<Normalization>,
+    id: Option<HeaderValue>,
 }
 
 impl TransientSheetInfo {
@@ -37,9 +67,17 @@ impl TransientSheetInfo {
         TransientSheetInfo{
             sheet_number: sheet_nr,
             resource_name: None,
+            resource_column: None,
+            resource_map: None,
             assignments: None,
             transformations: None,
             supplements: None,
+            filter: None,
+            links: None,
+            deduplicate: None,
+            group_by: None,
+            normalize: None,
+            id: None,
         }
     }
     pub(crate) fn add_res_name(&mut self, res_name: String) -> Result<(), HCLDataError> {
@@ -49,6 +87,19 @@ impl TransientSheetInfo {
         self.resource_name = Option::Some(res_name);
         Ok(())
     }
+    pub(crate) fn add_res_column(&mut self, input: HeaderValue) -> Result<(), HCLDataError> {
+        let resource_column = ResourceColumn::new(input, None);
+        self.add_res_name(resource_column.mapping_name())?;
+        self.resource_column = Some(resource_column);
+        Ok(())
+    }
+    pub(crate) fn add_res_map(&mut self, resource_map: HashMap<String, String>) -> Result<(), HCLDataError> {
+        if self.resource_map.is_some() {
+            return Err(HCLDataError::InputError(format!("multiple resource_maps: First: '{:?}', Second: '{:?}'", self.resource_map.as_ref().unwrap(), resource_map)));
+        }
+        self.resource_map = Some(resource_map);
+        Ok(())
+    }
     pub(crate) fn add_supplement(&mut self, supplements: Supplements) -> Result<(), HCLDataError> {
         if self.supplements.is_some() {
             return Err(HCLDataError::InputError(format!("multiple supplements: First: '{:?}', Second: '{:?}'", self.supplements, supplements)));
@@ -70,6 +121,48 @@ impl TransientSheetInfo {
         self.transformations = Option::Some(transformations);
         Ok(())
     }
+    pub(crate) fn add_filter(&mut self, filter: Filter) -> Result<(), HCLDataError> {
+        if self.filter.is_some() {
+            return Err(HCLDataError::InputError(format!("multiple filters provided: First: '{:?}', Second: '{:?}'", self.filter.as_ref().unwrap(), filter)));
+        }
+        self.filter = Some(filter);
+        Ok(())
+    }
+    pub(crate) fn add_links(&mut self, links: Links) -> Result<(), HCLDataError> {
+        if self.links.is_some() {
+            return Err(HCLDataError::InputError(format!("multiple links provided: First: '{:?}', Second: '{:?}'", self.links.as_ref().unwrap(), links)));
+        }
+        self.links = Some(links);
+        Ok(())
+    }
+    pub(crate) fn add_deduplicate(&mut self, deduplicate: bool) -> Result<(), HCLDataError> {
+        if self.deduplicate.is_some() {
+            return Err(HCLDataError::InputError(format!("multiple declaration of deduplicate in sheet '{}'", self.sheet_number)));
+        }
+        self.deduplicate = Some(deduplicate);
+        Ok(())
+    }
+    pub(crate) fn add_id(&mut self, id: HeaderValue) -> Result<(), HCLDataError> {
+        if self.id.is_some() {
+            return Err(HCLDataError::InputError(format!("multiple declaration of id in sheet '{}'", self.sheet_number)));
+        }
+        self.id = Some(id);
+        Ok(())
+    }
+    pub(crate) fn add_group_by(&mut self, group_by: HeaderValue) -> Result<(), HCLDataError> {
+        if self.group_by.is_some() {
+            return Err(HCLDataError::InputError(format!("multiple declaration of group_by in sheet '{}'", self.sheet_number)));
+        }
+        self.group_by = Some(group_by);
+        Ok(())
+    }
+    pub(crate) fn add_normalize(&mut self, normalize: Normalization) -> Result<(), HCLDataError> {
+        if self.normalize.is_some() {
+            return Err(HCLDataError::InputError(format!("multiple declaration of normalize in sheet '{}'", self.sheet_number)));
+        }
+        self.normalize = Some(normalize);
+        Ok(())
+    }
     pub(crate) fn is_complete(&self) -> Result<(), HCLDataError> {
         if self.resource_name.is_none() {
             return Err(HCLDataError::InputError("Resource name is missing.".to_string()))
@@ -77,6 +170,12 @@ impl TransientSheetInfo {
         if self.assignments.is_none() {
             return Err(HCLDataError::InputError("Assignments is missing.".to_string()))
         }
+        if self.resource_map.is_some() && self.resource_column.is_none() {
+            return Err(HCLDataError::InputError(format!("resource_map can only be used with 'resource = column(...)', but resource is '{}'.", self.resource_name.as_ref().unwrap())))
+        }
+        if self.group_by.is_some() && self.deduplicate == Some(true) {
+            return Err(HCLDataError::InputError(format!("deduplicate and group_by cannot be used together in sheet '{}'.", self.sheet_number)))
+        }
         Ok(())
     }
 }
@@ -90,13 +189,34 @@ impl SheetInfoWrapper {
         for attribute in self.0.attributes() {
             match attribute.key.as_str() {
                 "resource" => {
-                    let resource_name = match &attribute.expr {
-                        Expression::String(value) => {value}
+                    match &attribute.expr {
+                        Expression::String(value) => {
+                            transient_sheet_info.add_res_name(value.to_owned())?;
+                        }
+                        Expression::FuncCall(func_call) => {
+                            // resource-class is chosen per row from a column
+                            let input = ResourceColumnWrapper(*func_call.to_owned()).to_input()?;
+                            transient_sheet_info.add_res_column(input)?;
+                        }
                         _ => {
-                            return Err(HCLDataError::InputError(format!("Value of 'resource' should be a String, but found something else: {:?}", attribute.expr)));
+                            return Err(HCLDataError::InputError(format!("Value of 'resource' should be a String or 'column(...)', but found something else: {:?}", attribute.expr)));
                         }
                     };
-                    transient_sheet_info.add_res_name(resource_name.to_owned())?;
+                }
+                "resource_map" => {
+                    transient_sheet_info.add_res_map(attribute.expr.to_string_map()?)?;
+                }
+                "deduplicate" => {
+                    transient_sheet_info.add_deduplicate(attribute.expr.to_bool()?)?;
+                }
+                "group_by" => {
+                    transient_sheet_info.add_group_by(attribute.expr.to_header_value()?)?;
+                }
+                "id" => {
+                    transient_sheet_info.add_id(attribute.expr.to_header_value()?)?;
+                }
+                "normalize" => {
+                    transient_sheet_info.add_normalize(Normalization::from_steps(&attribute.expr)?)?;
                 }
                 _ => {
                     return Err(HCLDataError::InputError(format!("found unknown attribute-key '{}' in sheet-info.", attribute.key.as_str())));
@@ -118,6 +238,14 @@ impl SheetInfoWrapper {
                     let transformations = TransformationsWrapper(block.to_owned()).to_transformations()?;
                     transient_sheet_info.add_transformations(transformations)?;
                 }
+                "filter" => {
+                    let filter = FilterWrapper(block.to_owned()).to_filter()?;
+                    transient_sheet_info.add_filter(filter)?;
+                }
+                "links" => {
+                    let links = LinksWrapper(block.to_owned()).to_links()?;
+                    transient_sheet_info.add_links(links)?;
+                }
                 _ => {
                     return Err(HCLDataError::InputError(format!("found unknown block-identifier '{}' for attribute in sheet-info.", block.identifier.as_str())));
                 }
@@ -125,6 +253,9 @@ impl SheetInfoWrapper {
 
         }
         transient_sheet_info.is_complete()?;
+        if transient_sheet_info.resource_map.is_some() {
+            transient_sheet_info.resource_column.as_mut().unwrap().value_to_class = transient_sheet_info.resource_map.take().unwrap();
+        }
         Ok(SheetInfo::new(transient_sheet_info))
     }
     fn collect_sheet_nr(&self) -> Result<usize, HCLDataError> {
@@ -1,12 +1,12 @@
 use std::collections::{HashMap, HashSet};
 use crate::parse_hcl::domain::xlsx_sheet_info::{SheetInfo, SheetInfoWrapper};
 use crate::parse_hcl::errors::HCLDataError;
-use crate::parse_hcl::methods_domain::create_method::CreateMethod;
 use crate::parse_hcl::wrapper_trait::Wrapper;
 
 pub struct XLSXWorbookInfo {
     pub rel_path:String,
-    pub sheet_infos: HashMap<usize, SheetInfo>,
+    // a sheet can be mapped to multiple resources, thus every sheet-nr has one or more sheet-infos
+    pub sheet_infos: HashMap<usize, Vec<SheetInfo>>,
 }
 
 impl XLSXWorbookInfo {
@@ -19,13 +19,14 @@ pub(crate) struct XLSXWorkbookInfoWrapper(pub(crate) hcl::Block);
 
 struct TransientXLSXWorkbook {
     rel_path: String,
-    sheet_infos: HashMap<usize, SheetInfo>,
+    sheet_infos: HashMap<usize, Vec<SheetInfo>>,
 }
 
 impl TransientXLSXWorkbook {
     pub(crate) fn no_duplicates(&self) -> Result<(), HCLDataError> {
         self.no_duplicate_res_names()?;
         self.no_duplicate_output_names()?;
+        self.links_exist()?;
         Ok(())
     }
 }
@@ -38,10 +39,7 @@ impl TransientXLSXWorkbook {
         self.rel_path = rel_path;
     }
     pub(crate) fn add_sheet_info(&mut self, sheet_info: SheetInfo) -> Result<(), HCLDataError> {
-        if self.sheet_infos.contains_key(&sheet_info.sheet_nr) {
-            return Err(HCLDataError::InputError(format!("Found")));
-        }
-        self.sheet_infos.insert(sheet_info.sheet_nr, sheet_info);
+        self.sheet_infos.entry(sheet_info.sheet_nr).or_insert_with(Vec::new).push(sheet_info);
         Ok(())
 
     }
@@ -57,40 +55,50 @@ impl TransientXLSXWorkbook {
     }
     fn no_duplicate_output_names(&self) -> Result<(), HCLDataError> {
         // check that all output-names in assignments  transform don't have any duplicates
-        let mut output_names = vec![];
-        for (_, sheet_info) in self.sheet_infos.iter() {
+        // every resource-mapping has its own headers, so duplicates are checked per sheet-info
+        for sheet_info in self.sheet_infos.values().flatten() {
+            let mut output_names = vec![];
             sheet_info.assignments.propname_to_header.iter().for_each(|(propname, _)|output_names.push(propname));
             if sheet_info.transformations.is_some() {
                 let transformations = sheet_info.transformations.as_ref().unwrap();
                 transformations.replace_label_name_methods.iter().for_each(|method|output_names.push(&method.output));
                 transformations.replace_methods.iter().for_each(|method|output_names.push(&method.output));
                 transformations.alter_methods.iter().for_each(|method|output_names.push(&method.output));
-                for create_method in transformations.create_methods.iter() {
-                    match create_method {
-                        CreateMethod::IntegerCreateMethod(int_create) => {
-                            output_names.push(&int_create.output);
-                        }
-                        CreateMethod::PermissionsCreateMethod(permissions_create) => {
-                            output_names.push(&permissions_create.output);
-                        }
-                    }
-
-                }
+                transformations.create_methods.iter().for_each(|method|output_names.push(method.output()));
                 transformations.identify_methods.iter().for_each(|method|output_names.push(&method.output));
                 transformations.to_date_methods.iter().for_each(|method|output_names.push(&method.output));
                 transformations.combine_methods.iter().for_each(|method|output_names.push(&method.output));
                 transformations.upper_methods.iter().for_each(|method|output_names.push(&method.output));
                 transformations.lower_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.case_methods.iter().for_each(|method|output_names.push(&method.output));
                 transformations.update_with_server_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.map_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.normalize_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.split_methods.iter().for_each(|method|output_names.extend(method.outputs()));
+                transformations.distinct_methods.iter().for_each(|method|output_names.extend(method.outputs()));
+                transformations.to_number_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.to_boolean_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.to_color_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.to_time_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.to_geoname_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.slugify_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.to_richtext_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.to_uri_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.script_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.join_methods.iter().for_each(|method|output_names.push(&method.output));
+                transformations.extract_methods.iter().for_each(|method|output_names.extend(method.outputs.iter()));
 
             }
-        }
-        match Self::check_for_duplicates(output_names){
-            None => {
-                //ignore
+            if sheet_info.links.is_some() {
+                sheet_info.links.as_ref().unwrap().propname_to_res_name.iter().for_each(|(propname, _)|output_names.push(propname));
             }
-            Some(duplicate) => {
-                return Err(HCLDataError::InputError(format!("Duplicate prop-names '{}' found in sheet-infos of xlsx '{}'", duplicate, self.rel_path)));
+            match Self::check_for_duplicates(output_names){
+                None => {
+                    //ignore
+                }
+                Some(duplicate) => {
+                    return Err(HCLDataError::InputError(format!("Duplicate prop-names '{}' found in sheet-info of resource '{}' in xlsx '{}'", duplicate, sheet_info.resource_name, self.rel_path)));
+                }
             }
         }
         Ok(())
@@ -98,7 +106,7 @@ impl TransientXLSXWorkbook {
 
     }
     fn no_duplicate_res_names(&self) -> Result<(), HCLDataError> {
-        let resource_names = self.sheet_infos.iter().map(|(_, sheet_info)|&sheet_info.resource_name).collect::<Vec<_>>();
+        let resource_names = self.sheet_infos.values().flatten().map(|sheet_info|&sheet_info.resource_name).collect::<Vec<_>>();
         match Self::check_for_duplicates(resource_names){
             None => {
                 //ignore
@@ -109,6 +117,22 @@ impl TransientXLSXWorkbook {
         }
         Ok(())
     }
+    fn links_exist(&self) -> Result<(), HCLDataError> {
+        // links can only point to another resource-mapping of the same sheet
+        for (sheet_nr, sheet_infos) in self.sheet_infos.iter() {
+            for sheet_info in sheet_infos.iter().filter(|sheet_info|sheet_info.links.is_some()) {
+                for (propname, res_name) in sheet_info.links.as_ref().unwrap().propname_to_res_name.iter() {
+                    if res_name.eq(&sheet_info.resource_name) {
+                        return Err(HCLDataError::InputError(format!("Link '{}' of resource '{}' points to itself in sheet '{}' of xlsx '{}'", propname, res_name, sheet_nr, self.rel_path)));
+                    }
+                    if !sheet_infos.iter().any(|other|other.resource_name.eq(res_name)) {
+                        return Err(HCLDataError::InputError(format!("Link '{}' of resource '{}' points to resource '{}', but sheet '{}' of xlsx '{}' has no mapping for this resource. Existing mappings: {:?}", propname, sheet_info.resource_name, res_name, sheet_nr, self.rel_path, sheet_infos.iter().map(|other|&other.resource_name).collect::<Vec<_>>())));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 
@@ -2,6 +2,7 @@ use hcl::{Block, Expression};
 use crate::expression_trait::ExpressionTransform;
 use crate::parse_hcl::errors::HCLDataError;
 use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::when::{When, WrapperWhen};
 use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
 
 pub struct WrapperCombineMethod (pub(crate) Block);
@@ -12,6 +13,10 @@ struct TransientStructureCombineMethod {
     middle: Option<String>,
     prefix: Option<String>,
     suffix: Option<String>,
+    template: Option<String>,
+    empty: Option<String>,
+    multiple: Option<String>,
+    when: Option<When>,
 }
 
 impl TransientStructureCombineMethod {
@@ -22,14 +27,18 @@ impl TransientStructureCombineMethod {
             middle: None,
             prefix: None,
             suffix: None,
+            template: None,
+            empty: None,
+            multiple: None,
+            when: None,
         }
     }
     pub(crate) fn add_input(&mut self, input: Vec<HeaderValue>) -> Result<(), HCLDataError> {
         if self.input.is_some() {
             return Err(HCLDataError::ParsingError(format!("method: '{:?}' has multiple input-attributes", self)));
         }
-        if input.len() != 2 {
-            return Err(HCLDataError::ParsingError(format!("error in combine-method: Input array '{:?}' doesn't have exactly two entries.", input)))
+        if input.is_empty() {
+            return Err(HCLDataError::ParsingError(format!("error in combine-method: Input array of '{}' is empty.", self.output)))
         }
         self.input = Option::from(input);
         Ok(())
@@ -55,15 +64,52 @@ impl TransientStructureCombineMethod {
         self.suffix = Option::from(suffix);
         Ok(())
     }
+    pub(crate) fn add_template(&mut self, template: String) -> Result<(), HCLDataError>{
+        if self.template.is_some() {
+            return Err(HCLDataError::ParsingError(format!("method: '{:?}' has multiple template-attributes", self)));
+        }
+        self.template = Option::from(template);
+        Ok(())
+    }
+    pub(crate) fn add_empty(&mut self, empty: String) -> Result<(), HCLDataError>{
+        if self.empty.is_some() {
+            return Err(HCLDataError::ParsingError(format!("method: '{:?}' has multiple empty-attributes", self)));
+        }
+        self.empty = Option::from(empty);
+        Ok(())
+    }
+    pub(crate) fn add_multiple(&mut self, multiple: String) -> Result<(), HCLDataError>{
+        if self.multiple.is_some() {
+            return Err(HCLDataError::ParsingError(format!("method: '{:?}' has multiple multiple-attributes", self)));
+        }
+        self.multiple = Option::from(multiple);
+        Ok(())
+    }
 
+    pub(crate) fn add_when(&mut self, when: When) -> Result<(), HCLDataError> {
+        if self.when.is_some() {
+            return Err(HCLDataError::ParsingError(format!("method: '{:?}' has multiple when-blocks", self)));
+        }
+        self.when = Some(when);
+        Ok(())
+    }
     pub(crate) fn is_consistent(&self) -> Result<(), HCLDataError> {
         if self.input.is_none() {
             return Err(HCLDataError::ParsingError(format!("combine-method: '{:?}' doesn't have an input-attribute provided", self)));
         }
-        if self.middle.is_none() {
-            return Err(HCLDataError::ParsingError(format!("combine-method: '{:?}' doesn't have a middle provided", self)));
+        if self.template.is_some() {
+            if self.middle.is_some() {
+                return Err(HCLDataError::ParsingError(format!("combine-method: '{:?}' has a template and a middle, only one is allowed", self)));
+            }
+        } else {
+            if self.middle.is_none() {
+                return Err(HCLDataError::ParsingError(format!("combine-method: '{:?}' doesn't have a middle or a template provided", self)));
+            }
+            if self.input.as_ref().unwrap().len() < 2 {
+                return Err(HCLDataError::ParsingError(format!("combine-method: '{:?}' needs at least two inputs without a template", self)));
+            }
         }
-        // suffix, prefix are optional
+        // suffix, prefix, empty and multiple are optional
         Ok(())
     }
 }
@@ -73,7 +119,6 @@ impl WrapperCombineMethod {
 
     pub(crate) fn to_combine_method(&self) -> Result<CombineMethod, HCLDataError> {
         let mut transient_structure = TransientStructureCombineMethod::new(self.0.get_output()?);
-        self.0.no_blocks()?;
         for attribute in self.0.attributes() {
             match attribute.key.as_str() {
                 "input" => {
@@ -89,14 +134,33 @@ impl WrapperCombineMethod {
                 "suffix" => {
                     transient_structure.add_suffix(attribute.expr.to_string_2()?)?;
                 }
+                "template" => {
+                    transient_structure.add_template(attribute.expr.to_string_2()?)?;
+                }
+                "empty" => {
+                    transient_structure.add_empty(attribute.expr.to_string_2()?)?;
+                }
+                "multiple" => {
+                    transient_structure.add_multiple(attribute.expr.to_string_2()?)?;
+                }
                 _ => {
                     return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
                 }
             }
 
         }
+        for block in self.0.blocks() {
+            match block.identifier.as_str() {
+                "when" => {
+                    transient_structure.add_when(WrapperWhen(block.to_owned()).to_when()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown block '{:?}' in method '{:?}'.", block, transient_structure.output)));
+                }
+            }
+        }
         transient_structure.is_consistent()?;
-        let combine_method = CombineMethod::new(transient_structure);
+        let combine_method = CombineMethod::new(transient_structure)?;
         Ok(combine_method)
     }
 }
@@ -104,8 +168,10 @@ impl WrapperCombineMethod {
 fn parse_input(input: Expression) -> Result<Vec<HeaderValue>, HCLDataError>{
     match input {
         Expression::Array(array) => {
-            let str_vec:Vec<HeaderValue> = array.iter().map(|expr|expr.to_header_value().unwrap()).collect();
-
+            let mut str_vec:Vec<HeaderValue> = vec![];
+            for expr in array.iter() {
+                str_vec.push(expr.to_header_value()?);
+            }
             Ok(str_vec)
         }
         _ => {
@@ -121,18 +187,98 @@ pub struct CombineMethod{
     pub middle: Option<String>,
     pub prefix: Option<String>,
     pub suffix: Option<String>,
+    pub template: Option<Vec<TemplatePart>>,
+    pub empty: EmptyInput,
+    pub multiple: MultipleValues,
+    pub when: Option<When>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplatePart {
+    Text(String),
+    // position of the input in the input-array
+    Input(usize),
+    // text in square brackets, left out if one of its inputs is empty and empty is "drop"
+    Optional(Vec<TemplatePart>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmptyInput {
+    Keep,
+    Drop,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MultipleValues {
+    // the n-th values are combined with each other, single values are combined with every value
+    Pairwise,
+    // every value is combined with every value of the other inputs
+    Product,
 }
 
 
 impl CombineMethod {
-    fn new(transient_structure: TransientStructureCombineMethod) -> CombineMethod {
-        CombineMethod {
-        input: transient_structure.input.unwrap(),
+    fn new(transient_structure: TransientStructureCombineMethod) -> Result<CombineMethod, HCLDataError> {
+        let input = transient_structure.input.unwrap();
+        let template = match transient_structure.template {
+            None => { None }
+            Some(template) => { Some(parse_template(&template, &input)?) }
+        };
+        let empty = match transient_structure.empty.as_deref() {
+            None | Some("keep") => { EmptyInput::Keep }
+            Some("drop") => { EmptyInput::Drop }
+            Some(other) => {
+                return Err(HCLDataError::ParsingError(format!("combine-method: unknown value for 'empty': '{}', only 'keep' or 'drop' allowed.", other)));
+            }
+        };
+        let multiple = match transient_structure.multiple.as_deref() {
+            None | Some("pairwise") => { MultipleValues::Pairwise }
+            Some("product") => { MultipleValues::Product }
+            Some(other) => {
+                return Err(HCLDataError::ParsingError(format!("combine-method: unknown value for 'multiple': '{}', only 'pairwise' or 'product' allowed.", other)));
+            }
+        };
+        Ok(CombineMethod {
+        input,
         output: transient_structure.output,
         middle: transient_structure.middle,
         prefix: transient_structure.prefix,
         suffix: transient_structure.suffix,
+        template,
+        empty,
+        multiple,
+        when: transient_structure.when,
+        })
+    }
+    pub(crate) fn combine_values(&self, values: &Vec<&String>) -> String {
+        // values are in the order of the input-array
+        let combined = match &self.template {
+            None => {
+                let mut parts: Vec<&str> = vec![];
+                for value in values.iter() {
+                    if self.empty == EmptyInput::Drop && value.trim().is_empty() {
+                        continue;
+                    }
+                    parts.push(value.as_str());
+                }
+                parts.join(self.middle.as_ref().map(|middle| middle.as_str()).unwrap_or(""))
+            }
+            Some(template) => {
+                render_template(template, values, &self.empty)
+            }
+        };
+        if self.empty == EmptyInput::Drop && combined.trim().is_empty() {
+            return "".to_string();
+        }
+        let mut new_combined_value = "".to_string();
+        if self.prefix.is_some() {
+            new_combined_value += self.prefix.as_ref().unwrap().as_str();
+        }
+        new_combined_value += combined.as_str();
+        if self.suffix.is_some() {
+            new_combined_value += self.suffix.as_ref().unwrap().as_str();
         }
+        new_combined_value
     }
     pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
         let identical_input: Vec<&HeaderValue> = self.input.iter().filter(|value|value.is_equal(&self.output)).collect();
@@ -144,6 +290,111 @@ impl CombineMethod {
     }
 }
 
+fn parse_template(template: &String, input: &Vec<HeaderValue>) -> Result<Vec<TemplatePart>, HCLDataError> {
+    // "{0}, {lastname}[ ({year})]": placeholders refer to inputs by position or header-name,
+    // '{{', '}}', '[[' and ']]' are literal braces and brackets
+    let mut parts: Vec<TemplatePart> = vec![];
+    let mut optional: Option<Vec<TemplatePart>> = None;
+    let mut text = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '{' | '}' | '[' | ']' if chars.peek() == Some(&char) => {
+                chars.next();
+                text.push(char);
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        None => {
+                            return Err(HCLDataError::ParsingError(format!("combine-method: unclosed placeholder in template '{}'.", template)));
+                        }
+                        Some('}') => { break }
+                        Some(char) => { placeholder.push(char) }
+                    }
+                }
+                let position = template_input_position(placeholder.trim(), input, template)?;
+                let current = optional.as_mut().unwrap_or(&mut parts);
+                if !text.is_empty() {
+                    current.push(TemplatePart::Text(std::mem::take(&mut text)));
+                }
+                current.push(TemplatePart::Input(position));
+            }
+            '[' => {
+                if optional.is_some() {
+                    return Err(HCLDataError::ParsingError(format!("combine-method: nested square brackets are not allowed in template '{}'.", template)));
+                }
+                if !text.is_empty() {
+                    parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                }
+                optional = Some(vec![]);
+            }
+            ']' => {
+                let mut optional_parts = match optional.take() {
+                    None => {
+                        return Err(HCLDataError::ParsingError(format!("combine-method: closing square bracket without opening one in template '{}'.", template)));
+                    }
+                    Some(optional_parts) => { optional_parts }
+                };
+                if !text.is_empty() {
+                    optional_parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                }
+                parts.push(TemplatePart::Optional(optional_parts));
+            }
+            '}' => {
+                return Err(HCLDataError::ParsingError(format!("combine-method: closing brace without opening one in template '{}'.", template)));
+            }
+            _ => { text.push(char) }
+        }
+    }
+    if optional.is_some() {
+        return Err(HCLDataError::ParsingError(format!("combine-method: unclosed square bracket in template '{}'.", template)));
+    }
+    if !text.is_empty() {
+        parts.push(TemplatePart::Text(text));
+    }
+    Ok(parts)
+}
+
+fn template_input_position(placeholder: &str, input: &Vec<HeaderValue>, template: &String) -> Result<usize, HCLDataError> {
+    let position = match placeholder.parse::<usize>() {
+        Ok(position) => {
+            if position < input.len() { Some(position) } else { None }
+        }
+        Err(_) => {
+            input.iter().position(|header_value| header_value.is_equal(&placeholder.to_string()))
+        }
+    };
+    match position {
+        None => {
+            Err(HCLDataError::ParsingError(format!("combine-method: placeholder '{{{}}}' in template '{}' doesn't refer to a position or header-name in input '{:?}'.", placeholder, template, input)))
+        }
+        Some(position) => { Ok(position) }
+    }
+}
+
+fn render_template(parts: &Vec<TemplatePart>, values: &Vec<&String>, empty: &EmptyInput) -> String {
+    let mut rendered = String::new();
+    for part in parts.iter() {
+        match part {
+            TemplatePart::Text(text) => { rendered += text.as_str() }
+            TemplatePart::Input(position) => { rendered += values[*position].as_str() }
+            TemplatePart::Optional(optional_parts) => {
+                let has_empty_input = optional_parts.iter().any(|part| match part {
+                    TemplatePart::Input(position) => { values[*position].trim().is_empty() }
+                    _ => { false }
+                });
+                if *empty == EmptyInput::Drop && has_empty_input {
+                    continue;
+                }
+                rendered += render_template(optional_parts, values, empty).as_str();
+            }
+        }
+    }
+    rendered
+}
+
 #[cfg(test)]
 mod test {
 
@@ -161,4 +412,27 @@ mod test {
         println!("{:?}", result);
         assert!(result.is_ok());
     }
+    #[test]
+    fn test_combine_method_template() {
+        let block = block!(combine "label"{
+            input = [0, "lastname", "year"]
+            template = "{0}, {lastname}[ ({year})]"
+            empty = "drop"
+        });
+        let result = WrapperCombineMethod(block.to_owned()).to_combine_method();
+        assert!(result.is_ok());
+        let combine_method = result.unwrap();
+        let (first, last, year, no_year) = ("Ada".to_string(), "Lovelace".to_string(), "1843".to_string(), "".to_string());
+        assert_eq!(combine_method.combine_values(&vec![&first, &last, &year]), "Ada, Lovelace (1843)");
+        assert_eq!(combine_method.combine_values(&vec![&first, &last, &no_year]), "Ada, Lovelace");
+    }
+    #[test]
+    fn test_combine_method_unknown_placeholder() {
+        let block = block!(combine "label"{
+            input = [0, "lastname"]
+            template = "{0} {year}"
+        });
+        let result = WrapperCombineMethod(block.to_owned()).to_combine_method();
+        assert!(result.is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,78 @@
+use hcl::Block;
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::methods_domain::when::{When, WrapperWhen};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperConstantCreate(pub(crate) Block);
+
+#[derive(Clone, Debug)]
+pub struct ConstantCreate {
+    pub output: String,
+    pub value: String,
+    pub when: Option<When>,
+}
+
+impl ConstantCreate {
+    fn new(transient_constant_create: TransientConstantCreate) -> ConstantCreate {
+        ConstantCreate{ output: transient_constant_create.output, value: transient_constant_create.value.unwrap(), when: transient_constant_create.when }
+    }
+}
+
+struct TransientConstantCreate {
+    output: String,
+    value: Option<String>,
+    when: Option<When>,
+}
+impl TransientConstantCreate {
+    fn new(output: String) -> Self {
+        Self{ output, value: None, when: None }
+    }
+    fn add_value(&mut self, value: String) -> Result<(), HCLDataError> {
+        if self.value.is_some() {
+            return Err(HCLDataError::InputError(format!("Constant-create-method: Multiple values declared. First: '{}', second: '{}'", self.value.as_ref().unwrap(), value)));
+        }
+        self.value = Some(value);
+        Ok(())
+    }
+    fn add_when(&mut self, when: When) -> Result<(), HCLDataError> {
+        if self.when.is_some() {
+            return Err(HCLDataError::InputError(format!("Constant-create-method: Multiple when-blocks declared in method with output '{}'", self.output)));
+        }
+        self.when = Some(when);
+        Ok(())
+    }
+    fn is_complete(&self) -> Result<(), HCLDataError> {
+        if self.value.is_none() {
+            return Err(HCLDataError::InputError(format!("Constant-create-method: Cannot find 'value' in method with output '{}'", self.output)));
+        }
+        Ok(())
+    }
+}
+impl WrapperConstantCreate {
+    pub(crate) fn to_constant_create_method(&self, output: String) -> Result<ConstantCreate, HCLDataError> {
+        let mut transient = TransientConstantCreate::new(output);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "value" => {
+                    transient.add_value(attribute.expr.to_string_2()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::InputError(format!("Constant-create-method: key should be 'value', but found: '{}'", attribute.key)));
+                }
+            }
+        }
+        for block in self.0.blocks() {
+            match block.identifier.as_str() {
+                "when" => {
+                    transient.add_when(WrapperWhen(block.to_owned()).to_when()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::InputError(format!("Constant-create-method: block should be 'when', but found: '{}'", block.identifier)));
+                }
+            }
+        }
+        transient.is_complete()?;
+        Ok(ConstantCreate::new(transient))
+    }
+}
@@ -2,9 +2,16 @@ use std::fmt::Debug;
 use hcl::{Block};
 use crate::expression_trait::ExpressionTransform;
 use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::HeaderValue;
+use crate::parse_hcl::methods_domain::constant_create::{ConstantCreate, WrapperConstantCreate};
 use crate::parse_hcl::methods_domain::create_loop::{Create, WrapperCreateLoop};
+use crate::parse_hcl::methods_domain::hash_create::{HashCreate, WrapperHashCreate};
 use crate::parse_hcl::methods_domain::integer_create::{IntegerCreate, WrapperIntegerCreate};
 use crate::parse_hcl::methods_domain::permissions_create::{PermissionsCreate, WrapperPermissionsCreate};
+use crate::parse_hcl::methods_domain::row_number_create::{RowNumberCreate, WrapperRowNumberCreate};
+use crate::parse_hcl::methods_domain::source_create::{SourceCreate, WrapperSourceCreate};
+use crate::parse_hcl::methods_domain::uuid_create::{UuidCreate, WrapperUuidCreate};
+use crate::parse_hcl::methods_domain::when::When;
 use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
 
 pub struct WrapperCreateMethod(pub(crate) Block);
@@ -28,6 +35,26 @@ impl WrapperCreateMethod {
                 let permissions_create_method = WrapperPermissionsCreate(self.0.to_owned()).to_permissions_create_method(output)?;
                 Ok(CreateMethod::PermissionsCreateMethod(permissions_create_method))
             }
+            "hash" => {
+                let hash_create_method = WrapperHashCreate(self.0.to_owned()).to_hash_create_method(output)?;
+                Ok(CreateMethod::HashCreateMethod(hash_create_method))
+            }
+            "uuid" => {
+                let uuid_create_method = WrapperUuidCreate(self.0.to_owned()).to_uuid_create_method(output)?;
+                Ok(CreateMethod::UuidCreateMethod(uuid_create_method))
+            }
+            "constant" => {
+                let constant_create_method = WrapperConstantCreate(self.0.to_owned()).to_constant_create_method(output)?;
+                Ok(CreateMethod::ConstantCreateMethod(constant_create_method))
+            }
+            "row_number" => {
+                let row_number_create_method = WrapperRowNumberCreate(self.0.to_owned()).to_row_number_create_method(output)?;
+                Ok(CreateMethod::RowNumberCreateMethod(row_number_create_method))
+            }
+            "source" => {
+                let source_create_method = WrapperSourceCreate(self.0.to_owned()).to_source_create_method(output)?;
+                Ok(CreateMethod::SourceCreateMethod(source_create_method))
+            }
             _ => {
                 Err(HCLDataError::ParsingError(format!("found this unknown method-type '{:?}' in method with output'{:?}'.", method_type, output)))
             }
@@ -39,4 +66,46 @@ impl WrapperCreateMethod {
 pub enum CreateMethod {
     IntegerCreateMethod(IntegerCreate),
     PermissionsCreateMethod(PermissionsCreate),
+    HashCreateMethod(HashCreate),
+    UuidCreateMethod(UuidCreate),
+    ConstantCreateMethod(ConstantCreate),
+    RowNumberCreateMethod(RowNumberCreate),
+    SourceCreateMethod(SourceCreate),
+}
+
+impl CreateMethod {
+    pub(crate) fn when(&self) -> &Option<When> {
+        match self {
+            CreateMethod::IntegerCreateMethod(integer_create) => { &integer_create.when }
+            CreateMethod::PermissionsCreateMethod(permissions_create) => { &permissions_create.when }
+            CreateMethod::HashCreateMethod(hash_create) => { &hash_create.when }
+            CreateMethod::UuidCreateMethod(uuid_create) => { &uuid_create.when }
+            CreateMethod::ConstantCreateMethod(constant_create) => { &constant_create.when }
+            CreateMethod::RowNumberCreateMethod(row_number_create) => { &row_number_create.when }
+            CreateMethod::SourceCreateMethod(source_create) => { &source_create.when }
+        }
+    }
+    pub(crate) fn output(&self) -> &String {
+        match self {
+            CreateMethod::IntegerCreateMethod(integer_create) => { &integer_create.output }
+            CreateMethod::PermissionsCreateMethod(permissions_create) => { &permissions_create.output }
+            CreateMethod::HashCreateMethod(hash_create) => { &hash_create.output }
+            CreateMethod::UuidCreateMethod(uuid_create) => { &uuid_create.output }
+            CreateMethod::ConstantCreateMethod(constant_create) => { &constant_create.output }
+            CreateMethod::RowNumberCreateMethod(row_number_create) => { &row_number_create.output }
+            CreateMethod::SourceCreateMethod(source_create) => { &source_create.output }
+        }
+    }
+    pub(crate) fn is_sheet_value(&self) -> bool {
+        // constant, row number and source don't depend on other columns, they are created before all transformations
+        matches!(self, CreateMethod::ConstantCreateMethod(_) | CreateMethod::RowNumberCreateMethod(_) | CreateMethod::SourceCreateMethod(_))
+    }
+    pub(crate) fn unique_id_input(&self) -> Option<&Vec<HeaderValue>> {
+        // hash- and uuid-ids are checked for collisions across all sheets, random uuids have no input
+        match self {
+            CreateMethod::HashCreateMethod(hash_create) => { Some(&hash_create.input) }
+            CreateMethod::UuidCreateMethod(uuid_create) => { Some(&uuid_create.input) }
+            _ => { None }
+        }
+    }
 }
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use hcl::{Block, Expression};
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperDistinctMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureDistinctMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    case_insensitive: Option<bool>,
+    sort: Option<bool>,
+    aligned: Option<HashMap<String, String>>,
+}
+
+impl TransientStructureDistinctMethod {
+    fn new(output: String) -> TransientStructureDistinctMethod {
+        TransientStructureDistinctMethod {
+            output,
+            input: None,
+            case_insensitive: None,
+            sort: None,
+            aligned: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_case_insensitive(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.case_insensitive.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple case_insensitive-attributes  in method '{:?}'.", self.output)));
+        }
+        self.case_insensitive = Option::from(expression.to_bool()?);
+        Ok(())
+    }
+    fn add_sort(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.sort.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple sort-attributes  in method '{:?}'.", self.output)));
+        }
+        self.sort = Option::from(expression.to_bool()?);
+        Ok(())
+    }
+    fn add_aligned(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.aligned.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple aligned-attributes  in method '{:?}'.", self.output)));
+        }
+        self.aligned = Option::from(expression.to_string_map()?);
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("distinct-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        Ok(())
+    }
+}
+
+impl WrapperDistinctMethod {
+    pub(crate) fn to_distinct_method(&self) -> Result<DistinctMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureDistinctMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "case_insensitive" => {
+                    transient_structure.add_case_insensitive(attribute.expr.to_owned())?;
+                }
+                "sort" => {
+                    transient_structure.add_sort(attribute.expr.to_owned())?;
+                }
+                "aligned" => {
+                    transient_structure.add_aligned(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        Ok(DistinctMethod::new(transient_structure))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DistinctMethod {
+    pub output: String,
+    pub input: HeaderValue,
+    pub case_insensitive: bool,
+    pub sort: bool,
+    // supplement-columns (comment, permissions, encoding) that keep the values of the same positions; header to output
+    pub aligned: HashMap<String, String>,
+}
+
+impl DistinctMethod {
+    fn new(transient_structure: TransientStructureDistinctMethod) -> DistinctMethod {
+        DistinctMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            case_insensitive: transient_structure.case_insensitive.unwrap_or(false),
+            sort: transient_structure.sort.unwrap_or(false),
+            aligned: transient_structure.aligned.unwrap_or_default(),
+        }
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("distinct-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        for (header, output) in self.aligned.iter() {
+            if header == output || self.input.is_equal(output) || output == &self.output {
+                return Err(HCLDataError::ParsingError(format!("distinct-method '{}': aligned output '{}' is the same as an input or output.", self.output, output)));
+            }
+        }
+        Ok(())
+    }
+    pub(crate) fn outputs(&self) -> Vec<&String> {
+        let mut outputs = vec![&self.output];
+        outputs.extend(self.aligned.values());
+        outputs
+    }
+    pub(crate) fn distinct(&self, values: &[String]) -> (Vec<usize>, Vec<String>) {
+        // returns the positions of the distinct non-empty values in their new order (first occurrence is kept) and the removed duplicates
+        let mut positions: Vec<usize> = vec![];
+        let mut keys: Vec<String> = vec![];
+        let mut duplicates: Vec<String> = vec![];
+        for (pos, value) in values.iter().enumerate() {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            let key = if self.case_insensitive { value.to_lowercase() } else { value.to_string() };
+            if keys.contains(&key) {
+                duplicates.push(value.to_string());
+                continue;
+            }
+            keys.push(key);
+            positions.push(pos);
+        }
+        if self.sort {
+            if self.case_insensitive {
+                positions.sort_by_key(|pos| values[*pos].trim().to_lowercase());
+            } else {
+                positions.sort_by_key(|pos| values[*pos].trim());
+            }
+        }
+        (positions, duplicates)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::distinct_method::WrapperDistinctMethod;
+
+    #[test]
+    fn test_distinct_method() {
+        let block = block!(distinct "hasKeyword" {
+            input = "keywords"
+            case_insensitive = true
+            sort = true
+            aligned = { "keywords_comment" = "hasKeyword_comment" }
+        });
+        let result = WrapperDistinctMethod(block.to_owned()).to_distinct_method();
+        assert!(result.is_ok());
+        let distinct_method = result.unwrap();
+        assert_eq!(distinct_method.outputs().len(), 2);
+        let values = vec!["Zurich".to_string(), "bern".to_string(), "".to_string(), "zurich".to_string()];
+        let (positions, duplicates) = distinct_method.distinct(&values);
+        assert_eq!(positions, vec![1, 0]);
+        assert_eq!(duplicates, vec!["zurich".to_string()]);
+    }
+}
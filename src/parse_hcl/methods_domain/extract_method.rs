@@ -0,0 +1,176 @@
+use hcl::{Block, Expression};
+use regex::Regex;
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperExtractMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureExtractMethod {
+    input: Option<HeaderValue>,
+    regex: Option<Regex>,
+    unmatched: Option<Unmatched>,
+}
+
+impl TransientStructureExtractMethod {
+    fn new() -> TransientStructureExtractMethod {
+        TransientStructureExtractMethod {
+            input: None,
+            regex: None,
+            unmatched: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if let Some(input) = &self.input {
+            return Err(HCLDataError::ParsingError(format!("extract-method: found multiple input-attributes: First: '{:?}', Second: '{:?}'.", input, expression)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_regex(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.regex.is_some() {
+            return Err(HCLDataError::ParsingError(format!("extract-method: found multiple regex-attributes in method with input '{:?}'.", self.input)));
+        }
+        self.regex = Option::from(Regex::new(expression.to_string_2()?.as_str())?);
+        Ok(())
+    }
+    fn add_unmatched(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.unmatched.is_some() {
+            return Err(HCLDataError::ParsingError(format!("extract-method: found multiple unmatched-attributes in method with input '{:?}'.", self.input)));
+        }
+        let unmatched = expression.to_string_2()?;
+        self.unmatched = match unmatched.as_str() {
+            "empty" => { Some(Unmatched::Empty) }
+            "keep" => { Some(Unmatched::Keep) }
+            "error" => { Some(Unmatched::Error) }
+            _ => {
+                return Err(HCLDataError::ParsingError(format!("extract-method: unknown value for 'unmatched': '{}', only 'empty', 'keep' or 'error' allowed.", unmatched)));
+            }
+        };
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("extract-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        match &self.regex {
+            None => {
+                return Err(HCLDataError::ParsingError(format!("extract-method '{:?}' doesn't have a regex-attribute provided", self)));
+            }
+            Some(regex) => {
+                // every capturing group needs a name, the names are the output-headers
+                if regex.capture_names().skip(1).any(|name| name.is_none()) {
+                    return Err(HCLDataError::ParsingError(format!("extract-method: regex '{}' has unnamed groups, use (?P<name>...) or (?:...).", regex.as_str())));
+                }
+                if regex.capture_names().flatten().next().is_none() {
+                    return Err(HCLDataError::ParsingError(format!("extract-method: regex '{}' has no named groups.", regex.as_str())));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl WrapperExtractMethod {
+    pub(crate) fn to_extract_method(&self) -> Result<ExtractMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        if !self.0.labels.is_empty() {
+            return Err(HCLDataError::ParsingError(format!("extract-method: no label allowed, the outputs are the names of the groups of the regex, but found: '{:?}'", self.0.labels)));
+        }
+        let mut transient_structure = TransientStructureExtractMethod::new();
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "regex" => {
+                    transient_structure.add_regex(attribute.expr.to_owned())?;
+                }
+                "unmatched" => {
+                    transient_structure.add_unmatched(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("extract-method: found this unknown attribute '{:?}'.", attribute)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        Ok(ExtractMethod::new(transient_structure))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Unmatched {
+    // values the regex doesn't match: all outputs empty, the value in the first output or an error
+    Empty,
+    Keep,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExtractMethod {
+    pub input: HeaderValue,
+    pub regex: Regex,
+    // names of the groups in the order of the regex
+    pub outputs: Vec<String>,
+    pub unmatched: Unmatched,
+}
+
+impl ExtractMethod {
+    fn new(transient_structure: TransientStructureExtractMethod) -> ExtractMethod {
+        let regex = transient_structure.regex.unwrap();
+        let outputs = regex.capture_names().flatten().map(|name| name.to_string()).collect();
+        ExtractMethod {
+            input: transient_structure.input.unwrap(),
+            regex,
+            outputs,
+            unmatched: transient_structure.unmatched.unwrap_or(Unmatched::Empty),
+        }
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.outputs.iter().any(|output| self.input.is_equal(output)) {
+            return Err(HCLDataError::ParsingError(format!("extract-method has a group with the same name as the input, which is forbidden: '{:?}'", self.input)));
+        }
+        Ok(())
+    }
+    pub(crate) fn extract(&self, value: &str) -> Option<Vec<String>> {
+        // groups that don't participate in the match are empty
+        let captures = self.regex.captures(value)?;
+        Some(self.outputs.iter()
+            .map(|output| captures.name(output).map(|part| part.as_str().trim().to_string()).unwrap_or_default())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::extract_method::{Unmatched, WrapperExtractMethod};
+
+    #[test]
+    fn test_extract_method() {
+        let block = block!(extract {
+            input = "title"
+            regex = "^Letter from (?P<sender>.+?) to (?P<recipient>.+?), (?P<place>[^,]+), (?P<year>\\d{4})$"
+            unmatched = "keep"
+        });
+        let result = WrapperExtractMethod(block.to_owned()).to_extract_method();
+        assert!(result.is_ok());
+        let extract_method = result.unwrap();
+        assert_eq!(extract_method.outputs, vec!["sender", "recipient", "place", "year"]);
+        assert_eq!(extract_method.unmatched, Unmatched::Keep);
+        assert_eq!(extract_method.extract("Letter from A to B, Basel, 1783").unwrap(), vec!["A", "B", "Basel", "1783"]);
+        assert!(extract_method.extract("Postcard, Basel").is_none());
+    }
+    #[test]
+    fn test_extract_method_unnamed_group() {
+        let block = block!(extract {
+            input = "title"
+            regex = "^(\\w+) (?P<year>\\d{4})$"
+        });
+        let result = WrapperExtractMethod(block.to_owned()).to_extract_method();
+        assert!(result.is_err());
+    }
+}
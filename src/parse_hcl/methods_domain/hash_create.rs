@@ -0,0 +1,174 @@
+use hcl::{Block, Expression};
+use sha2::{Digest, Sha256};
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::number_trait::NumberTrait;
+use crate::parse_hcl::methods_domain::when::{When, WrapperWhen};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperHashCreate(pub(crate) Block);
+
+#[derive(Clone, Debug)]
+pub struct HashCreate {
+    pub output: String,
+    pub input: Vec<HeaderValue>,
+    pub prefix: Option<String>,
+    // number of hex-characters of the SHA-256 that are kept
+    pub length: usize,
+    pub when: Option<When>,
+}
+
+impl HashCreate {
+    fn new(transient_hash_create: TransientHashCreate) -> HashCreate {
+        HashCreate{
+            output: transient_hash_create.output,
+            input: transient_hash_create.input.unwrap(),
+            prefix: transient_hash_create.prefix,
+            length: transient_hash_create.length.unwrap_or(16),
+            when: transient_hash_create.when,
+        }
+    }
+    pub(crate) fn hash(&self, values: &Vec<&Vec<String>>) -> String {
+        // values of different inputs are separated by a unit separator, multiple values by a record separator
+        let content = values.iter()
+            .map(|values| values.iter().map(|value| value.trim()).collect::<Vec<&str>>().join("\u{1e}"))
+            .collect::<Vec<String>>()
+            .join("\u{1f}");
+        let digest = Sha256::digest(content.as_bytes());
+        let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+        format!("{}{}", self.prefix.as_deref().unwrap_or(""), &hex[..self.length])
+    }
+}
+#[derive(Debug)]
+struct TransientHashCreate {
+    output: String,
+    input: Option<Vec<HeaderValue>>,
+    prefix: Option<String>,
+    length: Option<usize>,
+    when: Option<When>,
+}
+
+impl TransientHashCreate {
+    fn new(output: String) -> Self {
+        TransientHashCreate{
+            output,
+            input: None,
+            prefix: None,
+            length: None,
+            when: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::InputError(format!("hash-create-method: multiple input in method with output '{}'", self.output)));
+        }
+        let mut input = vec![];
+        for expr in expression.to_vec()? {
+            input.push(expr.to_header_value()?);
+        }
+        self.input = Some(input);
+        Ok(())
+    }
+    fn add_prefix(&mut self, prefix: String) -> Result<(), HCLDataError> {
+        if self.prefix.is_some() {
+            return Err(HCLDataError::InputError(format!("hash-create-method: multiple prefix: First: '{}', Second: '{}'", self.prefix.as_ref().unwrap(), prefix)));
+        }
+        self.prefix = Some(prefix);
+        Ok(())
+    }
+    fn add_length(&mut self, length: usize) -> Result<(), HCLDataError> {
+        if self.length.is_some() {
+            return Err(HCLDataError::InputError(format!("hash-create-method: multiple length: First: '{}', Second: '{}'", self.length.as_ref().unwrap(), length)));
+        }
+        self.length = Some(length);
+        Ok(())
+    }
+    fn add_when(&mut self, when: When) -> Result<(), HCLDataError> {
+        if self.when.is_some() {
+            return Err(HCLDataError::InputError(format!("hash-create-method: multiple when-blocks in method with output '{}'", self.output)));
+        }
+        self.when = Some(when);
+        Ok(())
+    }
+    fn is_complete(&self) -> Result<(), HCLDataError> {
+        match &self.input {
+            None => {
+                return Err(HCLDataError::InputError(format!("Hash-create-method: Cannot find 'input' in '{:?}'", self)));
+            }
+            Some(input) => {
+                if input.is_empty() {
+                    return Err(HCLDataError::InputError(format!("Hash-create-method: 'input' is empty in '{:?}'", self)));
+                }
+            }
+        }
+        if let Some(length) = self.length {
+            // shorter hashes make collisions likely
+            if !(8..=64).contains(&length) {
+                return Err(HCLDataError::InputError(format!("Hash-create-method: 'length' must be between 8 and 64, but found: {}", length)));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl WrapperHashCreate {
+    pub(crate) fn to_hash_create_method(&self, output: String) -> Result<HashCreate, HCLDataError> {
+        let mut transient_structure = TransientHashCreate::new(output);
+        for attribute in self.0.body.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "prefix" => {
+                    transient_structure.add_prefix(attribute.expr.to_string_2()?)?;
+                }
+                "length" => {
+                    transient_structure.add_length(attribute.expr.to_number()?.as_usize()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("hash-create-method: found this unknown attribute '{:?}' in method '{:?}'.", attribute, self.0.labels)));
+                }
+            }
+        }
+        for block in self.0.blocks() {
+            match block.identifier.as_str() {
+                "when" => {
+                    transient_structure.add_when(WrapperWhen(block.to_owned()).to_when()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("hash-create-method: found this unknown block '{:?}' in method '{:?}'.", block.identifier, self.0.labels)));
+                }
+            }
+        }
+        transient_structure.is_complete()?;
+        Ok(HashCreate::new(transient_structure))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::create_method::{CreateMethod, WrapperCreateMethod};
+
+    #[test]
+    fn test_hash_create() {
+        let block = block!(create "hash" "id" {
+            input = ["author", "title"]
+            prefix = "letter_"
+            length = 12
+        });
+        let result = WrapperCreateMethod(block.to_owned()).to_create_method();
+        assert!(result.is_ok());
+        let hash_create = match result.unwrap() {
+            CreateMethod::HashCreateMethod(hash_create) => { hash_create }
+            _ => { panic!("expected hash-create-method") }
+        };
+        let author = vec!["Goethe".to_string()];
+        let title = vec!["Faust".to_string()];
+        let id = hash_create.hash(&vec![&author, &title]);
+        assert_eq!(id.len(), "letter_".len() + 12);
+        assert_eq!(id, hash_create.hash(&vec![&author, &title]));
+        assert_ne!(id, hash_create.hash(&vec![&title, &author]));
+    }
+}
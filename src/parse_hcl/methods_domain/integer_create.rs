@@ -1,8 +1,10 @@
 use hcl::{Block, Expression};
 use crate::expression_trait::ExpressionTransform;
 use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
 use crate::parse_hcl::methods_domain::number_trait::NumberTrait;
 use crate::parse_hcl::methods_domain::step::{Step, WrapperStep};
+use crate::parse_hcl::methods_domain::when::{When, WrapperWhen};
 use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
 
 pub struct WrapperIntegerCreate(pub(crate) Block);
@@ -13,7 +15,12 @@ pub struct IntegerCreate {
     pub prefix: Option<String>,
     pub suffix: Option<String>,
     pub start: usize,
-    pub step: Step
+    pub step: Step,
+    // the number is padded with zeros to this width, e.g. "00042"
+    pub width: Option<usize>,
+    // every value of this column has its own counter, e.g. per box
+    pub restart_on: Option<HeaderValue>,
+    pub when: Option<When>,
 }
 
 impl IntegerCreate {
@@ -24,8 +31,14 @@ impl IntegerCreate {
             suffix: transient_integer_create.suffix,
             start: transient_integer_create.start.unwrap(),
             step: transient_integer_create.step.unwrap(),
+            width: transient_integer_create.width,
+            restart_on: transient_integer_create.restart_on,
+            when: transient_integer_create.when,
         }
     }
+    pub(crate) fn value(&self, number: usize) -> String {
+        format!("{}{:0width$}{}", self.prefix.as_deref().unwrap_or(""), number, self.suffix.as_deref().unwrap_or(""), width = self.width.unwrap_or(0))
+    }
 }
 #[derive(Debug)]
 struct TransientIntegerCreate {
@@ -33,7 +46,10 @@ struct TransientIntegerCreate {
     suffix: Option<String>,
     start: Option<usize>,
     step: Option<Step>,
-    output: String
+    width: Option<usize>,
+    restart_on: Option<HeaderValue>,
+    output: String,
+    when: Option<When>,
 }
 
 impl TransientIntegerCreate {
@@ -43,7 +59,10 @@ impl TransientIntegerCreate {
             suffix: None,
             start: None,
             step: None,
-            output
+            width: None,
+            restart_on: None,
+            output,
+            when: None,
         }
     }
     fn add_prefix(&mut self, prefix: String) -> Result<(), HCLDataError> {
@@ -74,6 +93,30 @@ impl TransientIntegerCreate {
         self.start = Some(start);
         Ok(())
     }
+    fn add_width(&mut self, width: usize) -> Result<(), HCLDataError> {
+        if let Some(first) = &self.width {
+            return Err(HCLDataError::InputError(format!("integer-create-method: multiple width: First: '{}', Second: '{}'", first, width)));
+        }
+        if width == 0 {
+            return Err(HCLDataError::InputError(format!("integer-create-method: width of method with output '{}' must be greater than 0.", self.output)));
+        }
+        self.width = Some(width);
+        Ok(())
+    }
+    fn add_restart_on(&mut self, restart_on: HeaderValue) -> Result<(), HCLDataError> {
+        if let Some(first) = &self.restart_on {
+            return Err(HCLDataError::InputError(format!("integer-create-method: multiple restart_on: First: '{:?}', Second: '{:?}'", first, restart_on)));
+        }
+        self.restart_on = Some(restart_on);
+        Ok(())
+    }
+    fn add_when(&mut self, when: When) -> Result<(), HCLDataError> {
+        if self.when.is_some() {
+            return Err(HCLDataError::InputError(format!("integer-create-method: multiple when-blocks in method with output '{}'", self.output)));
+        }
+        self.when = Some(when);
+        Ok(())
+    }
     fn is_complete(&self) -> Result<(), HCLDataError> {
         // prefix and suffix is facultative
         if self.start.is_none() {
@@ -88,7 +131,6 @@ impl TransientIntegerCreate {
 
 impl WrapperIntegerCreate {
     pub(crate) fn to_integer_create_method(&self, output: String) -> Result<IntegerCreate, HCLDataError> {
-        self.0.no_blocks()?;
         let mut transient_structure = TransientIntegerCreate::new(output);
         for attribute in self.0.body.attributes() {
             match attribute.key.as_str() {
@@ -113,12 +155,62 @@ impl WrapperIntegerCreate {
                     let step = WrapperStep(attribute.expr.to_string_2()?).to_step()?;
                     transient_structure.add_step(step)?;
                 }
+                "width" => {
+                    let width = match attribute.expr {
+                        Expression::Number(number) => { number
+                        }
+                        _ => {
+                            return Err(HCLDataError::InputError(format!("Integer-create-method: 'width' is not of type Number: '{}'", attribute.expr)));
+                        }
+                    };
+                    transient_structure.add_width(width.as_usize()?)?
+                }
+                "restart_on" => {
+                    transient_structure.add_restart_on(attribute.expr.to_header_value()?)?;
+                }
                 _ => {
                     return Err(HCLDataError::ParsingError(format!("integer-create-method: found this unknown attribute '{:?}' in method '{:?}'.", attribute, self.0.labels)));
                 }
             }
         }
+        for block in self.0.blocks() {
+            match block.identifier.as_str() {
+                "when" => {
+                    transient_structure.add_when(WrapperWhen(block.to_owned()).to_when()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("integer-create-method: found this unknown block '{:?}' in method '{:?}'.", block.identifier, self.0.labels)));
+                }
+            }
+        }
         transient_structure.is_complete()?;
         Ok(IntegerCreate::new(transient_structure))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::create_method::{CreateMethod, WrapperCreateMethod};
+
+    #[test]
+    fn test_integer_create() {
+        let block = block!(create "integer" "id" {
+            prefix = "BIZ_"
+            start = 42
+            step = "-2"
+            width = 5
+            restart_on = "box"
+        });
+        let result = WrapperCreateMethod(block.to_owned()).to_create_method();
+        assert!(result.is_ok());
+        let integer_create = match result.unwrap() {
+            CreateMethod::IntegerCreateMethod(integer_create) => { integer_create }
+            _ => { panic!("expected integer-create-method") }
+        };
+        assert_eq!(integer_create.value(42), "BIZ_00042");
+        assert_eq!(integer_create.value(integer_create.step.next(42).unwrap()), "BIZ_00040");
+        assert_eq!(integer_create.value(1234567), "BIZ_1234567");
+        assert!(integer_create.restart_on.is_some());
+    }
+}
@@ -0,0 +1,220 @@
+use hcl::{Block, Expression};
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperJoinMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureJoinMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    resource: Option<String>,
+    foreign_key: Option<HeaderValue>,
+    value: Option<HeaderValue>,
+    aggregate: Option<Aggregate>,
+    missing: Option<MissingKeys>,
+}
+
+impl TransientStructureJoinMethod {
+    fn new(output: String) -> TransientStructureJoinMethod {
+        TransientStructureJoinMethod {
+            output,
+            input: None,
+            resource: None,
+            foreign_key: None,
+            value: None,
+            aggregate: None,
+            missing: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_resource(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.resource.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple resource-attributes  in method '{:?}'.", self.output)));
+        }
+        self.resource = Option::from(expression.to_string_2()?);
+        Ok(())
+    }
+    fn add_foreign_key(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.foreign_key.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple foreign_key-attributes  in method '{:?}'.", self.output)));
+        }
+        self.foreign_key = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_value(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.value.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple value-attributes  in method '{:?}'.", self.output)));
+        }
+        self.value = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_aggregate(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.aggregate.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple aggregate-attributes  in method '{:?}'.", self.output)));
+        }
+        let aggregate = expression.to_string_2()?;
+        self.aggregate = match aggregate.as_str() {
+            "all" => { Some(Aggregate::All) }
+            "distinct" => { Some(Aggregate::Distinct) }
+            _ => {
+                return Err(HCLDataError::ParsingError(format!("join-method '{}': unknown value for 'aggregate': '{}', only 'all' or 'distinct' allowed.", self.output, aggregate)));
+            }
+        };
+        Ok(())
+    }
+    fn add_missing(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.missing.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple missing-attributes  in method '{:?}'.", self.output)));
+        }
+        let missing = expression.to_string_2()?;
+        self.missing = match missing.as_str() {
+            "empty" => { Some(MissingKeys::Empty) }
+            "warn" => { Some(MissingKeys::Warn) }
+            "error" => { Some(MissingKeys::Error) }
+            _ => {
+                return Err(HCLDataError::ParsingError(format!("join-method '{}': unknown value for 'missing': '{}', only 'empty', 'warn' or 'error' allowed.", self.output, missing)));
+            }
+        };
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("join-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        if self.resource.is_none() {
+            return Err(HCLDataError::ParsingError(format!("join-method '{:?}' doesn't have a resource-attribute provided", self)));
+        }
+        if self.foreign_key.is_none() || self.value.is_none() {
+            return Err(HCLDataError::ParsingError(format!("join-method '{:?}' needs a foreign_key- and a value-attribute", self)));
+        }
+        Ok(())
+    }
+}
+
+impl WrapperJoinMethod {
+    pub(crate) fn to_join_method(&self) -> Result<JoinMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureJoinMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "resource" => {
+                    transient_structure.add_resource(attribute.expr.to_owned())?;
+                }
+                "foreign_key" => {
+                    transient_structure.add_foreign_key(attribute.expr.to_owned())?;
+                }
+                "value" => {
+                    transient_structure.add_value(attribute.expr.to_owned())?;
+                }
+                "aggregate" => {
+                    transient_structure.add_aggregate(attribute.expr.to_owned())?;
+                }
+                "missing" => {
+                    transient_structure.add_missing(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        Ok(JoinMethod::new(transient_structure))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Aggregate {
+    All,
+    Distinct,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MissingKeys {
+    // rows without any joined value stay empty, silently, with a message or with an error
+    Empty,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct JoinMethod {
+    pub output: String,
+    // column of this sheet with the key (e.g. the id of the letter)
+    pub input: HeaderValue,
+    // the other resource, its column referencing the key and the column whose values are collected
+    pub resource: String,
+    pub foreign_key: HeaderValue,
+    pub value: HeaderValue,
+    pub aggregate: Aggregate,
+    pub missing: MissingKeys,
+}
+
+impl JoinMethod {
+    fn new(transient_structure: TransientStructureJoinMethod) -> JoinMethod {
+        JoinMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            resource: transient_structure.resource.unwrap(),
+            foreign_key: transient_structure.foreign_key.unwrap(),
+            value: transient_structure.value.unwrap(),
+            aggregate: transient_structure.aggregate.unwrap_or(Aggregate::All),
+            missing: transient_structure.missing.unwrap_or(MissingKeys::Empty),
+        }
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("join-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        Ok(())
+    }
+    pub(crate) fn aggregate(&self, values: Vec<String>) -> Vec<String> {
+        match self.aggregate {
+            Aggregate::All => { values }
+            Aggregate::Distinct => {
+                let mut distinct_values: Vec<String> = vec![];
+                for value in values {
+                    if !distinct_values.contains(&value) {
+                        distinct_values.push(value);
+                    }
+                }
+                distinct_values
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::join_method::{MissingKeys, WrapperJoinMethod};
+
+    #[test]
+    fn test_join_method() {
+        let block = block!(join "hasKeyword" {
+            input = "id"
+            resource = "Keyword"
+            foreign_key = "letter_id"
+            value = "keyword"
+            aggregate = "distinct"
+            missing = "warn"
+        });
+        let result = WrapperJoinMethod(block.to_owned()).to_join_method();
+        assert!(result.is_ok());
+        let join_method = result.unwrap();
+        assert_eq!(join_method.missing, MissingKeys::Warn);
+        let values = vec!["Rom".to_string(), "Paris".to_string(), "Rom".to_string()];
+        assert_eq!(join_method.aggregate(values), vec!["Rom".to_string(), "Paris".to_string()]);
+    }
+}
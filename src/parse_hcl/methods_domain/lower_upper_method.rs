@@ -1,6 +1,8 @@
 use hcl::Expression;
+use regex::Regex;
+use crate::expression_trait::ExpressionTransform;
 use crate::parse_hcl::errors::HCLDataError;
-use crate::parse_hcl::header_value::{HeaderValue};
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
 use crate::parse_hcl::methods_domain::number_trait::NumberTrait;
 use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
 
@@ -58,6 +60,127 @@ impl WrapperLowerUpperMethod {
     }
 }
 
+impl WrapperLowerUpperMethod {
+    pub fn to_case_method(&self) -> Result<CaseMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureCaseMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "mode" => {
+                    transient_structure.add_mode(attribute.expr.to_owned())?;
+                }
+                "language" => {
+                    transient_structure.add_language(attribute.expr.to_owned())?;
+                }
+                "exceptions" => {
+                    transient_structure.add_exceptions(attribute.expr.to_owned())?;
+                }
+                "roman_numerals" => {
+                    transient_structure.add_roman_numerals(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_complete()?;
+        Ok(CaseMethod::new(transient_structure))
+    }
+}
+
+#[derive(Debug)]
+struct TransientStructureCaseMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    mode: Option<CaseMode>,
+    language: Option<String>,
+    exceptions: Option<Vec<String>>,
+    roman_numerals: Option<bool>,
+}
+
+impl TransientStructureCaseMethod {
+    fn new(output: String) -> TransientStructureCaseMethod {
+        TransientStructureCaseMethod {
+            output,
+            input: None,
+            mode: None,
+            language: None,
+            exceptions: None,
+            roman_numerals: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found more than one 'input'-declaration in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_mode(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.mode.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found more than one 'mode'-declaration in method '{:?}'.", self.output)));
+        }
+        let mode = expression.to_string_2()?;
+        self.mode = match mode.as_str() {
+            "title" => { Some(CaseMode::Title) }
+            "sentence" => { Some(CaseMode::Sentence) }
+            "capitalize" => { Some(CaseMode::Capitalize) }
+            _ => {
+                return Err(HCLDataError::ParsingError(format!("case-method '{}': unknown mode '{}', only 'title', 'sentence' or 'capitalize' allowed.", self.output, mode)));
+            }
+        };
+        Ok(())
+    }
+    fn add_language(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.language.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found more than one 'language'-declaration in method '{:?}'.", self.output)));
+        }
+        let language = expression.to_string_2()?.trim().to_lowercase();
+        if stop_words(&language).is_none() {
+            return Err(HCLDataError::ParsingError(format!("case-method '{}': no stop words for language '{}', only 'en', 'de', 'fr' or 'it' allowed.", self.output, language)));
+        }
+        self.language = Option::from(language);
+        Ok(())
+    }
+    fn add_exceptions(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.exceptions.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found more than one 'exceptions'-declaration in method '{:?}'.", self.output)));
+        }
+        let mut exceptions = vec![];
+        for expr in expression.to_vec()? {
+            let exception = expr.to_string_2()?.split_whitespace().collect::<Vec<&str>>().join(" ");
+            if exception.is_empty() {
+                return Err(HCLDataError::ParsingError(format!("case-method '{}': found an empty exception.", self.output)));
+            }
+            exceptions.push(exception);
+        }
+        self.exceptions = Option::from(exceptions);
+        Ok(())
+    }
+    fn add_roman_numerals(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.roman_numerals.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found more than one 'roman_numerals'-declaration in method '{:?}'.", self.output)));
+        }
+        self.roman_numerals = Option::from(expression.to_bool()?);
+        Ok(())
+    }
+    fn is_complete(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("found no 'input'-declaration in method '{:?}'.", self.output)));
+        }
+        if self.mode.is_none() {
+            return Err(HCLDataError::ParsingError(format!("found no 'mode'-declaration in method '{:?}'.", self.output)));
+        }
+        if self.language.is_some() && self.mode != Some(CaseMode::Title) {
+            return Err(HCLDataError::ParsingError(format!("case-method '{}': 'language' is only used with mode 'title'.", self.output)));
+        }
+        Ok(())
+    }
+}
+
 fn get_transient_structure(wrapper: &WrapperLowerUpperMethod) -> Result<TransientStructureLowerUpperMethod, HCLDataError> {
     wrapper.0.no_blocks()?;
     let mut transient_structure: TransientStructureLowerUpperMethod = TransientStructureLowerUpperMethod::new( wrapper.0.get_output()?);
@@ -107,6 +230,174 @@ impl UpperMethod {
         Ok(())
     }
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CaseMode {
+    // every word capitalized except stop words; first letter of every sentence; only the first letter of the value
+    Title,
+    Sentence,
+    Capitalize,
+}
+
+#[derive(Debug, Clone)]
+pub struct CaseMethod {
+    pub output: String,
+    pub input: HeaderValue,
+    pub mode: CaseMode,
+    // stop words of the language stay lowercase in title case (except the first word)
+    pub stop_words: Vec<&'static str>,
+    // words or word sequences that are written as given, e.g. "von", "van der", "McDonald"
+    pub exceptions: Vec<Vec<String>>,
+    // roman numerals are written in uppercase, e.g. "xiv" -> "XIV"
+    pub roman_numerals: Option<Regex>,
+    // words of the language that look like roman numerals, e.g. "mix" or "dix"
+    pub roman_lookalikes: Vec<&'static str>,
+}
+
+impl CaseMethod {
+    fn new(transient_structure: TransientStructureCaseMethod) -> CaseMethod {
+        let language = transient_structure.language.unwrap_or("en".to_string());
+        let mut exceptions: Vec<Vec<String>> = transient_structure.exceptions.unwrap_or_default().iter()
+            .map(|exception| exception.split(' ').map(|word| word.to_string()).collect())
+            .collect();
+        // longer sequences first, so "van der" is found before "van"
+        exceptions.sort_by_key(|exception| std::cmp::Reverse(exception.len()));
+        CaseMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            mode: transient_structure.mode.unwrap(),
+            stop_words: stop_words(&language).unwrap(),
+            exceptions,
+            roman_numerals: match transient_structure.roman_numerals {
+                Some(true) => { Some(Regex::new("^m{0,3}(cm|cd|d?c{0,3})(xc|xl|l?x{0,3})(ix|iv|v?i{0,3})$").unwrap()) }
+                _ => { None }
+            },
+            roman_lookalikes: roman_lookalikes(&language),
+        }
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("case-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        Ok(())
+    }
+    pub(crate) fn change_case(&self, value: &str) -> String {
+        if self.mode == CaseMode::Capitalize {
+            return capitalize(value, false);
+        }
+        let tokens: Vec<&str> = split_keep_whitespace(value);
+        let words: Vec<(usize, String)> = tokens.iter().enumerate()
+            .filter(|(_, token)| !token.trim().is_empty())
+            .map(|(pos, token)| (pos, core(token).to_lowercase()))
+            .collect();
+        let mut new_tokens: Vec<String> = tokens.iter().map(|token| token.to_string()).collect();
+        let mut sentence_start = true;
+        let mut nr = 0;
+        while nr < words.len() {
+            if let Some(exception) = self.exception_at(&words[nr..]) {
+                for (offset, word) in exception.iter().enumerate() {
+                    let pos = words[nr + offset].0;
+                    new_tokens[pos] = tokens[pos].replacen(core(tokens[pos]), word, 1);
+                }
+                nr += exception.len();
+                sentence_start = ends_sentence(tokens[words[nr - 1].0]);
+                continue;
+            }
+            let (pos, word) = &words[nr];
+            let token = tokens[*pos];
+            let is_stop_word = self.stop_words.contains(&word.as_str());
+            new_tokens[*pos] = if self.mode == CaseMode::Title && is_stop_word {
+                capitalize(token, !sentence_start)
+            } else if self.is_roman_numeral(word) && !is_stop_word {
+                token.to_uppercase()
+            } else {
+                capitalize(token, !sentence_start && self.mode != CaseMode::Title)
+            };
+            sentence_start = ends_sentence(token);
+            nr += 1;
+        }
+        new_tokens.concat()
+    }
+    fn is_roman_numeral(&self, word: &str) -> bool {
+        // only words without another reading, e.g. "storia di roma" keeps "di"
+        !word.is_empty() && !self.roman_lookalikes.contains(&word) && self.roman_numerals.as_ref().is_some_and(|regex| regex.is_match(word))
+    }
+    fn exception_at(&self, words: &[(usize, String)]) -> Option<&Vec<String>> {
+        self.exceptions.iter().find(|exception| exception.len() <= words.len() && exception.iter().zip(words.iter()).all(|(exception, (_, word))| exception.to_lowercase().eq(word)))
+    }
+}
+
+fn stop_words(language: &str) -> Option<Vec<&'static str>> {
+    let stop_words = match language {
+        "en" => { vec!["a", "an", "the", "and", "but", "or", "nor", "for", "so", "yet", "as", "at", "by", "in", "of", "off", "on", "per", "to", "up", "via", "with", "from", "into", "over"] }
+        "de" => { vec!["der", "die", "das", "den", "dem", "des", "ein", "eine", "einer", "eines", "einem", "einen", "und", "oder", "aber", "von", "vom", "zu", "zum", "zur", "im", "in", "am", "an", "auf", "aus", "bei", "mit", "nach", "über", "unter", "vor", "für"] }
+        "fr" => { vec!["le", "la", "les", "l", "un", "une", "des", "du", "de", "d", "et", "ou", "à", "au", "aux", "en", "par", "pour", "sur", "sous", "dans", "avec"] }
+        "it" => { vec!["il", "lo", "la", "i", "gli", "le", "un", "uno", "una", "di", "del", "della", "dei", "degli", "delle", "e", "o", "a", "al", "alla", "in", "con", "per", "su", "da"] }
+        _ => { return None }
+    };
+    Some(stop_words)
+}
+
+fn roman_lookalikes(language: &str) -> Vec<&'static str> {
+    match language {
+        "en" => { vec!["i", "mix", "mid", "mild", "lid", "dim", "civil", "vivid", "livid"] }
+        "de" => { vec!["mild", "mix", "mic"] }
+        "fr" => { vec!["dix", "mi", "ci", "mil", "mix", "vil"] }
+        "it" => { vec!["di", "vi", "ci", "mi", "li", "dici", "mix"] }
+        _ => { vec![] }
+    }
+}
+
+fn split_keep_whitespace(value: &str) -> Vec<&str> {
+    // words and the whitespace between them, so the original spacing is kept
+    let mut tokens = vec![];
+    let mut start = 0;
+    let mut in_whitespace = None;
+    for (pos, character) in value.char_indices() {
+        let is_whitespace = character.is_whitespace();
+        if in_whitespace.is_some_and(|in_whitespace| in_whitespace != is_whitespace) {
+            tokens.push(&value[start..pos]);
+            start = pos;
+        }
+        in_whitespace = Some(is_whitespace);
+    }
+    if start < value.len() {
+        tokens.push(&value[start..]);
+    }
+    tokens
+}
+
+fn core(token: &str) -> &str {
+    // the word without surrounding punctuation, e.g. "(Basel," -> "Basel"
+    token.trim_matches(|character: char| !character.is_alphanumeric())
+}
+
+fn ends_sentence(token: &str) -> bool {
+    token.trim_end_matches(['"', '\'', ')', '»', '«']).ends_with(['.', '!', '?', ':'])
+}
+
+fn capitalize(token: &str, lowercase_only: bool) -> String {
+    // parts of hyphenated words are capitalized on their own, e.g. "jean-paul" -> "Jean-Paul"
+    let lowercase = token.to_lowercase();
+    if lowercase_only {
+        return lowercase;
+    }
+    let mut capitalized = String::new();
+    let mut upper_next = true;
+    for character in lowercase.chars() {
+        if upper_next && character.is_alphanumeric() {
+            capitalized.extend(character.to_uppercase());
+            upper_next = false;
+        } else {
+            capitalized.push(character);
+            if character == '-' {
+                upper_next = true;
+            }
+        }
+    }
+    capitalized
+}
+
 #[cfg(test)]
 mod test {
     use crate::parse_hcl::methods_domain::lower_upper_method::WrapperLowerUpperMethod;
@@ -127,4 +418,49 @@ mod test {
    });
         let result = WrapperLowerUpperMethod(block).to_upper_method();
         assert!(result.is_ok());
-    }}
+    }
+    #[test]
+    fn test_case_method() {
+        let block = hcl::block!(case "hasTitle"{
+            input = "title"
+            mode = "title"
+            exceptions = ["von", "van der", "McDonald"]
+            roman_numerals = true
+        });
+        let result = WrapperLowerUpperMethod(block).to_case_method();
+        assert!(result.is_ok());
+        let case_method = result.unwrap();
+        assert_eq!(case_method.change_case("the letters of louis xiv to  jean-paul van der berg"), "The Letters of Louis XIV to  Jean-Paul van der Berg");
+        assert_eq!(case_method.change_case("VON GOETHE AND MCDONALD: A STUDY"), "von Goethe and McDonald: A Study");
+    }
+    #[test]
+    fn test_case_method_sentence() {
+        let block = hcl::block!(case "hasDescription"{
+            input = "description"
+            mode = "sentence"
+        });
+        let case_method = WrapperLowerUpperMethod(block).to_case_method().unwrap();
+        assert_eq!(case_method.change_case("A LETTER FROM BASEL. WRITTEN IN WINTER"), "A letter from basel. Written in winter");
+    }
+    #[test]
+    fn test_case_method_roman_numerals() {
+        // stop words and words of the language are not taken for roman numerals
+        let block = hcl::block!(case "hasTitle"{
+            input = "title"
+            mode = "title"
+            language = "it"
+            roman_numerals = true
+        });
+        let case_method = WrapperLowerUpperMethod(block).to_case_method().unwrap();
+        assert_eq!(case_method.change_case("storia di roma e dei papi di pio ix"), "Storia di Roma e dei Papi di Pio IX");
+        assert_eq!(case_method.change_case("i papi del secolo xvi"), "I Papi del Secolo XVI");
+        let block = hcl::block!(case "hasTitle"{
+            input = "title"
+            mode = "title"
+            language = "fr"
+            roman_numerals = true
+        });
+        let case_method = WrapperLowerUpperMethod(block).to_case_method().unwrap();
+        assert_eq!(case_method.change_case("dix lettres de louis xiv"), "Dix Lettres de Louis XIV");
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+use hcl::{Block, Expression};
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::number_trait::NumberTrait;
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperMapMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureMapMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    table: Option<HashMap<String, String>>,
+    file: Option<MapFile>,
+    case_insensitive: Option<bool>,
+    default: Option<String>,
+    fail_on_unmapped: Option<bool>,
+}
+
+impl TransientStructureMapMethod {
+    fn new(output: String) -> TransientStructureMapMethod {
+        TransientStructureMapMethod {
+            output,
+            input: None,
+            table: None,
+            file: None,
+            case_insensitive: None,
+            default: None,
+            fail_on_unmapped: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_table(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.table.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple table-attributes  in method '{:?}'.", self.output)));
+        }
+        self.table = Option::from(expression.to_string_map()?);
+        Ok(())
+    }
+    fn add_file(&mut self, file: MapFile) -> Result<(), HCLDataError> {
+        if self.file.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple file-blocks  in method '{:?}'.", self.output)));
+        }
+        self.file = Option::from(file);
+        Ok(())
+    }
+    fn add_case_insensitive(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.case_insensitive.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple case_insensitive-attributes  in method '{:?}'.", self.output)));
+        }
+        self.case_insensitive = Option::from(expression.to_bool()?);
+        Ok(())
+    }
+    fn add_default(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.default.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple default-attributes  in method '{:?}'.", self.output)));
+        }
+        self.default = Option::from(expression.to_string_2()?);
+        Ok(())
+    }
+    fn add_fail_on_unmapped(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.fail_on_unmapped.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple fail_on_unmapped-attributes  in method '{:?}'.", self.output)));
+        }
+        self.fail_on_unmapped = Option::from(expression.to_bool()?);
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("map-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        if self.table.is_none() && self.file.is_none() {
+            return Err(HCLDataError::ParsingError(format!("map-method '{:?}' needs either a table-attribute or a file-block", self)));
+        }
+        if self.table.is_some() && self.file.is_some() {
+            return Err(HCLDataError::ParsingError(format!("map-method '{:?}' has a table-attribute and a file-block, only one is allowed", self)));
+        }
+        if self.default.is_some() && self.fail_on_unmapped == Some(true) {
+            return Err(HCLDataError::ParsingError(format!("map-method '{:?}' has a default-value and fail_on_unmapped set to true, only one is allowed", self)));
+        }
+        Ok(())
+    }
+}
+
+impl WrapperMapMethod {
+    pub(crate) fn to_map_method(&self) -> Result<MapMethod, HCLDataError> {
+        let mut transient_structure = TransientStructureMapMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "table" => {
+                    transient_structure.add_table(attribute.expr.to_owned())?;
+                }
+                "case_insensitive" => {
+                    transient_structure.add_case_insensitive(attribute.expr.to_owned())?;
+                }
+                "default" => {
+                    transient_structure.add_default(attribute.expr.to_owned())?;
+                }
+                "fail_on_unmapped" => {
+                    transient_structure.add_fail_on_unmapped(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        for block in self.0.blocks() {
+            match block.identifier.as_str() {
+                "file" => {
+                    transient_structure.add_file(WrapperMapFile(block.to_owned()).to_map_file()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown block '{:?}' in method '{:?}'.", block, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        Ok(MapMethod::new(transient_structure))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MapMethod {
+    pub output: String,
+    pub input: HeaderValue,
+    // inline table or, for a file, the table loaded before the transformations are performed
+    pub table: HashMap<String, String>,
+    pub file: Option<MapFile>,
+    pub case_insensitive: bool,
+    pub default: Option<String>,
+    pub fail_on_unmapped: bool,
+}
+
+impl MapMethod {
+    fn new(transient_structure: TransientStructureMapMethod) -> MapMethod {
+        MapMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            table: transient_structure.table.unwrap_or_default(),
+            file: transient_structure.file,
+            case_insensitive: transient_structure.case_insensitive.unwrap_or(false),
+            default: transient_structure.default,
+            fail_on_unmapped: transient_structure.fail_on_unmapped.unwrap_or(false),
+        }
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("map-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        Ok(())
+    }
+    pub(crate) fn lookup_table(&self) -> Result<HashMap<String, String>, HCLDataError> {
+        // keys are trimmed and, if case-insensitive, lowercased; keys that become equal must not map to different values
+        let mut lookup_table: HashMap<String, String> = HashMap::new();
+        for (key, value) in self.table.iter() {
+            let key = self.lookup_key(key);
+            if let Some(existing) = lookup_table.get(&key) {
+                if existing != value {
+                    return Err(HCLDataError::InputError(format!("map '{}': key '{}' maps to different values '{}' and '{}'.", self.output, key, existing, value)));
+                }
+            }
+            lookup_table.insert(key, value.to_owned());
+        }
+        Ok(lookup_table)
+    }
+    pub(crate) fn lookup_key(&self, value: &str) -> String {
+        if self.case_insensitive {
+            value.trim().to_lowercase()
+        } else {
+            value.trim().to_string()
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MapFile {
+    // path relative to the resources folder, csv or xlsx
+    pub path: String,
+    pub key: HeaderValue,
+    pub value: HeaderValue,
+    pub sheet: usize,
+}
+
+struct WrapperMapFile(Block);
+
+impl WrapperMapFile {
+    fn to_map_file(&self) -> Result<MapFile, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut path: Option<String> = None;
+        let mut key: Option<HeaderValue> = None;
+        let mut value: Option<HeaderValue> = None;
+        let mut sheet: Option<usize> = None;
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "path" => {
+                    if path.is_some() {
+                        return Err(HCLDataError::ParsingError(format!("found multiple path-attributes in file-block '{:?}'.", self.0)));
+                    }
+                    path = Some(attribute.expr.to_string_2()?);
+                }
+                "key" => {
+                    if key.is_some() {
+                        return Err(HCLDataError::ParsingError(format!("found multiple key-attributes in file-block '{:?}'.", self.0)));
+                    }
+                    key = Some(attribute.expr.to_header_value()?);
+                }
+                "value" => {
+                    if value.is_some() {
+                        return Err(HCLDataError::ParsingError(format!("found multiple value-attributes in file-block '{:?}'.", self.0)));
+                    }
+                    value = Some(attribute.expr.to_header_value()?);
+                }
+                "sheet" => {
+                    if sheet.is_some() {
+                        return Err(HCLDataError::ParsingError(format!("found multiple sheet-attributes in file-block '{:?}'.", self.0)));
+                    }
+                    sheet = Some(attribute.expr.to_number()?.as_usize()?);
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in file-block '{:?}'.", attribute, self.0)));
+                }
+            }
+        }
+        let path = match path {
+            None => { return Err(HCLDataError::ParsingError(format!("file-block '{:?}' doesn't have a path-attribute provided", self.0))) }
+            Some(path) => { path }
+        };
+        if !(path.ends_with(".csv") || path.ends_with(".xlsx")) {
+            return Err(HCLDataError::ParsingError(format!("file-block: only csv- or xlsx-files allowed, but found: '{}'", path)));
+        }
+        if key.is_none() || value.is_none() {
+            return Err(HCLDataError::ParsingError(format!("file-block '{:?}' needs a key- and a value-attribute", self.0)));
+        }
+        let sheet = sheet.unwrap_or(1);
+        if sheet == 0 {
+            return Err(HCLDataError::ParsingError("file-block: sheet-numbers start at 1.".to_string()));
+        }
+        Ok(MapFile { path, key: key.unwrap(), value: value.unwrap(), sheet })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::map_method::WrapperMapMethod;
+
+    #[test]
+    fn test_map_method_table() {
+        let block = block!(map "newCode" {
+            input = "oldCode"
+            table = { "A1" = "X1", "b2" = "Y2" }
+            case_insensitive = true
+            default = "unknown"
+        });
+        let result = WrapperMapMethod(block.to_owned()).to_map_method();
+        assert!(result.is_ok());
+        let map_method = result.unwrap();
+        assert_eq!(map_method.lookup_table().unwrap().get("a1").unwrap(), "X1");
+    }
+    #[test]
+    fn test_map_method_file() {
+        let block = block!(map "newCode" {
+            input = "oldCode"
+            file {
+                path = "concordance.csv"
+                key = "old"
+                value = "new"
+            }
+            fail_on_unmapped = true
+        });
+        let result = WrapperMapMethod(block.to_owned()).to_map_method();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().file.unwrap().sheet, 1);
+    }
+    #[test]
+    fn test_map_method_default_and_fail() {
+        let block = block!(map "newCode" {
+            input = "oldCode"
+            table = { "A1" = "X1" }
+            default = "unknown"
+            fail_on_unmapped = true
+        });
+        let result = WrapperMapMethod(block.to_owned()).to_map_method();
+        assert!(result.is_err());
+    }
+}
@@ -20,3 +20,24 @@ pub mod permissions_create;
 pub mod replace_label_name;
 pub mod update_with_server_method;
 pub mod separate_method;
+pub mod extract_method;
+pub mod slugify_method;
+pub mod to_richtext_method;
+pub mod to_uri_method;
+pub mod script_method;
+pub mod when;
+pub mod map_method;
+pub mod normalize_method;
+pub mod split_method;
+pub mod distinct_method;
+pub mod to_number_method;
+pub mod to_boolean_method;
+pub mod to_color_method;
+pub mod to_time_method;
+pub mod to_geoname_method;
+pub mod hash_create;
+pub mod uuid_create;
+pub mod constant_create;
+pub mod row_number_create;
+pub mod source_create;
+pub mod join_method;
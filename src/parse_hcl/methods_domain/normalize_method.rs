@@ -0,0 +1,217 @@
+use hcl::{Block, Expression};
+use icu_normalizer::ComposingNormalizerBorrowed;
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperNormalizeMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureNormalizeMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    steps: Option<Normalization>,
+}
+
+impl TransientStructureNormalizeMethod {
+    fn new(output: String) -> TransientStructureNormalizeMethod {
+        TransientStructureNormalizeMethod {
+            output,
+            input: None,
+            steps: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_steps(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.steps.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple steps-attributes  in method '{:?}'.", self.output)));
+        }
+        self.steps = Option::from(Normalization::from_steps(&expression)?);
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("normalize-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        if self.steps.is_none() {
+            return Err(HCLDataError::ParsingError(format!("normalize-method '{:?}' doesn't have a steps-attribute provided", self)));
+        }
+        Ok(())
+    }
+}
+
+impl WrapperNormalizeMethod {
+    pub(crate) fn to_normalize_method(&self) -> Result<NormalizeMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureNormalizeMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "steps" => {
+                    transient_structure.add_steps(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        Ok(NormalizeMethod::new(transient_structure))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NormalizeMethod {
+    pub output: String,
+    pub input: HeaderValue,
+    pub steps: Normalization,
+}
+
+impl NormalizeMethod {
+    fn new(transient_structure: TransientStructureNormalizeMethod) -> NormalizeMethod {
+        NormalizeMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            steps: transient_structure.steps.unwrap(),
+        }
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("normalize-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnicodeForm {
+    NFC,
+    NFKC,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Normalization {
+    pub unicode: Option<UnicodeForm>,
+    pub whitespace: bool,
+    pub invisible: bool,
+    pub quotes: bool,
+    pub dashes: bool,
+}
+
+impl Normalization {
+    pub(crate) fn from_steps(expression: &Expression) -> Result<Normalization, HCLDataError> {
+        // steps = ["nfc", "whitespace", "invisible", "quotes", "dashes"]; also used as sheet-wide default
+        let mut normalization = Normalization::default();
+        let steps = expression.to_vec()?;
+        if steps.is_empty() {
+            return Err(HCLDataError::ParsingError("normalize: 'steps' is empty.".to_string()));
+        }
+        for step in steps.iter() {
+            let step = step.to_string_2()?;
+            match step.as_str() {
+                "nfc" | "nfkc" => {
+                    if normalization.unicode.is_some() {
+                        return Err(HCLDataError::ParsingError(format!("normalize: only one of 'nfc' or 'nfkc' allowed in steps '{:?}'.", steps)));
+                    }
+                    normalization.unicode = Some(if step == "nfc" { UnicodeForm::NFC } else { UnicodeForm::NFKC });
+                }
+                "whitespace" => { normalization.whitespace = true }
+                "invisible" => { normalization.invisible = true }
+                "quotes" => { normalization.quotes = true }
+                "dashes" => { normalization.dashes = true }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("normalize: unknown step '{}', allowed are 'nfc', 'nfkc', 'whitespace', 'invisible', 'quotes' and 'dashes'.", step)));
+                }
+            }
+        }
+        Ok(normalization)
+    }
+    pub(crate) fn normalize(&self, value: &str) -> String {
+        let mut value = match self.unicode {
+            None => { value.to_string() }
+            Some(UnicodeForm::NFC) => { ComposingNormalizerBorrowed::new_nfc().normalize(value).into_owned() }
+            Some(UnicodeForm::NFKC) => { ComposingNormalizerBorrowed::new_nfkc().normalize(value).into_owned() }
+        };
+        if self.invisible {
+            // control characters except line breaks and tabs, zero-width characters and soft hyphens
+            value = value.chars()
+                .filter(|char| !(char.is_control() && *char != '\n' && *char != '\t'))
+                .filter(|char| !matches!(char, '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}'))
+                .collect();
+        }
+        if self.quotes {
+            value = value.chars().map(|char| match char {
+                '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => '\'',
+                '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => '"',
+                _ => char,
+            }).collect();
+        }
+        if self.dashes {
+            value = value.chars().map(|char| match char {
+                '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}' | '\u{2212}' => '-',
+                _ => char,
+            }).collect();
+        }
+        if self.whitespace {
+            value = collapse_whitespace(&value);
+        }
+        value
+    }
+}
+
+fn collapse_whitespace(value: &str) -> String {
+    // runs of whitespace (incl. non-breaking spaces) become one space, or one line break if the run contains one
+    let mut collapsed = String::new();
+    let mut run: Option<char> = None;
+    for char in value.trim().chars() {
+        if char.is_whitespace() {
+            if char == '\n' {
+                run = Some('\n');
+            } else if run.is_none() {
+                run = Some(' ');
+            }
+            continue;
+        }
+        if let Some(whitespace) = run.take() {
+            collapsed.push(whitespace);
+        }
+        collapsed.push(char);
+    }
+    collapsed
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::normalize_method::WrapperNormalizeMethod;
+
+    #[test]
+    fn test_normalize_method() {
+        let block = block!(normalize "cleanTitle" {
+            input = "title"
+            steps = ["nfc", "whitespace", "invisible", "quotes", "dashes"]
+        });
+        let result = WrapperNormalizeMethod(block.to_owned()).to_normalize_method();
+        assert!(result.is_ok());
+        let steps = result.unwrap().steps;
+        assert_eq!(steps.normalize(" Cafe\u{0301}\u{00A0} \u{201C}Zu\u{200B}rich\u{201D}  12\u{2013}14 "), "Café \"Zurich\" 12-14");
+    }
+    #[test]
+    fn test_normalize_method_two_unicode_forms() {
+        let block = block!(normalize "cleanTitle" {
+            input = "title"
+            steps = ["nfc", "nfkc"]
+        });
+        let result = WrapperNormalizeMethod(block.to_owned()).to_normalize_method();
+        assert!(result.is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use hcl::Block;
 use crate::expression_trait::ExpressionTransform;
 use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::methods_domain::when::{When, WrapperWhen};
 use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
 use crate::parse_xlsx::domain::permissions::Permissions;
 use crate::parse_xlsx::domain::permissions::PermissionsWrapper;
@@ -10,22 +11,24 @@ pub struct WrapperPermissionsCreate(pub(crate) Block);
 #[derive(Clone, Debug)]
 pub struct PermissionsCreate {
     pub output: String,
-    pub permissions: Permissions
+    pub permissions: Permissions,
+    pub when: Option<When>,
 }
 
 impl PermissionsCreate {
     fn new(transient_permissions_create: TransientPermissionsCreate) -> PermissionsCreate {
-        PermissionsCreate{ output: transient_permissions_create.output, permissions: transient_permissions_create.permissions.unwrap() }
+        PermissionsCreate{ output: transient_permissions_create.output, permissions: transient_permissions_create.permissions.unwrap(), when: transient_permissions_create.when }
     }
 }
 
 struct TransientPermissionsCreate {
     output: String,
-    permissions: Option<Permissions>
+    permissions: Option<Permissions>,
+    when: Option<When>,
 }
 impl TransientPermissionsCreate  {
     fn new(output: String) -> Self {
-        Self{ output , permissions: None }
+        Self{ output , permissions: None, when: None }
     }
     fn add_permissions(&mut self, permissions: Permissions) -> Result<(), HCLDataError>{
         if self.permissions.is_some() {
@@ -34,10 +37,16 @@ impl TransientPermissionsCreate  {
         self.permissions = Some(permissions);
         Ok(())
     }
+    fn add_when(&mut self, when: When) -> Result<(), HCLDataError> {
+        if self.when.is_some() {
+            return Err(HCLDataError::InputError(format!("Permissions-create-method: Multiple when-blocks declared in method with output '{}'", self.output)));
+        }
+        self.when = Some(when);
+        Ok(())
+    }
 }
 impl WrapperPermissionsCreate {
     pub(crate) fn to_permissions_create_method(&self, output: String) -> Result<PermissionsCreate, HCLDataError> {
-        self.0.no_blocks()?;
         let mut transient = TransientPermissionsCreate::new(output);
         for attribute in self.0.attributes() {
             match attribute.key.as_str() {
@@ -55,6 +64,16 @@ impl WrapperPermissionsCreate {
                 }
             }
         }
+        for block in self.0.blocks() {
+            match block.identifier.as_str() {
+                "when" => {
+                    transient.add_when(WrapperWhen(block.to_owned()).to_when()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::InputError(format!("Permissions-create-method: block should be 'when', but found: '{}'", block.identifier)));
+                }
+            }
+        }
         Ok(PermissionsCreate::new(transient))
     }
 }
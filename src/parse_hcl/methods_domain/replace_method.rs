@@ -1,9 +1,11 @@
 use hcl::Expression;
+use regex::Regex;
 use crate::expression_trait::ExpressionTransform;
 use crate::parse_hcl::errors::HCLDataError;
 use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
 use crate::parse_hcl::methods_domain::behavior_type::BehaviorType;
 use crate::parse_hcl::methods_domain::target_type::TargetType;
+use crate::parse_hcl::methods_domain::when::{When, WrapperWhen};
 use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
 
 pub struct WrapperReplaceMethod(pub(crate) hcl::Block);
@@ -16,6 +18,8 @@ struct TransientStructureReplaceMethod {
     new: Option<String>,
     behavior: Option<String>,
     target: Option<String>,
+    mode: Option<String>,
+    when: Option<When>,
 }
 
 impl TransientStructureReplaceMethod {
@@ -27,6 +31,8 @@ impl TransientStructureReplaceMethod {
             behavior: None,
             target: None,
             new: None,
+            mode: None,
+            when: None,
         }
     }
     fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
@@ -67,6 +73,12 @@ impl TransientStructureReplaceMethod {
                     }
                     self.target = Option::from(attribute.expr.to_string_2()?);
                 }
+                "mode" => {
+                    if self.mode.is_some() {
+                        return Err(HCLDataError::ParsingError(format!("found multiple mode-attributes  in method '{:?}'.", self.output)));
+                    }
+                    self.mode = Option::from(attribute.expr.to_string_2()?);
+                }
                 _ => {
                     return Err(HCLDataError::ParsingError(format!("found 'condition'-attribute that is unknown in method '{:?}', found: {:?}.", self.output, attribute)));
                 }
@@ -74,6 +86,13 @@ impl TransientStructureReplaceMethod {
         }
         Ok(())
     }
+    fn add_when(&mut self, when: When) -> Result<(), HCLDataError> {
+        if self.when.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple when-blocks in method '{:?}'.", self.output)));
+        }
+        self.when = Some(when);
+        Ok(())
+    }
     fn is_consistent(&self) -> Result<(), HCLDataError> {
         if self.input.is_none() {
             return Err(HCLDataError::ParsingError(format!("replace-method '{:?}' doesn't have an input-attribute provided", self)));
@@ -118,6 +137,9 @@ impl WrapperReplaceMethod{
                 "condition" => {
                     transient_structure.add_condition(block.to_owned())?;
                 }
+                "when" => {
+                    transient_structure.add_when(WrapperWhen(block.to_owned()).to_when()?)?;
+                }
                 _ => {
                     return Err(HCLDataError::ParsingError(format!("found this unknown block '{:?}' in method '{:?}'.", block, transient_structure.output)));
                 }
@@ -137,6 +159,9 @@ pub struct ReplaceMethod {
     pub new: String,
     pub behavior: BehaviorType,
     pub target: TargetType,
+    // set if 'mode' is "regex": 'old' is the pattern and 'new' may reference groups by $1 or $name
+    pub regex: Option<Regex>,
+    pub when: Option<When>,
 }
 
 
@@ -145,14 +170,34 @@ impl ReplaceMethod {
 
        let behavior_type: BehaviorType = BehaviorType::behavior_type(transient_structure.behavior.unwrap())?;
         let target_type: TargetType = TargetType::target_type(transient_structure.target.unwrap())?;
+        let old = transient_structure.old.unwrap();
+        let new = transient_structure.new.unwrap();
+        let regex = match transient_structure.mode {
+            None => { None }
+            Some(mode) => {
+                match mode.as_str() {
+                    "literal" => { None }
+                    "regex" => {
+                        let regex = Regex::new(old.as_str())?;
+                        check_group_references(&regex, &new)?;
+                        Some(regex)
+                    }
+                    _ => {
+                        return Err(HCLDataError::ParsingError(format!("unknown value for 'mode'-attribute: '{}', only 'literal' or 'regex' allowed.", mode)));
+                    }
+                }
+            }
+        };
 
         Ok(ReplaceMethod{
             output: transient_structure.output,
             input: transient_structure.input.unwrap(),
-            old: transient_structure.old.unwrap(),
-            new: transient_structure.new.unwrap(),
+            old,
+            new,
             behavior: behavior_type,
             target: target_type,
+            regex,
+            when: transient_structure.when,
         })
     }
     pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
@@ -163,6 +208,26 @@ impl ReplaceMethod {
     }
 }
 
+fn check_group_references(regex: &Regex, new: &String) -> Result<(), HCLDataError> {
+    // every $1, ${1}, $name or ${name} in 'new' must refer to an existing group of the pattern; '$$' is a literal '$'
+    let reference = Regex::new(r"\$(\$|\{([^}]*)\}|([0-9A-Za-z_]+))").unwrap();
+    let names: Vec<&str> = regex.capture_names().flatten().collect();
+    for caps in reference.captures_iter(new) {
+        let group = match caps.get(2).or(caps.get(3)) {
+            None => { continue }
+            Some(group) => { group.as_str() }
+        };
+        let exists = match group.parse::<usize>() {
+            Ok(number) => { number < regex.captures_len() }
+            Err(_) => { names.contains(&group) }
+        };
+        if !exists {
+            return Err(HCLDataError::ParsingError(format!("replace: 'new' refers to group '{}' that doesn't exist in regex '{}'.", group, regex.as_str())));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use hcl::block;
@@ -184,4 +249,57 @@ mod test {
         println!("{:?}", result);
         assert!(result.is_ok());
     }
+    #[test]
+    fn test_replace_method_when() {
+        let block = block!(replace "replacement"{
+            input = 3
+            old = "Dict"
+            new = "Dictionary"
+            condition {
+                behavior = "lazy"
+                target = "part"
+            }
+            when {
+                input = "type"
+                equals = "book"
+                fallback = 4
+            }
+        });
+
+        let result = WrapperReplaceMethod(block.to_owned()).to_replace_method();
+        assert!(result.is_ok());
+        assert!(result.unwrap().when.is_some());
+    }
+    #[test]
+    fn test_replace_method_regex() {
+        let block = block!(replace "pages"{
+            input = "pages"
+            old = "p\\. (?<from>\\d+)-(\\d+)"
+            new = "${from}–$2"
+            condition {
+                behavior = "greedy"
+                target = "part"
+                mode = "regex"
+            }
+        });
+        let result = WrapperReplaceMethod(block.to_owned()).to_replace_method();
+        assert!(result.is_ok());
+        let regex = result.unwrap().regex.unwrap();
+        assert_eq!(regex.replace_all("p. 12-14", "${from}–$2"), "12–14");
+    }
+    #[test]
+    fn test_replace_method_regex_unknown_group() {
+        let block = block!(replace "pages"{
+            input = "pages"
+            old = "p\\. (\\d+)"
+            new = "$2"
+            condition {
+                behavior = "greedy"
+                target = "part"
+                mode = "regex"
+            }
+        });
+        let result = WrapperReplaceMethod(block.to_owned()).to_replace_method();
+        assert!(result.is_err());
+    }
 }
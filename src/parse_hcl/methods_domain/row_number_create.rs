@@ -0,0 +1,91 @@
+use hcl::Block;
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::methods_domain::when::{When, WrapperWhen};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperRowNumberCreate(pub(crate) Block);
+
+#[derive(Clone, Debug)]
+pub struct RowNumberCreate {
+    pub output: String,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    pub when: Option<When>,
+}
+
+impl RowNumberCreate {
+    fn new(transient_row_number_create: TransientRowNumberCreate) -> RowNumberCreate {
+        RowNumberCreate{
+            output: transient_row_number_create.output,
+            prefix: transient_row_number_create.prefix,
+            suffix: transient_row_number_create.suffix,
+            when: transient_row_number_create.when,
+        }
+    }
+    pub(crate) fn row_number(&self, excel_row: usize) -> String {
+        format!("{}{}{}", self.prefix.as_deref().unwrap_or(""), excel_row, self.suffix.as_deref().unwrap_or(""))
+    }
+}
+
+struct TransientRowNumberCreate {
+    output: String,
+    prefix: Option<String>,
+    suffix: Option<String>,
+    when: Option<When>,
+}
+impl TransientRowNumberCreate {
+    fn new(output: String) -> Self {
+        Self{ output, prefix: None, suffix: None, when: None }
+    }
+    fn add_prefix(&mut self, prefix: String) -> Result<(), HCLDataError> {
+        if self.prefix.is_some() {
+            return Err(HCLDataError::InputError(format!("row-number-create-method: multiple prefix: First: '{}', Second: '{}'", self.prefix.as_ref().unwrap(), prefix)));
+        }
+        self.prefix = Some(prefix);
+        Ok(())
+    }
+    fn add_suffix(&mut self, suffix: String) -> Result<(), HCLDataError> {
+        if self.suffix.is_some() {
+            return Err(HCLDataError::InputError(format!("row-number-create-method: multiple suffix: First: '{}', Second: '{}'", self.suffix.as_ref().unwrap(), suffix)));
+        }
+        self.suffix = Some(suffix);
+        Ok(())
+    }
+    fn add_when(&mut self, when: When) -> Result<(), HCLDataError> {
+        if self.when.is_some() {
+            return Err(HCLDataError::InputError(format!("row-number-create-method: multiple when-blocks in method with output '{}'", self.output)));
+        }
+        self.when = Some(when);
+        Ok(())
+    }
+}
+impl WrapperRowNumberCreate {
+    pub(crate) fn to_row_number_create_method(&self, output: String) -> Result<RowNumberCreate, HCLDataError> {
+        let mut transient = TransientRowNumberCreate::new(output);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "prefix" => {
+                    transient.add_prefix(attribute.expr.to_string_2()?)?;
+                }
+                "suffix" => {
+                    transient.add_suffix(attribute.expr.to_string_2()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("row-number-create-method: found this unknown attribute '{:?}' in method '{:?}'.", attribute, self.0.labels)));
+                }
+            }
+        }
+        for block in self.0.blocks() {
+            match block.identifier.as_str() {
+                "when" => {
+                    transient.add_when(WrapperWhen(block.to_owned()).to_when()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("row-number-create-method: found this unknown block '{:?}' in method '{:?}'.", block.identifier, self.0.labels)));
+                }
+            }
+        }
+        Ok(RowNumberCreate::new(transient))
+    }
+}
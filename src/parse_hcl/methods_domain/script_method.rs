@@ -0,0 +1,309 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+use hcl::{Block, Expression};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use rhai::module_resolvers::DummyModuleResolver;
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::number_trait::NumberTrait;
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperScriptMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureScriptMethod {
+    output: String,
+    input: Option<Vec<HeaderValue>>,
+    script: Option<AST>,
+    per: Option<ScriptPer>,
+    timeout: Option<usize>,
+}
+
+impl TransientStructureScriptMethod {
+    fn new(output: String) -> TransientStructureScriptMethod {
+        TransientStructureScriptMethod {
+            output,
+            input: None,
+            script: None,
+            per: None,
+            timeout: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        let mut input = vec![];
+        for expr in expression.to_vec()? {
+            input.push(expr.to_header_value()?);
+        }
+        self.input = Some(input);
+        Ok(())
+    }
+    fn add_script(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.script.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple script-attributes  in method '{:?}'.", self.output)));
+        }
+        // syntax errors are found before the excel-files are read
+        let script = expression.to_string_2()?;
+        let ast = match ScriptMethod::engine(DEFAULT_TIMEOUT).compile(&script) {
+            Ok(ast) => { ast }
+            Err(error) => {
+                return Err(HCLDataError::ParsingError(format!("script-method '{}': cannot compile script: {}", self.output, error)));
+            }
+        };
+        self.script = Some(ast);
+        Ok(())
+    }
+    fn add_per(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.per.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple per-attributes  in method '{:?}'.", self.output)));
+        }
+        let per = expression.to_string_2()?;
+        self.per = match per.as_str() {
+            "value" => { Some(ScriptPer::Value) }
+            "row" => { Some(ScriptPer::Row) }
+            _ => {
+                return Err(HCLDataError::ParsingError(format!("script-method '{}': unknown value for 'per': '{}', only 'value' or 'row' allowed.", self.output, per)));
+            }
+        };
+        Ok(())
+    }
+    fn add_timeout(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.timeout.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple timeout-attributes  in method '{:?}'.", self.output)));
+        }
+        let timeout = match expression {
+            Expression::Number(number) => { number.as_usize()? }
+            _ => {
+                return Err(HCLDataError::ParsingError(format!("script-method '{}': 'timeout' is not of type Number: '{}'", self.output, expression)));
+            }
+        };
+        if timeout == 0 {
+            return Err(HCLDataError::ParsingError(format!("script-method '{}': timeout must be greater than 0.", self.output)));
+        }
+        self.timeout = Some(timeout);
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        match &self.input {
+            None => {
+                return Err(HCLDataError::ParsingError(format!("script-method '{}' doesn't have an input-attribute provided", self.output)));
+            }
+            Some(input) => {
+                if input.is_empty() {
+                    return Err(HCLDataError::ParsingError(format!("script-method '{}': input is empty.", self.output)));
+                }
+            }
+        }
+        if self.script.is_none() {
+            return Err(HCLDataError::ParsingError(format!("script-method '{}' doesn't have a script-attribute provided", self.output)));
+        }
+        Ok(())
+    }
+}
+
+impl WrapperScriptMethod {
+    pub(crate) fn to_script_method(&self) -> Result<ScriptMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureScriptMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "script" => {
+                    transient_structure.add_script(attribute.expr.to_owned())?;
+                }
+                "per" => {
+                    transient_structure.add_per(attribute.expr.to_owned())?;
+                }
+                "timeout" => {
+                    transient_structure.add_timeout(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        Ok(ScriptMethod::new(transient_structure))
+    }
+}
+
+// milliseconds a single run of a script may take
+const DEFAULT_TIMEOUT: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptPer {
+    // the script runs for every value of the first input, which is available as 'value'
+    Value,
+    // the script runs once for every row
+    Row,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScriptMethod {
+    pub output: String,
+    pub input: Vec<HeaderValue>,
+    pub script: AST,
+    pub per: ScriptPer,
+    pub timeout: usize,
+}
+
+impl ScriptMethod {
+    fn new(transient_structure: TransientStructureScriptMethod) -> ScriptMethod {
+        ScriptMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            script: transient_structure.script.unwrap(),
+            per: transient_structure.per.unwrap_or(ScriptPer::Value),
+            timeout: transient_structure.timeout.unwrap_or(DEFAULT_TIMEOUT),
+        }
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.iter().any(|input| input.is_equal(&self.output)) {
+            return Err(HCLDataError::ParsingError(format!("script-method has the same in- and output-String, which is forbidden: '{:?}'", self.output)));
+        }
+        Ok(())
+    }
+    pub(crate) fn input_name(input: &HeaderValue) -> String {
+        // the name of the input in 'row' and 'values', columns given by number are named by their number
+        match input {
+            HeaderValue::Name(name) => { name.to_owned() }
+            HeaderValue::Number(number) => { number.to_string() }
+        }
+    }
+    fn engine(timeout: usize) -> Engine {
+        // no modules can be imported, so scripts have no access to files; limits keep scripts from running away
+        let mut engine = Engine::new();
+        engine.set_module_resolver(DummyModuleResolver::new());
+        engine.disable_symbol("eval");
+        engine.set_max_call_levels(32);
+        engine.set_max_string_size(1_000_000);
+        engine.set_max_array_size(100_000);
+        engine.set_max_map_size(100_000);
+        let started = Cell::new(Instant::now());
+        engine.on_progress(move |operations| {
+            // the first operation of every run resets the clock
+            if operations == 1 {
+                started.set(Instant::now());
+            }
+            if started.get().elapsed() > Duration::from_millis(timeout as u64) {
+                return Some(Dynamic::UNIT);
+            }
+            None
+        });
+        engine
+    }
+    pub(crate) fn runner(&self) -> ScriptRunner<'_> {
+        ScriptRunner { method: self, engine: ScriptMethod::engine(self.timeout) }
+    }
+}
+
+pub struct ScriptRunner<'a> {
+    method: &'a ScriptMethod,
+    engine: Engine,
+}
+
+impl ScriptRunner<'_> {
+    pub(crate) fn run(&self, value: Option<&str>, row: &[&Vec<String>]) -> Result<Vec<String>, String> {
+        // returns the new values or the reason why the script failed
+        let mut row_map = Map::new();
+        let mut values_map = Map::new();
+        for (input, values) in self.method.input.iter().zip(row.iter()) {
+            let name = ScriptMethod::input_name(input);
+            row_map.insert(name.as_str().into(), Dynamic::from(values.first().cloned().unwrap_or_default()));
+            values_map.insert(name.as_str().into(), Dynamic::from(values.iter().map(|value| Dynamic::from(value.to_owned())).collect::<Array>()));
+        }
+        let mut scope = Scope::new();
+        if let Some(value) = value {
+            scope.push("value", value.to_string());
+        }
+        scope.push("row", row_map);
+        scope.push("values", values_map);
+        let result = match self.engine.eval_ast_with_scope::<Dynamic>(&mut scope, &self.method.script) {
+            Ok(result) => { result }
+            Err(error) => {
+                return Err(match *error {
+                    EvalAltResult::ErrorTerminated(_, _) => { format!("script took longer than {} ms", self.method.timeout) }
+                    error => { format!("script failed: {}", error) }
+                });
+            }
+        };
+        // () is written as empty value, an array as multiple values
+        if result.is_unit() {
+            return Ok(vec![String::new()]);
+        }
+        if result.is_array() {
+            let values: Vec<String> = result.cast::<Array>().into_iter().map(|value| if value.is_unit() { String::new() } else { value.to_string() }).collect();
+            return Ok(if values.is_empty() { vec![String::new()] } else { values });
+        }
+        Ok(vec![result.to_string()])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::script_method::{ScriptPer, WrapperScriptMethod};
+
+    #[test]
+    fn test_script_method() {
+        let block = block!(script "hasTitle" {
+            input = ["title", "year"]
+            script = "if value == \"\" { () } else { value.trim(); `${value} (${row.year})` }"
+        });
+        let result = WrapperScriptMethod(block.to_owned()).to_script_method();
+        assert!(result.is_ok());
+        let script_method = result.unwrap();
+        assert_eq!(script_method.per, ScriptPer::Value);
+        let runner = script_method.runner();
+        let title = vec!["  Faust ".to_string()];
+        let year = vec!["1808".to_string()];
+        assert_eq!(runner.run(Some(&title[0]), &[&title, &year]).unwrap(), vec!["Faust (1808)"]);
+        assert_eq!(runner.run(Some(""), &[&title, &year]).unwrap(), vec![""]);
+    }
+    #[test]
+    fn test_script_method_per_row() {
+        let block = block!(script "hasKeyword" {
+            input = ["keywords"]
+            per = "row"
+            script = "values.keywords.filter(|keyword| keyword != \"todo\").map(|keyword| keyword.to_upper())"
+        });
+        let script_method = WrapperScriptMethod(block.to_owned()).to_script_method().unwrap();
+        let keywords = vec!["poem".to_string(), "todo".to_string(), "letter".to_string()];
+        assert_eq!(script_method.runner().run(None, &[&keywords]).unwrap(), vec!["POEM", "LETTER"]);
+    }
+    #[test]
+    fn test_script_method_errors() {
+        let block = block!(script "hasTitle" {
+            input = ["title"]
+            script = "let x = ;"
+        });
+        assert!(WrapperScriptMethod(block.to_owned()).to_script_method().is_err());
+        let block = block!(script "hasTitle" {
+            input = ["title"]
+            script = "loop { }"
+            timeout = 50
+        });
+        let script_method = WrapperScriptMethod(block.to_owned()).to_script_method().unwrap();
+        let title = vec!["Faust".to_string()];
+        assert_eq!(script_method.runner().run(Some("Faust"), &[&title]).unwrap_err(), "script took longer than 50 ms");
+    }
+    #[test]
+    fn test_script_method_import() {
+        // scripts cannot load other scripts from disk
+        std::fs::write(std::env::temp_dir().join("excel2xml_import.rhai"), "export const TITLE = \"imported\";").unwrap();
+        let script = format!("import \"{}\" as other; other::TITLE", std::env::temp_dir().join("excel2xml_import").display());
+        let block = block!(script "hasTitle" {
+            input = ["title"]
+            script = (script)
+        });
+        let script_method = WrapperScriptMethod(block.to_owned()).to_script_method().unwrap();
+        let title = vec!["Faust".to_string()];
+        let error = script_method.runner().run(Some("Faust"), &[&title]).unwrap_err();
+        assert!(error.contains("excel2xml_import"), "{}", error);
+    }
+}
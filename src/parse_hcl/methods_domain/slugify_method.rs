@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use hcl::{Block, Expression};
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperSlugifyMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureSlugifyMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    separator: Option<String>,
+    lowercase: Option<bool>,
+    replacements: Option<HashMap<String, String>>,
+    deduplicate: Option<bool>,
+}
+
+impl TransientStructureSlugifyMethod {
+    fn new(output: String) -> TransientStructureSlugifyMethod {
+        TransientStructureSlugifyMethod {
+            output,
+            input: None,
+            separator: None,
+            lowercase: None,
+            replacements: None,
+            deduplicate: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_separator(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.separator.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple separator-attributes  in method '{:?}'.", self.output)));
+        }
+        let separator = expression.to_string_2()?;
+        if !separator.chars().all(|character| matches!(character, '-' | '_' | '.')) {
+            return Err(HCLDataError::ParsingError(format!("slugify-method '{}': separator must consist of '-', '_' or '.', but found: '{}'", self.output, separator)));
+        }
+        self.separator = Option::from(separator);
+        Ok(())
+    }
+    fn add_lowercase(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.lowercase.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple lowercase-attributes  in method '{:?}'.", self.output)));
+        }
+        self.lowercase = Option::from(expression.to_bool()?);
+        Ok(())
+    }
+    fn add_replacements(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.replacements.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple replacements-attributes  in method '{:?}'.", self.output)));
+        }
+        self.replacements = Option::from(expression.to_string_map()?);
+        Ok(())
+    }
+    fn add_deduplicate(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.deduplicate.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple deduplicate-attributes  in method '{:?}'.", self.output)));
+        }
+        self.deduplicate = Option::from(expression.to_bool()?);
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("slugify-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        if let Some(replacements) = &self.replacements {
+            if replacements.keys().any(|key| key.is_empty()) {
+                return Err(HCLDataError::ParsingError(format!("slugify-method '{}': found an empty key in replacements.", self.output)));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl WrapperSlugifyMethod {
+    pub(crate) fn to_slugify_method(&self) -> Result<SlugifyMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureSlugifyMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "separator" => {
+                    transient_structure.add_separator(attribute.expr.to_owned())?;
+                }
+                "lowercase" => {
+                    transient_structure.add_lowercase(attribute.expr.to_owned())?;
+                }
+                "replacements" => {
+                    transient_structure.add_replacements(attribute.expr.to_owned())?;
+                }
+                "deduplicate" => {
+                    transient_structure.add_deduplicate(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        Ok(SlugifyMethod::new(transient_structure))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SlugifyMethod {
+    pub output: String,
+    pub input: HeaderValue,
+    pub separator: String,
+    pub lowercase: bool,
+    // replaced before the transliteration, longest first, e.g. "ä" = "ae"
+    pub replacements: Vec<(String, String)>,
+    // equal slugs of the column get a numeric suffix, e.g. "muller-hans", "muller-hans-2"
+    pub deduplicate: bool,
+}
+
+impl SlugifyMethod {
+    fn new(transient_structure: TransientStructureSlugifyMethod) -> SlugifyMethod {
+        let mut replacements: Vec<(String, String)> = transient_structure.replacements.unwrap_or_default().into_iter().collect();
+        replacements.sort_by(|(first, _), (second, _)| second.chars().count().cmp(&first.chars().count()).then(first.cmp(second)));
+        SlugifyMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            separator: transient_structure.separator.unwrap_or("-".to_string()),
+            lowercase: transient_structure.lowercase.unwrap_or(true),
+            replacements,
+            deduplicate: transient_structure.deduplicate.unwrap_or(false),
+        }
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("slugify-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        Ok(())
+    }
+    pub(crate) fn slugify(&self, value: &str) -> String {
+        let mut value = value.to_string();
+        for (old, new) in self.replacements.iter() {
+            value = value.replace(old, new);
+        }
+        let mut transliterated = deunicode::deunicode(&value);
+        if self.lowercase {
+            transliterated = transliterated.to_lowercase();
+        }
+        // every sequence of other characters than ascii letters and digits becomes one separator
+        transliterated
+            .split(|character: char| !character.is_ascii_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<&str>>()
+            .join(&self.separator)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::slugify_method::WrapperSlugifyMethod;
+
+    #[test]
+    fn test_slugify_method() {
+        let block = block!(slugify "id" {
+            input = "name"
+            separator = "_"
+            replacements = { "ö" = "oe" }
+        });
+        let result = WrapperSlugifyMethod(block.to_owned()).to_slugify_method();
+        assert!(result.is_ok());
+        let slugify_method = result.unwrap();
+        assert_eq!(slugify_method.slugify("Müller, Hans-Jörg"), "muller_hans_joerg");
+        assert_eq!(slugify_method.slugify(" Straße (Émile)! "), "strasse_emile");
+        assert_eq!(slugify_method.slugify("Достоевский"), "dostoevskii");
+    }
+}
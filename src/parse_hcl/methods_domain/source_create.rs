@@ -0,0 +1,115 @@
+use hcl::Block;
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::methods_domain::when::{When, WrapperWhen};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+use crate::parse_xlsx::domain::intermediate_sheet::SheetSource;
+
+pub struct WrapperSourceCreate(pub(crate) Block);
+
+#[derive(Clone, Debug)]
+pub enum SourcePart {
+    File,
+    Sheet,
+    FileAndSheet,
+}
+
+#[derive(Clone, Debug)]
+pub struct SourceCreate {
+    pub output: String,
+    pub part: SourcePart,
+    pub when: Option<When>,
+}
+
+impl SourceCreate {
+    fn new(transient_source_create: TransientSourceCreate) -> SourceCreate {
+        SourceCreate{
+            output: transient_source_create.output,
+            part: transient_source_create.part.unwrap_or(SourcePart::FileAndSheet),
+            when: transient_source_create.when,
+        }
+    }
+    pub(crate) fn source(&self, source: &SheetSource) -> String {
+        match self.part {
+            SourcePart::File => { source.rel_path.to_owned() }
+            SourcePart::Sheet => { source.sheet_name.to_owned() }
+            SourcePart::FileAndSheet => { format!("{}: {}", source.rel_path, source.sheet_name) }
+        }
+    }
+}
+
+struct TransientSourceCreate {
+    output: String,
+    part: Option<SourcePart>,
+    when: Option<When>,
+}
+impl TransientSourceCreate {
+    fn new(output: String) -> Self {
+        Self{ output, part: None, when: None }
+    }
+    fn add_part(&mut self, part: String) -> Result<(), HCLDataError> {
+        if self.part.is_some() {
+            return Err(HCLDataError::InputError(format!("source-create-method: multiple part in method with output '{}'", self.output)));
+        }
+        self.part = match part.as_str() {
+            "file" => { Some(SourcePart::File) }
+            "sheet" => { Some(SourcePart::Sheet) }
+            "file_and_sheet" => { Some(SourcePart::FileAndSheet) }
+            _ => {
+                return Err(HCLDataError::InputError(format!("source-create-method: part should be 'file', 'sheet' or 'file_and_sheet', but found: '{}'", part)));
+            }
+        };
+        Ok(())
+    }
+    fn add_when(&mut self, when: When) -> Result<(), HCLDataError> {
+        if self.when.is_some() {
+            return Err(HCLDataError::InputError(format!("source-create-method: multiple when-blocks in method with output '{}'", self.output)));
+        }
+        self.when = Some(when);
+        Ok(())
+    }
+}
+impl WrapperSourceCreate {
+    pub(crate) fn to_source_create_method(&self, output: String) -> Result<SourceCreate, HCLDataError> {
+        let mut transient = TransientSourceCreate::new(output);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "part" => {
+                    transient.add_part(attribute.expr.to_string_2()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("source-create-method: found this unknown attribute '{:?}' in method '{:?}'.", attribute, self.0.labels)));
+                }
+            }
+        }
+        for block in self.0.blocks() {
+            match block.identifier.as_str() {
+                "when" => {
+                    transient.add_when(WrapperWhen(block.to_owned()).to_when()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("source-create-method: found this unknown block '{:?}' in method '{:?}'.", block.identifier, self.0.labels)));
+                }
+            }
+        }
+        Ok(SourceCreate::new(transient))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::source_create::WrapperSourceCreate;
+    use crate::parse_xlsx::domain::intermediate_sheet::SheetSource;
+
+    #[test]
+    fn test_source_create() {
+        let block = block!(create "source" "hasSource" {
+            part = "sheet"
+        });
+        let result = WrapperSourceCreate(block.to_owned()).to_source_create_method("hasSource".to_string());
+        assert!(result.is_ok());
+        let source = SheetSource { rel_path: "letters.xlsx".to_string(), sheet_name: "1850-1860".to_string(), first_row: 2, kept_rows: None };
+        assert_eq!(result.unwrap().source(&source), "1850-1860");
+    }
+}
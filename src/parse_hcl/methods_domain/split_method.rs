@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use hcl::{Block, Expression};
+use regex::Regex;
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperSplitMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureSplitMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    delimiter: Option<Delimiter>,
+    aligned: Option<HashMap<String, String>>,
+}
+
+impl TransientStructureSplitMethod {
+    fn new(output: String) -> TransientStructureSplitMethod {
+        TransientStructureSplitMethod {
+            output,
+            input: None,
+            delimiter: None,
+            aligned: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_delimiter(&mut self, delimiter: Delimiter) -> Result<(), HCLDataError> {
+        if self.delimiter.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple delimiter- or regex-attributes in method '{:?}', only one is allowed.", self.output)));
+        }
+        self.delimiter = Option::from(delimiter);
+        Ok(())
+    }
+    fn add_aligned(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.aligned.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple aligned-attributes  in method '{:?}'.", self.output)));
+        }
+        self.aligned = Option::from(expression.to_string_map()?);
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("split-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        if self.delimiter.is_none() {
+            return Err(HCLDataError::ParsingError(format!("split-method '{:?}' doesn't have a delimiter- or regex-attribute provided", self)));
+        }
+        Ok(())
+    }
+}
+
+impl WrapperSplitMethod {
+    pub(crate) fn to_split_method(&self) -> Result<SplitMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureSplitMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "delimiter" => {
+                    let delimiter = attribute.expr.to_string_2()?;
+                    if delimiter.is_empty() {
+                        return Err(HCLDataError::ParsingError(format!("split-method '{}': delimiter is empty.", transient_structure.output)));
+                    }
+                    transient_structure.add_delimiter(Delimiter::Text(delimiter))?;
+                }
+                "regex" => {
+                    let regex = Regex::new(attribute.expr.to_string_2()?.as_str())?;
+                    transient_structure.add_delimiter(Delimiter::Regex(regex))?;
+                }
+                "aligned" => {
+                    transient_structure.add_aligned(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        Ok(SplitMethod::new(transient_structure))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Delimiter {
+    Text(String),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone)]
+pub struct SplitMethod {
+    pub output: String,
+    pub input: HeaderValue,
+    pub delimiter: Delimiter,
+    // supplement-columns (comment, permissions, encoding) split in the same way; header to output
+    pub aligned: HashMap<String, String>,
+}
+
+impl SplitMethod {
+    fn new(transient_structure: TransientStructureSplitMethod) -> SplitMethod {
+        SplitMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            delimiter: transient_structure.delimiter.unwrap(),
+            aligned: transient_structure.aligned.unwrap_or_default(),
+        }
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("split-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        for (header, output) in self.aligned.iter() {
+            if header == output || self.input.is_equal(output) || output == &self.output {
+                return Err(HCLDataError::ParsingError(format!("split-method '{}': aligned output '{}' is the same as an input or output.", self.output, output)));
+            }
+        }
+        Ok(())
+    }
+    pub(crate) fn outputs(&self) -> Vec<&String> {
+        let mut outputs = vec![&self.output];
+        outputs.extend(self.aligned.values());
+        outputs
+    }
+    pub(crate) fn split(&self, value: &str) -> Vec<String> {
+        let parts: Vec<&str> = match &self.delimiter {
+            Delimiter::Text(delimiter) => { value.split(delimiter.as_str()).collect() }
+            Delimiter::Regex(regex) => { regex.split(value).collect() }
+        };
+        parts.iter().map(|part| part.trim().to_string()).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::split_method::WrapperSplitMethod;
+
+    #[test]
+    fn test_split_method() {
+        let block = block!(split "hasKeyword" {
+            input = "keywords"
+            regex = "\\s*[;/]\\s*"
+            aligned = { "keywords_comment" = "hasKeyword_comment" }
+        });
+        let result = WrapperSplitMethod(block.to_owned()).to_split_method();
+        assert!(result.is_ok());
+        let split_method = result.unwrap();
+        assert_eq!(split_method.split("a; b / c"), vec!["a", "b", "c"]);
+        assert_eq!(split_method.outputs().len(), 2);
+    }
+    #[test]
+    fn test_split_method_delimiter_and_regex() {
+        let block = block!(split "hasKeyword" {
+            input = "keywords"
+            delimiter = "; "
+            regex = "\\s*;\\s*"
+        });
+        let result = WrapperSplitMethod(block.to_owned()).to_split_method();
+        assert!(result.is_err());
+    }
+}
@@ -1,4 +1,3 @@
-use std::num::ParseIntError;
 use crate::parse_hcl::errors::HCLDataError;
 
 #[derive(Clone, Debug)]
@@ -11,11 +10,20 @@ impl Step {
     fn new(step_method: StepMethod, step_rate: usize) -> Self {
         Step{ step_rate, step_method }
     }
+    pub(crate) fn next(&self, curr: usize) -> Option<usize> {
+        // None if the next number is negative or too large
+        match self.step_method {
+            StepMethod::Plus => { curr.checked_add(self.step_rate) }
+            StepMethod::Minus => { curr.checked_sub(self.step_rate) }
+            StepMethod::Multiplication => { curr.checked_mul(self.step_rate) }
+        }
+    }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum StepMethod {
     Plus,
+    Minus,
     Multiplication
 }
 impl StepMethod {
@@ -24,11 +32,14 @@ impl StepMethod {
             "+" => {
                 Ok(StepMethod::Plus)
             }
+            "-" => {
+                Ok(StepMethod::Minus)
+            }
             "*" => {
                  Ok(StepMethod::Multiplication)
             }
             _ => {
-                Err(HCLDataError::ParsingError(format!("Found unknown step-method '{}'. Add first.", step_method)))
+                Err(HCLDataError::ParsingError(format!("Found unknown step-method '{}', only '+', '-' or '*' allowed.", step_method)))
             }
         }
     }
@@ -38,15 +49,15 @@ pub struct WrapperStep(pub(crate) String);
 
 impl WrapperStep {
     pub(crate) fn to_step(&self) -> Result<Step, HCLDataError> {
-        // first element: StepMethod
-        // after first element: StepRate
-
-        let (step_method, step_rate) = self.0.split_at(1);
-        let step_method = StepMethod::step_method(step_method)?;
-        let step_rate = match step_rate.to_owned().parse::<usize>() {
+        // StepMethod until the first digit, then StepRate, e.g. "+1", "- 10" or "*2"
+        let step = self.0.trim();
+        let pos = step.find(|character: char| character.is_ascii_digit()).unwrap_or(step.len());
+        let (step_method, step_rate) = step.split_at(pos);
+        let step_method = StepMethod::step_method(step_method.trim())?;
+        let step_rate = match step_rate.parse::<usize>() {
             Ok(step_rate) => {step_rate}
             Err(_) => {
-                return Err(HCLDataError::ParseInt(format!("Couldn't parse step-rate '{}' to integer.", step_rate)))
+                return Err(HCLDataError::ParseInt(format!("Couldn't parse step-rate '{}' of step '{}' to integer.", step_rate, self.0)))
             }
         };
 
@@ -54,3 +65,21 @@ impl WrapperStep {
     }
 }
 
+#[cfg(test)]
+mod test {
+    use crate::parse_hcl::methods_domain::step::{StepMethod, WrapperStep};
+
+    #[test]
+    fn test_to_step() {
+        let step = WrapperStep("+10".to_string()).to_step().unwrap();
+        assert_eq!(step.step_method, StepMethod::Plus);
+        assert_eq!(step.step_rate, 10);
+        let step = WrapperStep(" - 250".to_string()).to_step().unwrap();
+        assert_eq!(step.step_method, StepMethod::Minus);
+        assert_eq!(step.next(300), Some(50));
+        assert_eq!(step.next(50), None);
+        assert!(WrapperStep("10".to_string()).to_step().is_err());
+        assert!(WrapperStep("++1".to_string()).to_step().is_err());
+        assert!(WrapperStep("−1".to_string()).to_step().is_err());
+    }
+}
@@ -2,6 +2,7 @@ use hcl::{Block, Expression};
 use crate::expression_trait::ExpressionTransform;
 use crate::parse_hcl::errors::HCLDataError;
 use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::when::{When, WrapperWhen};
 use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
 
 #[derive(Debug, Clone)]
@@ -9,14 +10,16 @@ pub struct AlterMethod{
     pub(crate) prefix: Option<String>,
     pub(crate) suffix: Option<String>,
     pub(crate) input: HeaderValue,
-    pub(crate) output: String
+    pub(crate) output: String,
+    pub(crate) when: Option<When>,
 }
 
 struct TransientStructureAlterMethod {
     prefix: Option<String>,
     suffix: Option<String>,
     input: Option<HeaderValue>,
-    output: String
+    output: String,
+    when: Option<When>,
 }
 
 impl TransientStructureAlterMethod {
@@ -26,6 +29,7 @@ impl TransientStructureAlterMethod {
             suffix: None,
             input: None,
             output,
+            when: None,
         }
     }
     pub(crate) fn add_input(&mut self, input: HeaderValue) -> Result<(), HCLDataError> {
@@ -49,6 +53,13 @@ impl TransientStructureAlterMethod {
         self.prefix = Some(prefix);
         Ok(())
     }
+    pub(crate) fn add_when(&mut self, when: When) -> Result<(), HCLDataError> {
+        if self.when.is_some() {
+            return Err(HCLDataError::ParsingError(format!("Alter-method: found multiple when-blocks in alter-method with output: {}", self.output)));
+        }
+        self.when = Some(when);
+        Ok(())
+    }
     pub(crate) fn is_consistent(&self) -> Result<(), HCLDataError> {
         if self.input.is_none() {
             return Err(HCLDataError::ParsingError(format!("Alter-method: not found 'input' value in alter-method with output: {}", self.output)));
@@ -67,7 +78,8 @@ impl AlterMethod {
             prefix:transient_structure_alter_method.prefix,
             suffix: transient_structure_alter_method.suffix,
             input: transient_structure_alter_method.input.unwrap(),
-            output: transient_structure_alter_method.output
+            output: transient_structure_alter_method.output,
+            when: transient_structure_alter_method.when,
         }
     }
 }
@@ -77,7 +89,6 @@ pub struct WrapperAlterMethod(pub(crate) Block);
 impl WrapperAlterMethod {
     pub(crate) fn to_alter_method(&self) -> Result<AlterMethod, HCLDataError> {
         let mut transient_structure = TransientStructureAlterMethod::new(self.0.get_output()?);
-        self.0.no_blocks()?;
         for attribute in self.0.attributes() {
             match attribute.key.as_str() {
                 "input" => {
@@ -96,6 +107,16 @@ impl WrapperAlterMethod {
             }
 
         }
+        for block in self.0.blocks() {
+            match block.identifier.as_str() {
+                "when" => {
+                    transient_structure.add_when(WrapperWhen(block.to_owned()).to_when()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown block '{:?}' in method '{:?}'.", block, transient_structure.output)));
+                }
+            }
+        }
         transient_structure.is_consistent()?;
         let combine_method = AlterMethod::new(transient_structure);
         Ok(combine_method)
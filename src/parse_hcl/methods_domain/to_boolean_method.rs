@@ -0,0 +1,149 @@
+use hcl::{Block, Expression};
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperToBooleanMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureToBooleanMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    truthy: Option<Vec<String>>,
+    falsy: Option<Vec<String>>,
+}
+
+impl TransientStructureToBooleanMethod {
+    fn new(output: String) -> TransientStructureToBooleanMethod {
+        TransientStructureToBooleanMethod {
+            output,
+            input: None,
+            truthy: None,
+            falsy: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_truthy(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.truthy.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple truthy-attributes  in method '{:?}'.", self.output)));
+        }
+        self.truthy = Option::from(to_words(expression)?);
+        Ok(())
+    }
+    fn add_falsy(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.falsy.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple falsy-attributes  in method '{:?}'.", self.output)));
+        }
+        self.falsy = Option::from(to_words(expression)?);
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("to_boolean-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        if let (Some(truthy), Some(falsy)) = (&self.truthy, &self.falsy) {
+            if let Some(word) = truthy.iter().find(|word| falsy.contains(word)) {
+                return Err(HCLDataError::ParsingError(format!("to_boolean-method '{}': '{}' is truthy and falsy.", self.output, word)));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn to_words(expression: Expression) -> Result<Vec<String>, HCLDataError> {
+    // words are compared trimmed and in lowercase
+    let mut words = vec![];
+    for expr in expression.to_vec()? {
+        words.push(expr.to_string_2()?.trim().to_lowercase());
+    }
+    Ok(words)
+}
+
+impl WrapperToBooleanMethod {
+    pub(crate) fn to_boolean_method(&self) -> Result<ToBooleanMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureToBooleanMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "truthy" => {
+                    transient_structure.add_truthy(attribute.expr.to_owned())?;
+                }
+                "falsy" => {
+                    transient_structure.add_falsy(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        Ok(ToBooleanMethod::new(transient_structure))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ToBooleanMethod {
+    pub output: String,
+    pub input: HeaderValue,
+    pub truthy: Vec<String>,
+    pub falsy: Vec<String>,
+}
+
+impl ToBooleanMethod {
+    fn new(transient_structure: TransientStructureToBooleanMethod) -> ToBooleanMethod {
+        ToBooleanMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            truthy: transient_structure.truthy.unwrap_or(vec!["true".to_string(), "1".to_string(), "yes".to_string()]),
+            falsy: transient_structure.falsy.unwrap_or(vec!["false".to_string(), "0".to_string(), "no".to_string()]),
+        }
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("to_boolean-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        Ok(())
+    }
+    pub(crate) fn to_boolean(&self, value: &str) -> Option<String> {
+        // an empty value is only converted if "" is one of the words
+        let word = value.trim().to_lowercase();
+        if self.truthy.contains(&word) {
+            Some("true".to_string())
+        } else if self.falsy.contains(&word) {
+            Some("false".to_string())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::to_boolean_method::WrapperToBooleanMethod;
+
+    #[test]
+    fn test_to_boolean_method() {
+        let block = block!(to_boolean "isPublished" {
+            input = "publiziert"
+            truthy = ["ja", "x"]
+            falsy = ["nein", ""]
+        });
+        let result = WrapperToBooleanMethod(block.to_owned()).to_boolean_method();
+        assert!(result.is_ok());
+        let to_boolean_method = result.unwrap();
+        assert_eq!(to_boolean_method.to_boolean(" X").unwrap(), "true");
+        assert_eq!(to_boolean_method.to_boolean("").unwrap(), "false");
+        assert!(to_boolean_method.to_boolean("vielleicht").is_none());
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use hcl::{Block, Expression};
+use regex::Regex;
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperToColorMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureToColorMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    names: Option<HashMap<String, String>>,
+}
+
+impl TransientStructureToColorMethod {
+    fn new(output: String) -> TransientStructureToColorMethod {
+        TransientStructureToColorMethod {
+            output,
+            input: None,
+            names: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_names(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.names.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple names-attributes  in method '{:?}'.", self.output)));
+        }
+        self.names = Option::from(expression.to_string_map()?);
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("to_color-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        Ok(())
+    }
+}
+
+impl WrapperToColorMethod {
+    pub(crate) fn to_color_method(&self) -> Result<ToColorMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureToColorMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "names" => {
+                    transient_structure.add_names(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        ToColorMethod::new(transient_structure)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ToColorMethod {
+    pub output: String,
+    pub input: HeaderValue,
+    // additional color-names (e.g. in German) to a color; lowercase name to '#rrggbb'
+    pub names: HashMap<String, String>,
+}
+
+impl ToColorMethod {
+    fn new(transient_structure: TransientStructureToColorMethod) -> Result<ToColorMethod, HCLDataError> {
+        let mut names = HashMap::new();
+        for (name, color) in transient_structure.names.unwrap_or_default() {
+            let hex = match to_hex(&color) {
+                None => {
+                    return Err(HCLDataError::ParsingError(format!("to_color-method '{}': '{}' of name '{}' is not a color.", transient_structure.output, color, name)));
+                }
+                Some(hex) => { hex }
+            };
+            names.insert(name.trim().to_lowercase(), hex);
+        }
+        Ok(ToColorMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            names,
+        })
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("to_color-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        Ok(())
+    }
+    pub(crate) fn to_color(&self, value: &str) -> Option<String> {
+        match self.names.get(&value.trim().to_lowercase()) {
+            None => { to_hex(value) }
+            Some(hex) => { Some(hex.to_owned()) }
+        }
+    }
+}
+
+fn to_hex(value: &str) -> Option<String> {
+    // css-name, 'rgb(255, 0, 0)', '#f00' or '#ff0000' to '#ff0000'
+    let value = value.trim().to_lowercase();
+    if let Some((_, hex)) = CSS_COLORS.iter().find(|(name, _)| *name == value) {
+        return Some(hex.to_string());
+    }
+    let re_hex = Regex::new(r"^#?([0-9a-f]{3}|[0-9a-f]{6})$").unwrap();
+    if let Some(caps) = re_hex.captures(&value) {
+        let digits = caps.get(1).unwrap().as_str();
+        if digits.len() == 3 {
+            return Some(format!("#{}", digits.chars().map(|digit| format!("{}{}", digit, digit)).collect::<String>()));
+        }
+        return Some(format!("#{}", digits));
+    }
+    let re_rgb = Regex::new(r"^rgb\(\s*(\d{1,3})\s*,\s*(\d{1,3})\s*,\s*(\d{1,3})\s*\)$").unwrap();
+    if let Some(caps) = re_rgb.captures(&value) {
+        let mut hex = "#".to_string();
+        for nr in 1..4 {
+            let channel = caps.get(nr).unwrap().as_str().parse::<u16>().unwrap();
+            if channel > 255 {
+                return None;
+            }
+            hex += format!("{:02x}", channel).as_str();
+        }
+        return Some(hex);
+    }
+    None
+}
+
+const CSS_COLORS: [(&str, &str); 148] = [
+    ("aliceblue", "#f0f8ff"), ("antiquewhite", "#faebd7"), ("aqua", "#00ffff"), ("aquamarine", "#7fffd4"),
+    ("azure", "#f0ffff"), ("beige", "#f5f5dc"), ("bisque", "#ffe4c4"), ("black", "#000000"),
+    ("blanchedalmond", "#ffebcd"), ("blue", "#0000ff"), ("blueviolet", "#8a2be2"), ("brown", "#a52a2a"),
+    ("burlywood", "#deb887"), ("cadetblue", "#5f9ea0"), ("chartreuse", "#7fff00"), ("chocolate", "#d2691e"),
+    ("coral", "#ff7f50"), ("cornflowerblue", "#6495ed"), ("cornsilk", "#fff8dc"), ("crimson", "#dc143c"),
+    ("cyan", "#00ffff"), ("darkblue", "#00008b"), ("darkcyan", "#008b8b"), ("darkgoldenrod", "#b8860b"),
+    ("darkgray", "#a9a9a9"), ("darkgreen", "#006400"), ("darkgrey", "#a9a9a9"), ("darkkhaki", "#bdb76b"),
+    ("darkmagenta", "#8b008b"), ("darkolivegreen", "#556b2f"), ("darkorange", "#ff8c00"), ("darkorchid", "#9932cc"),
+    ("darkred", "#8b0000"), ("darksalmon", "#e9967a"), ("darkseagreen", "#8fbc8f"), ("darkslateblue", "#483d8b"),
+    ("darkslategray", "#2f4f4f"), ("darkslategrey", "#2f4f4f"), ("darkturquoise", "#00ced1"), ("darkviolet", "#9400d3"),
+    ("deeppink", "#ff1493"), ("deepskyblue", "#00bfff"), ("dimgray", "#696969"), ("dimgrey", "#696969"),
+    ("dodgerblue", "#1e90ff"), ("firebrick", "#b22222"), ("floralwhite", "#fffaf0"), ("forestgreen", "#228b22"),
+    ("fuchsia", "#ff00ff"), ("gainsboro", "#dcdcdc"), ("ghostwhite", "#f8f8ff"), ("gold", "#ffd700"),
+    ("goldenrod", "#daa520"), ("gray", "#808080"), ("green", "#008000"), ("greenyellow", "#adff2f"),
+    ("grey", "#808080"), ("honeydew", "#f0fff0"), ("hotpink", "#ff69b4"), ("indianred", "#cd5c5c"),
+    ("indigo", "#4b0082"), ("ivory", "#fffff0"), ("khaki", "#f0e68c"), ("lavender", "#e6e6fa"),
+    ("lavenderblush", "#fff0f5"), ("lawngreen", "#7cfc00"), ("lemonchiffon", "#fffacd"), ("lightblue", "#add8e6"),
+    ("lightcoral", "#f08080"), ("lightcyan", "#e0ffff"), ("lightgoldenrodyellow", "#fafad2"), ("lightgray", "#d3d3d3"),
+    ("lightgreen", "#90ee90"), ("lightgrey", "#d3d3d3"), ("lightpink", "#ffb6c1"), ("lightsalmon", "#ffa07a"),
+    ("lightseagreen", "#20b2aa"), ("lightskyblue", "#87cefa"), ("lightslategray", "#778899"), ("lightslategrey", "#778899"),
+    ("lightsteelblue", "#b0c4de"), ("lightyellow", "#ffffe0"), ("lime", "#00ff00"), ("limegreen", "#32cd32"),
+    ("linen", "#faf0e6"), ("magenta", "#ff00ff"), ("maroon", "#800000"), ("mediumaquamarine", "#66cdaa"),
+    ("mediumblue", "#0000cd"), ("mediumorchid", "#ba55d3"), ("mediumpurple", "#9370db"), ("mediumseagreen", "#3cb371"),
+    ("mediumslateblue", "#7b68ee"), ("mediumspringgreen", "#00fa9a"), ("mediumturquoise", "#48d1cc"), ("mediumvioletred", "#c71585"),
+    ("midnightblue", "#191970"), ("mintcream", "#f5fffa"), ("mistyrose", "#ffe4e1"), ("moccasin", "#ffe4b5"),
+    ("navajowhite", "#ffdead"), ("navy", "#000080"), ("oldlace", "#fdf5e6"), ("olive", "#808000"),
+    ("olivedrab", "#6b8e23"), ("orange", "#ffa500"), ("orangered", "#ff4500"), ("orchid", "#da70d6"),
+    ("palegoldenrod", "#eee8aa"), ("palegreen", "#98fb98"), ("paleturquoise", "#afeeee"), ("palevioletred", "#db7093"),
+    ("papayawhip", "#ffefd5"), ("peachpuff", "#ffdab9"), ("peru", "#cd853f"), ("pink", "#ffc0cb"),
+    ("plum", "#dda0dd"), ("powderblue", "#b0e0e6"), ("purple", "#800080"), ("rebeccapurple", "#663399"),
+    ("red", "#ff0000"), ("rosybrown", "#bc8f8f"), ("royalblue", "#4169e1"), ("saddlebrown", "#8b4513"),
+    ("salmon", "#fa8072"), ("sandybrown", "#f4a460"), ("seagreen", "#2e8b57"), ("seashell", "#fff5ee"),
+    ("sienna", "#a0522d"), ("silver", "#c0c0c0"), ("skyblue", "#87ceeb"), ("slateblue", "#6a5acd"),
+    ("slategray", "#708090"), ("slategrey", "#708090"), ("snow", "#fffafa"), ("springgreen", "#00ff7f"),
+    ("steelblue", "#4682b4"), ("tan", "#d2b48c"), ("teal", "#008080"), ("thistle", "#d8bfd8"),
+    ("tomato", "#ff6347"), ("turquoise", "#40e0d0"), ("violet", "#ee82ee"), ("wheat", "#f5deb3"),
+    ("white", "#ffffff"), ("whitesmoke", "#f5f5f5"), ("yellow", "#ffff00"), ("yellowgreen", "#9acd32"),
+];
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::to_color_method::WrapperToColorMethod;
+
+    #[test]
+    fn test_to_color_method() {
+        let block = block!(to_color "hasColor" {
+            input = "Farbe"
+            names = { "rot" = "red" }
+        });
+        let result = WrapperToColorMethod(block.to_owned()).to_color_method();
+        assert!(result.is_ok());
+        let to_color_method = result.unwrap();
+        assert_eq!(to_color_method.to_color("Rot").unwrap(), "#ff0000");
+        assert_eq!(to_color_method.to_color("#0F0").unwrap(), "#00ff00");
+        assert_eq!(to_color_method.to_color("rgb(0, 0, 255)").unwrap(), "#0000ff");
+        assert!(to_color_method.to_color("rgb(0, 0, 256)").is_none());
+    }
+}
@@ -4,6 +4,7 @@ use crate::parse_hcl::errors::HCLDataError;
 use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
 use crate::parse_hcl::methods_domain::date_pattern::{DatePattern, WrapperDatePattern};
 use crate::parse_hcl::methods_domain::date_type::DateType;
+use crate::parse_hcl::methods_domain::when::{When, WrapperWhen};
 use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
 
 pub struct WrapperToDateMethod(pub(crate) hcl::Block);
@@ -14,6 +15,7 @@ struct TransientStructureToDateMethod{
     input: Option<HeaderValue>,
     date_type: Option<String>,
     date_pattern: Vec<DatePattern>,
+    when: Option<When>,
 }
 
 impl TransientStructureToDateMethod {
@@ -23,6 +25,7 @@ impl TransientStructureToDateMethod {
             input: None,
             date_type: None,
             date_pattern: vec![],
+            when: None,
         }
     }
     fn add_input(&mut self, input: Expression) -> Result<(), HCLDataError> {
@@ -43,6 +46,13 @@ impl TransientStructureToDateMethod {
     fn add_date_pattern(&mut self, date_pattern: DatePattern) {
         self.date_pattern.push(date_pattern);
     }
+    fn add_when(&mut self, when: When) -> Result<(), HCLDataError> {
+        if self.when.is_some() {
+            return Err(HCLDataError::ParsingError(format!("error in to_date-method '{:?}'. 'when'-block multiple times provided", self)));
+        }
+        self.when = Some(when);
+        Ok(())
+    }
     fn is_consistent(&self) -> Result<(), HCLDataError> {
         if self.input.is_none() {
             return Err(HCLDataError::ParsingError(format!("error in to_date-method '{:?}'. 'input'-attribute not provided", self)));
@@ -83,6 +93,9 @@ impl WrapperToDateMethod {
                     let date_pattern = WrapperDatePattern(block.to_owned()).to_pattern()?;
                     transient_structure.add_date_pattern(date_pattern);
                 }
+                "when" => {
+                    transient_structure.add_when(WrapperWhen(block.to_owned()).to_when()?)?;
+                }
                 _ => {
                     return Err(HCLDataError::ParsingError(format!("found unknown block-identifier in 'to_date'-method: {:?}", transient_structure.output)));
                 }
@@ -98,7 +111,8 @@ pub struct ToDateMethod{
     pub output: String,
     pub input: HeaderValue,
     pub date_type: DateType,
-    pub date_patterns: Vec<DatePattern>
+    pub date_patterns: Vec<DatePattern>,
+    pub when: Option<When>,
 }
 
 
@@ -110,6 +124,7 @@ impl ToDateMethod {
             input: transient_structure.input.unwrap(),
             date_type,
             date_patterns: transient_structure.date_pattern,
+            when: transient_structure.when,
         })
     }
     pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
@@ -0,0 +1,264 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use hcl::{Block, Expression};
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperToGeonameMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureToGeonameMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    country: Option<HeaderValue>,
+    path: Option<String>,
+    feature_classes: Option<Vec<String>>,
+    prefer_population: Option<bool>,
+    fail_on_unresolved: Option<bool>,
+}
+
+impl TransientStructureToGeonameMethod {
+    fn new(output: String) -> TransientStructureToGeonameMethod {
+        TransientStructureToGeonameMethod {
+            output,
+            input: None,
+            country: None,
+            path: None,
+            feature_classes: None,
+            prefer_population: None,
+            fail_on_unresolved: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_country(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.country.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple country-attributes  in method '{:?}'.", self.output)));
+        }
+        self.country = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_path(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.path.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple path-attributes  in method '{:?}'.", self.output)));
+        }
+        self.path = Option::from(expression.to_string_2()?);
+        Ok(())
+    }
+    fn add_feature_classes(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.feature_classes.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple feature_classes-attributes  in method '{:?}'.", self.output)));
+        }
+        let mut feature_classes = vec![];
+        for expr in expression.to_vec()? {
+            feature_classes.push(expr.to_string_2()?.trim().to_uppercase());
+        }
+        self.feature_classes = Option::from(feature_classes);
+        Ok(())
+    }
+    fn add_prefer_population(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.prefer_population.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple prefer_population-attributes  in method '{:?}'.", self.output)));
+        }
+        self.prefer_population = Option::from(expression.to_bool()?);
+        Ok(())
+    }
+    fn add_fail_on_unresolved(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.fail_on_unresolved.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple fail_on_unresolved-attributes  in method '{:?}'.", self.output)));
+        }
+        self.fail_on_unresolved = Option::from(expression.to_bool()?);
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("to_geoname-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        match &self.path {
+            None => {
+                return Err(HCLDataError::ParsingError(format!("to_geoname-method '{:?}' doesn't have a path-attribute provided", self)));
+            }
+            Some(path) => {
+                if !path.ends_with(".txt") {
+                    return Err(HCLDataError::ParsingError(format!("to_geoname-method '{}': path must be a GeoNames-dump (.txt), but found: '{}'", self.output, path)));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl WrapperToGeonameMethod {
+    pub(crate) fn to_geoname_method(&self) -> Result<ToGeonameMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureToGeonameMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "country" => {
+                    transient_structure.add_country(attribute.expr.to_owned())?;
+                }
+                "path" => {
+                    transient_structure.add_path(attribute.expr.to_owned())?;
+                }
+                "feature_classes" => {
+                    transient_structure.add_feature_classes(attribute.expr.to_owned())?;
+                }
+                "prefer_population" => {
+                    transient_structure.add_prefer_population(attribute.expr.to_owned())?;
+                }
+                "fail_on_unresolved" => {
+                    transient_structure.add_fail_on_unresolved(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        Ok(ToGeonameMethod::new(transient_structure))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ToGeonameMethod {
+    pub output: String,
+    pub input: HeaderValue,
+    // optional column with ISO-3166 country codes, e.g. "CH"
+    pub country: Option<HeaderValue>,
+    // path relative to the resources folder; replaced by the full path before the transformations are performed
+    pub path: PathBuf,
+    pub feature_classes: Vec<String>,
+    pub prefer_population: bool,
+    pub fail_on_unresolved: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeonameCandidate {
+    pub id: String,
+    pub name: String,
+    pub country: String,
+    pub feature_code: String,
+    pub population: u64,
+}
+
+impl ToGeonameMethod {
+    fn new(transient_structure: TransientStructureToGeonameMethod) -> ToGeonameMethod {
+        ToGeonameMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            country: transient_structure.country,
+            path: PathBuf::from(transient_structure.path.unwrap()),
+            feature_classes: transient_structure.feature_classes.unwrap_or(vec!["P".to_string(), "A".to_string()]),
+            prefer_population: transient_structure.prefer_population.unwrap_or(false),
+            fail_on_unresolved: transient_structure.fail_on_unresolved.unwrap_or(false),
+        }
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("to_geoname-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        Ok(())
+    }
+    pub(crate) fn candidates(&self, names: &HashSet<String>) -> Result<HashMap<String, Vec<GeonameCandidate>>, HCLDataError> {
+        // the dump is read line by line and only entries of the searched names (lowercase) are kept
+        let file = match File::open(&self.path) {
+            Ok(file) => { file }
+            Err(error) => {
+                return Err(HCLDataError::InputError(format!("to_geoname '{}': cannot open '{:?}': {}", self.output, self.path, error)));
+            }
+        };
+        let mut name_to_candidates: HashMap<String, Vec<GeonameCandidate>> = HashMap::new();
+        for line in BufReader::new(file).lines() {
+            let line = match line {
+                Ok(line) => { line }
+                Err(error) => {
+                    return Err(HCLDataError::InputError(format!("to_geoname '{}': cannot read '{:?}': {}", self.output, self.path, error)));
+                }
+            };
+            for (name, candidate) in self.read_line(&line, names) {
+                let candidates = name_to_candidates.entry(name).or_default();
+                if !candidates.contains(&candidate) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+        Ok(name_to_candidates)
+    }
+    fn read_line(&self, line: &str, names: &HashSet<String>) -> Vec<(String, GeonameCandidate)> {
+        // columns of a GeoNames-dump: geonameid, name, asciiname, alternatenames, latitude, longitude, feature class, feature code, country code, ..., population (14)
+        let columns: Vec<&str> = line.split('\t').collect();
+        if columns.len() < 15 || !self.feature_classes.contains(&columns[6].to_string()) {
+            return vec![];
+        }
+        let mut found = vec![];
+        let mut line_names: Vec<String> = vec![columns[1].to_lowercase(), columns[2].to_lowercase()];
+        line_names.extend(columns[3].split(',').map(|name| name.trim().to_lowercase()));
+        for line_name in line_names {
+            if names.contains(&line_name) && !found.contains(&line_name) {
+                found.push(line_name);
+            }
+        }
+        found.into_iter().map(|name| (name, GeonameCandidate {
+            id: columns[0].to_string(),
+            name: columns[1].to_string(),
+            country: columns[8].to_string(),
+            feature_code: columns[7].to_string(),
+            population: columns[14].parse::<u64>().unwrap_or(0),
+        })).collect()
+    }
+    pub(crate) fn resolve<'a>(&self, candidates: &'a [GeonameCandidate], country: Option<&str>) -> Result<&'a GeonameCandidate, Vec<&'a GeonameCandidate>> {
+        // returns the resolved candidate or the remaining candidates (none: unresolved, multiple: ambiguous)
+        let mut remaining: Vec<&GeonameCandidate> = candidates.iter()
+            .filter(|candidate| country.is_none_or(|country| country.trim().is_empty() || candidate.country.eq_ignore_ascii_case(country.trim())))
+            .collect();
+        if remaining.len() > 1 && self.prefer_population {
+            remaining.sort_by_key(|candidate| std::cmp::Reverse(candidate.population));
+            if remaining[0].population > remaining[1].population {
+                return Ok(remaining[0]);
+            }
+        }
+        if remaining.len() == 1 {
+            return Ok(remaining[0]);
+        }
+        Err(remaining)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::to_geoname_method::WrapperToGeonameMethod;
+
+    #[test]
+    fn test_to_geoname_method() {
+        let block = block!(to_geoname "hasPlace" {
+            input = "Ort"
+            country = "Land"
+            path = "geonames/CH.txt"
+            prefer_population = true
+        });
+        let result = WrapperToGeonameMethod(block.to_owned()).to_geoname_method();
+        assert!(result.is_ok());
+        let to_geoname_method = result.unwrap();
+        let names = HashSet::from(["basel".to_string(), "bâle".to_string()]);
+        let line = "2661604\tBasel\tBasel\tBale,Bâle,Basilea\t47.55839\t7.57327\tP\tPPLA\tCH\t\tBS\t\t\t\t164488\t\t260\tEurope/Zurich\t2019-09-05";
+        let found = to_geoname_method.read_line(line, &names);
+        assert_eq!(found.len(), 2);
+        let candidates = vec![found[0].1.to_owned()];
+        assert_eq!(to_geoname_method.resolve(&candidates, Some("ch")).unwrap().id, "2661604");
+        assert!(to_geoname_method.resolve(&candidates, Some("DE")).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use std::str::FromStr;
+use hcl::{Block, Expression};
+use regex::Regex;
+use rust_decimal::{Decimal, RoundingStrategy};
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::number_trait::NumberTrait;
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperToNumberMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureToNumberMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    decimal_separator: Option<String>,
+    thousands_separators: Option<Vec<String>>,
+    precision: Option<u32>,
+    rounding: Option<String>,
+}
+
+impl TransientStructureToNumberMethod {
+    fn new(output: String) -> TransientStructureToNumberMethod {
+        TransientStructureToNumberMethod {
+            output,
+            input: None,
+            decimal_separator: None,
+            thousands_separators: None,
+            precision: None,
+            rounding: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_decimal_separator(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.decimal_separator.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple decimal_separator-attributes  in method '{:?}'.", self.output)));
+        }
+        self.decimal_separator = Option::from(expression.to_string_2()?);
+        Ok(())
+    }
+    fn add_thousands_separators(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        // a single separator or a list, e.g. ["'", "’", " "]
+        if self.thousands_separators.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple thousands_separator-attributes  in method '{:?}'.", self.output)));
+        }
+        let separators = match expression {
+            Expression::Array(array) => {
+                let mut separators = vec![];
+                for expr in array.iter() {
+                    separators.push(expr.to_string_2()?);
+                }
+                separators
+            }
+            _ => { vec![expression.to_string_2()?] }
+        };
+        self.thousands_separators = Option::from(separators);
+        Ok(())
+    }
+    fn add_precision(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.precision.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple precision-attributes  in method '{:?}'.", self.output)));
+        }
+        self.precision = Option::from(expression.to_number()?.as_usize()? as u32);
+        Ok(())
+    }
+    fn add_rounding(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.rounding.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple rounding-attributes  in method '{:?}'.", self.output)));
+        }
+        self.rounding = Option::from(expression.to_string_2()?);
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("to_number-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        let decimal_separator = self.decimal_separator.to_owned().unwrap_or(".".to_string());
+        if decimal_separator.is_empty() {
+            return Err(HCLDataError::ParsingError(format!("to_number-method '{:?}': decimal_separator is empty", self)));
+        }
+        if let Some(thousands_separators) = &self.thousands_separators {
+            if thousands_separators.iter().any(|separator| separator.is_empty() || separator == &decimal_separator) {
+                return Err(HCLDataError::ParsingError(format!("to_number-method '{:?}': thousands_separator must not be empty or the same as the decimal_separator", self)));
+            }
+        }
+        if self.rounding.is_some() && self.precision.is_none() {
+            return Err(HCLDataError::ParsingError(format!("to_number-method '{:?}': rounding needs a precision", self)));
+        }
+        Ok(())
+    }
+}
+
+impl WrapperToNumberMethod {
+    pub(crate) fn to_number_method(&self) -> Result<ToNumberMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureToNumberMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "decimal_separator" => {
+                    transient_structure.add_decimal_separator(attribute.expr.to_owned())?;
+                }
+                "thousands_separator" => {
+                    transient_structure.add_thousands_separators(attribute.expr.to_owned())?;
+                }
+                "precision" => {
+                    transient_structure.add_precision(attribute.expr.to_owned())?;
+                }
+                "rounding" => {
+                    transient_structure.add_rounding(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        ToNumberMethod::new(transient_structure)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ToNumberMethod {
+    pub output: String,
+    pub input: HeaderValue,
+    pub decimal_separator: String,
+    pub thousands_separators: Vec<String>,
+    pub precision: Option<u32>,
+    pub rounding: RoundingStrategy,
+}
+
+impl ToNumberMethod {
+    fn new(transient_structure: TransientStructureToNumberMethod) -> Result<ToNumberMethod, HCLDataError> {
+        let rounding = match transient_structure.rounding.as_deref() {
+            None | Some("half_up") => { RoundingStrategy::MidpointAwayFromZero }
+            Some("half_even") => { RoundingStrategy::MidpointNearestEven }
+            Some("down") => { RoundingStrategy::ToZero }
+            Some("up") => { RoundingStrategy::AwayFromZero }
+            Some(other) => {
+                return Err(HCLDataError::ParsingError(format!("to_number-method: unknown value for 'rounding': '{}', only 'half_up', 'half_even', 'down' or 'up' allowed.", other)));
+            }
+        };
+        Ok(ToNumberMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            decimal_separator: transient_structure.decimal_separator.unwrap_or(".".to_string()),
+            thousands_separators: transient_structure.thousands_separators.unwrap_or_default(),
+            precision: transient_structure.precision,
+            rounding,
+        })
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("to_number-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        Ok(())
+    }
+    pub(crate) fn to_number(&self, value: &str) -> Result<String, HCLDataError> {
+        // "1'234,50" -> "1234.5"; the result is written without trailing zeros
+        let mut number = value.trim().to_string();
+        for separator in self.thousands_separators.iter() {
+            number = number.replace(separator.as_str(), "");
+        }
+        if self.decimal_separator != "." {
+            if number.contains('.') {
+                return Err(HCLDataError::InputError(format!("to_number '{}': value '{}' contains '.', but the decimal_separator is '{}'.", self.output, value, self.decimal_separator)));
+            }
+            number = number.replace(self.decimal_separator.as_str(), ".");
+        }
+        let re = Regex::new(r"^[+-]?\d+(\.\d+)?$").unwrap();
+        if !re.is_match(number.as_str()) {
+            return Err(HCLDataError::InputError(format!("to_number '{}': cannot read '{}' as number.", self.output, value)));
+        }
+        let mut decimal = match Decimal::from_str(number.trim_start_matches('+')) {
+            Ok(decimal) => { decimal }
+            Err(error) => {
+                return Err(HCLDataError::InputError(format!("to_number '{}': cannot read '{}' as number: {}", self.output, value, error)));
+            }
+        };
+        if let Some(precision) = self.precision {
+            decimal = decimal.round_dp_with_strategy(precision, self.rounding);
+        }
+        Ok(decimal.normalize().to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::to_number_method::WrapperToNumberMethod;
+
+    #[test]
+    fn test_to_number_method() {
+        let block = block!(to_number "hasAmount" {
+            input = "Betrag"
+            decimal_separator = ","
+            thousands_separator = ["'", "."]
+            precision = 2
+        });
+        let result = WrapperToNumberMethod(block.to_owned()).to_number_method();
+        assert!(result.is_ok());
+        let to_number_method = result.unwrap();
+        assert_eq!(to_number_method.to_number("1'234,50").unwrap(), "1234.5");
+        assert_eq!(to_number_method.to_number("1.234,555").unwrap(), "1234.56");
+        assert!(to_number_method.to_number("12 Fr.").is_err());
+    }
+    #[test]
+    fn test_to_number_method_float() {
+        let block = block!(to_number "hasCount" {
+            input = "count"
+            precision = 0
+        });
+        let to_number_method = WrapperToNumberMethod(block.to_owned()).to_number_method().unwrap();
+        assert_eq!(to_number_method.to_number("12.000000000000002").unwrap(), "12");
+    }
+}
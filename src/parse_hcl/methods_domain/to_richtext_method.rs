@@ -0,0 +1,142 @@
+use hcl::{Block, Expression};
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+use crate::parse_xlsx::domain::richtext::{markdown_to_html, sanitize, Richtext};
+
+pub struct WrapperToRichtextMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureToRichtextMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    format: Option<RichtextFormat>,
+}
+
+impl TransientStructureToRichtextMethod {
+    fn new(output: String) -> TransientStructureToRichtextMethod {
+        TransientStructureToRichtextMethod {
+            output,
+            input: None,
+            format: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_format(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.format.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple format-attributes  in method '{:?}'.", self.output)));
+        }
+        let format = expression.to_string_2()?;
+        self.format = match format.as_str() {
+            "html" => { Some(RichtextFormat::Html) }
+            "markdown" => { Some(RichtextFormat::Markdown) }
+            _ => {
+                return Err(HCLDataError::ParsingError(format!("to_richtext-method '{}': unknown format '{}', only 'html' or 'markdown' allowed.", self.output, format)));
+            }
+        };
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("to_richtext-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        Ok(())
+    }
+}
+
+impl WrapperToRichtextMethod {
+    pub(crate) fn to_richtext_method(&self) -> Result<ToRichtextMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureToRichtextMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "format" => {
+                    transient_structure.add_format(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        Ok(ToRichtextMethod::new(transient_structure))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RichtextFormat {
+    Html,
+    Markdown,
+}
+
+#[derive(Debug, Clone)]
+pub struct ToRichtextMethod {
+    pub output: String,
+    pub input: HeaderValue,
+    pub format: RichtextFormat,
+}
+
+impl ToRichtextMethod {
+    fn new(transient_structure: TransientStructureToRichtextMethod) -> ToRichtextMethod {
+        ToRichtextMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            format: transient_structure.format.unwrap_or(RichtextFormat::Html),
+        }
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("to_richtext-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        Ok(())
+    }
+    pub(crate) fn to_richtext(&self, value: &str) -> Richtext {
+        match self.format {
+            RichtextFormat::Html => { sanitize(value) }
+            RichtextFormat::Markdown => { sanitize(&markdown_to_html(value)) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::to_richtext_method::WrapperToRichtextMethod;
+
+    #[test]
+    fn test_to_richtext_html() {
+        let block = block!(to_richtext "hasText" {
+            input = "text"
+        });
+        let result = WrapperToRichtextMethod(block.to_owned()).to_richtext_method();
+        assert!(result.is_ok());
+        let to_richtext_method = result.unwrap();
+        let richtext = to_richtext_method.to_richtext("<p>A <i>letter</i> from <span>Basel</span> & <a href=\"resource:letter_2\">more</a><script>alert(1)</script>");
+        assert_eq!(richtext.text, "<p>A <em>letter</em> from Basel &amp; <a class=\"salsah-link\" href=\"IRI:letter_2:IRI\">more</a></p>");
+        assert_eq!(richtext.removed, vec!["span", "script"]);
+        assert_eq!(richtext.links, vec!["letter_2"]);
+        // converting the result again doesn't change it
+        assert_eq!(to_richtext_method.to_richtext(&richtext.text).text, richtext.text);
+    }
+    #[test]
+    fn test_to_richtext_markdown() {
+        let block = block!(to_richtext "hasText" {
+            input = "text"
+            format = "markdown"
+        });
+        let to_richtext_method = WrapperToRichtextMethod(block.to_owned()).to_richtext_method().unwrap();
+        assert_eq!(to_richtext_method.to_richtext("A **letter** to [Goethe](https://goethe.de) & *Schiller*").text, "A <strong>letter</strong> to <a href=\"https://goethe.de\">Goethe</a> &amp; <em>Schiller</em>");
+        assert_eq!(to_richtext_method.to_richtext("# Letter\n- one\n- two\n\nfirst line\nsecond line").text, "<h1>Letter</h1><ul><li>one</li><li>two</li></ul><p>first line<br/>second line</p>");
+        assert_eq!(to_richtext_method.to_richtext("[x](javascript:alert)").removed, vec!["a href 'javascript:alert'"]);
+    }
+}
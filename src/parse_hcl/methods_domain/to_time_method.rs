@@ -0,0 +1,185 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
+use hcl::{Block, Expression};
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperToTimeMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureToTimeMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    formats: Option<Vec<String>>,
+    timezone: Option<String>,
+}
+
+impl TransientStructureToTimeMethod {
+    fn new(output: String) -> TransientStructureToTimeMethod {
+        TransientStructureToTimeMethod {
+            output,
+            input: None,
+            formats: None,
+            timezone: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_formats(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.formats.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple formats-attributes  in method '{:?}'.", self.output)));
+        }
+        let mut formats = vec![];
+        for expr in expression.to_vec()? {
+            formats.push(expr.to_string_2()?);
+        }
+        self.formats = Option::from(formats);
+        Ok(())
+    }
+    fn add_timezone(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.timezone.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple timezone-attributes  in method '{:?}'.", self.output)));
+        }
+        self.timezone = Option::from(expression.to_string_2()?);
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("to_time-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        if self.timezone.is_none() {
+            return Err(HCLDataError::ParsingError(format!("to_time-method '{:?}' doesn't have a timezone-attribute provided", self)));
+        }
+        if let Some(formats) = &self.formats {
+            if formats.is_empty() {
+                return Err(HCLDataError::ParsingError(format!("to_time-method '{:?}': formats is empty", self)));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl WrapperToTimeMethod {
+    pub(crate) fn to_time_method(&self) -> Result<ToTimeMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureToTimeMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "formats" => {
+                    transient_structure.add_formats(attribute.expr.to_owned())?;
+                }
+                "timezone" => {
+                    transient_structure.add_timezone(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        ToTimeMethod::new(transient_structure)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ToTimeMethod {
+    pub output: String,
+    pub input: HeaderValue,
+    // chrono-formats of the local date-times, e.g. "%d.%m.%Y %H:%M"
+    pub formats: Vec<String>,
+    pub timezone: FixedOffset,
+}
+
+impl ToTimeMethod {
+    fn new(transient_structure: TransientStructureToTimeMethod) -> Result<ToTimeMethod, HCLDataError> {
+        let timezone = to_offset(transient_structure.timezone.as_ref().unwrap())?;
+        let formats = transient_structure.formats.unwrap_or(
+            ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M", "%d.%m.%Y %H:%M:%S", "%d.%m.%Y %H:%M", "%Y-%m-%d", "%d.%m.%Y"]
+                .iter().map(|format| format.to_string()).collect());
+        Ok(ToTimeMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            formats,
+            timezone,
+        })
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("to_time-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        Ok(())
+    }
+    pub(crate) fn to_time(&self, value: &str) -> Result<String, HCLDataError> {
+        // values that already have a time zone are kept in their offset
+        let value = value.trim();
+        if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+            return Ok(date_time.format("%Y-%m-%dT%H:%M:%S%:z").to_string());
+        }
+        for format in self.formats.iter() {
+            let naive = match NaiveDateTime::parse_from_str(value, format) {
+                Ok(naive) => { naive }
+                Err(_) => {
+                    // formats without a time are read as midnight
+                    match NaiveDate::parse_from_str(value, format) {
+                        Ok(date) => { date.and_hms_opt(0, 0, 0).unwrap() }
+                        Err(_) => { continue; }
+                    }
+                }
+            };
+            let date_time = self.timezone.from_local_datetime(&naive).unwrap();
+            return Ok(date_time.format("%Y-%m-%dT%H:%M:%S%:z").to_string());
+        }
+        Err(HCLDataError::InputError(format!("to_time '{}': cannot read '{}' as time with formats {:?}.", self.output, value, self.formats)))
+    }
+}
+
+fn to_offset(timezone: &str) -> Result<FixedOffset, HCLDataError> {
+    // only fixed offsets like "+01:00", "-05:30" or "Z"; named zones would need a tz-database
+    if timezone == "Z" || timezone == "UTC" {
+        return Ok(FixedOffset::east_opt(0).unwrap());
+    }
+    match DateTime::parse_from_rfc3339(format!("2000-01-01T00:00:00{}", timezone).as_str()) {
+        Ok(date_time) => { Ok(*date_time.offset()) }
+        Err(_) => {
+            Err(HCLDataError::ParsingError(format!("to_time-method: timezone '{}' is not a fixed offset like '+01:00' or 'Z'.", timezone)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::to_time_method::WrapperToTimeMethod;
+
+    #[test]
+    fn test_to_time_method() {
+        let block = block!(to_time "hasTimeStamp" {
+            input = "Zeitpunkt"
+            formats = ["%d.%m.%Y %H:%M"]
+            timezone = "+01:00"
+        });
+        let result = WrapperToTimeMethod(block.to_owned()).to_time_method();
+        assert!(result.is_ok());
+        let to_time_method = result.unwrap();
+        assert_eq!(to_time_method.to_time("24.12.2019 18:30").unwrap(), "2019-12-24T18:30:00+01:00");
+        assert_eq!(to_time_method.to_time("2019-12-24T18:30:00Z").unwrap(), "2019-12-24T18:30:00+00:00");
+        assert!(to_time_method.to_time("24.12.2019").is_err());
+    }
+    #[test]
+    fn test_to_time_method_named_zone() {
+        let block = block!(to_time "hasTimeStamp" {
+            input = "Zeitpunkt"
+            timezone = "Europe/Zurich"
+        });
+        assert!(WrapperToTimeMethod(block.to_owned()).to_time_method().is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use hcl::{Block, Expression};
+use regex::Regex;
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+use crate::parse_xlsx::domain::uri::{encode_uri, is_uri};
+
+pub struct WrapperToUriMethod(pub(crate) Block);
+
+#[derive(Debug)]
+struct TransientStructureToUriMethod {
+    output: String,
+    input: Option<HeaderValue>,
+    scheme: Option<String>,
+    force_https: Option<bool>,
+}
+
+impl TransientStructureToUriMethod {
+    fn new(output: String) -> TransientStructureToUriMethod {
+        TransientStructureToUriMethod {
+            output,
+            input: None,
+            scheme: None,
+            force_https: None,
+        }
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple input-attributes  in method '{:?}'.", self.output)));
+        }
+        self.input = Option::from(expression.to_header_value()?);
+        Ok(())
+    }
+    fn add_scheme(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.scheme.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple scheme-attributes  in method '{:?}'.", self.output)));
+        }
+        let scheme = expression.to_string_2()?.to_lowercase();
+        if !Regex::new(r"^[a-z][a-z0-9+.\-]*$").unwrap().is_match(&scheme) {
+            return Err(HCLDataError::ParsingError(format!("to_uri-method '{}': '{}' is not a valid scheme, expected e.g. 'https'.", self.output, scheme)));
+        }
+        self.scheme = Option::from(scheme);
+        Ok(())
+    }
+    fn add_force_https(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.force_https.is_some() {
+            return Err(HCLDataError::ParsingError(format!("found multiple force_https-attributes  in method '{:?}'.", self.output)));
+        }
+        self.force_https = Option::from(expression.to_bool()?);
+        Ok(())
+    }
+    fn is_consistent(&self) -> Result<(), HCLDataError> {
+        if self.input.is_none() {
+            return Err(HCLDataError::ParsingError(format!("to_uri-method '{:?}' doesn't have an input-attribute provided", self)));
+        }
+        Ok(())
+    }
+}
+
+impl WrapperToUriMethod {
+    pub(crate) fn to_uri_method(&self) -> Result<ToUriMethod, HCLDataError> {
+        self.0.no_blocks()?;
+        let mut transient_structure = TransientStructureToUriMethod::new(self.0.get_output()?);
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                "scheme" => {
+                    transient_structure.add_scheme(attribute.expr.to_owned())?;
+                }
+                "force_https" => {
+                    transient_structure.add_force_https(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("found this unknown attribute '{:?}' in method '{:?}'.", attribute, transient_structure.output)));
+                }
+            }
+        }
+        transient_structure.is_consistent()?;
+        Ok(ToUriMethod::new(transient_structure))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ToUriMethod {
+    pub output: String,
+    pub input: HeaderValue,
+    // scheme added to values without one, e.g. "www.example.org"
+    pub scheme: String,
+    pub force_https: bool,
+    scheme_regex: Regex,
+    email_regex: Regex,
+}
+
+impl ToUriMethod {
+    fn new(transient_structure: TransientStructureToUriMethod) -> ToUriMethod {
+        ToUriMethod {
+            output: transient_structure.output,
+            input: transient_structure.input.unwrap(),
+            scheme: transient_structure.scheme.unwrap_or("https".to_string()),
+            force_https: transient_structure.force_https.unwrap_or(false),
+            scheme_regex: Regex::new(r"^([A-Za-z][A-Za-z0-9+.\-]*):").unwrap(),
+            email_regex: Regex::new(r"^[^@\s/:]+@[^@\s/:]+\.[A-Za-z]{2,}$").unwrap(),
+        }
+    }
+    pub(crate) fn is_correct(&self) -> Result<(), HCLDataError> {
+        if self.input.is_equal(&self.output) {
+            return Err(HCLDataError::ParsingError(format!("to_uri-method has the same in- and output-String, which is forbidden: '{:?}'", self.input)));
+        }
+        Ok(())
+    }
+    pub(crate) fn to_uri(&self, value: &str) -> Result<String, String> {
+        // returns the URI or the reason why the value cannot be a URI
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(String::new());
+        }
+        let mut uri = match self.scheme_regex.captures(value) {
+            // "www.example.org:8080" has no scheme, a scheme is followed by "//" or is known to have none
+            Some(captures) if value[captures[0].len()..].starts_with("//") || ["mailto", "urn", "tel", "doi", "data"].contains(&captures[1].to_lowercase().as_str()) => {
+                format!("{}{}", captures[1].to_lowercase(), &value[captures[1].len()..])
+            }
+            _ => {
+                if self.email_regex.is_match(value) {
+                    format!("mailto:{}", value)
+                } else {
+                    format!("{}://{}", self.scheme, value.trim_start_matches('/'))
+                }
+            }
+        };
+        if self.force_https {
+            if let Some(rest) = uri.strip_prefix("http://") {
+                uri = format!("https://{}", rest);
+            }
+        }
+        // the host is case-insensitive
+        if let Some(pos) = uri.find("://") {
+            let end = uri[pos + 3..].find(['/', '?', '#']).map(|end| pos + 3 + end).unwrap_or(uri.len());
+            let authority = &uri[pos + 3..end];
+            let host_start = authority.rfind('@').map(|at| pos + 3 + at + 1).unwrap_or(pos + 3);
+            uri = format!("{}{}{}", &uri[..host_start], uri[host_start..end].to_lowercase(), &uri[end..]);
+        }
+        let uri = encode_uri(&uri);
+        if !is_uri(&uri) {
+            return Err(format!("'{}' cannot be written as URI, result: '{}'", value, uri));
+        }
+        Ok(uri)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::to_uri_method::WrapperToUriMethod;
+
+    #[test]
+    fn test_to_uri_method() {
+        let block = block!(to_uri "hasLink" {
+            input = "link"
+            force_https = true
+        });
+        let result = WrapperToUriMethod(block.to_owned()).to_uri_method();
+        assert!(result.is_ok());
+        let to_uri_method = result.unwrap();
+        assert_eq!(to_uri_method.to_uri("www.Example.org/a b").unwrap(), "https://www.example.org/a%20b");
+        assert_eq!(to_uri_method.to_uri("http://example.org/Zürich?q=1#top").unwrap(), "https://example.org/Z%C3%BCrich?q=1#top");
+        assert_eq!(to_uri_method.to_uri("example.org:8080/100%/50%25").unwrap(), "https://example.org:8080/100%25/50%25");
+        assert_eq!(to_uri_method.to_uri("info@example.org").unwrap(), "mailto:info@example.org");
+        assert_eq!(to_uri_method.to_uri("urn:isbn:0451450523").unwrap(), "urn:isbn:0451450523");
+        assert!(to_uri_method.to_uri("https://").is_err());
+    }
+}
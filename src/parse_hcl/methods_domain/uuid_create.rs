@@ -0,0 +1,165 @@
+use hcl::{Block, Expression};
+use uuid::Uuid;
+use crate::expression_trait::ExpressionTransform;
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::when::{When, WrapperWhen};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+pub struct WrapperUuidCreate(pub(crate) Block);
+
+#[derive(Clone, Debug)]
+pub struct UuidCreate {
+    pub output: String,
+    pub prefix: Option<String>,
+    // with a seed the uuids are derived (v5) from the seed and the input-values, otherwise they are random (v4)
+    pub seed: Option<String>,
+    pub input: Vec<HeaderValue>,
+    pub when: Option<When>,
+}
+
+impl UuidCreate {
+    fn new(transient_uuid_create: TransientUuidCreate) -> UuidCreate {
+        UuidCreate{
+            output: transient_uuid_create.output,
+            prefix: transient_uuid_create.prefix,
+            seed: transient_uuid_create.seed,
+            input: transient_uuid_create.input.unwrap_or_default(),
+            when: transient_uuid_create.when,
+        }
+    }
+    pub(crate) fn uuid(&self, values: &[&Vec<String>]) -> String {
+        let uuid = match &self.seed {
+            None => { Uuid::new_v4() }
+            Some(seed) => {
+                let namespace = Uuid::new_v5(&Uuid::NAMESPACE_OID, seed.as_bytes());
+                let name = values.iter()
+                    .map(|values| values.iter().map(|value| value.trim()).collect::<Vec<&str>>().join("\u{1e}"))
+                    .collect::<Vec<String>>()
+                    .join("\u{1f}");
+                Uuid::new_v5(&namespace, name.as_bytes())
+            }
+        };
+        format!("{}{}", self.prefix.as_deref().unwrap_or(""), uuid)
+    }
+}
+#[derive(Debug)]
+struct TransientUuidCreate {
+    output: String,
+    prefix: Option<String>,
+    seed: Option<String>,
+    input: Option<Vec<HeaderValue>>,
+    when: Option<When>,
+}
+
+impl TransientUuidCreate {
+    fn new(output: String) -> Self {
+        TransientUuidCreate{
+            output,
+            prefix: None,
+            seed: None,
+            input: None,
+            when: None,
+        }
+    }
+    fn add_prefix(&mut self, prefix: String) -> Result<(), HCLDataError> {
+        if self.prefix.is_some() {
+            return Err(HCLDataError::InputError(format!("uuid-create-method: multiple prefix: First: '{}', Second: '{}'", self.prefix.as_ref().unwrap(), prefix)));
+        }
+        self.prefix = Some(prefix);
+        Ok(())
+    }
+    fn add_seed(&mut self, seed: String) -> Result<(), HCLDataError> {
+        if self.seed.is_some() {
+            return Err(HCLDataError::InputError(format!("uuid-create-method: multiple seed: First: '{}', Second: '{}'", self.seed.as_ref().unwrap(), seed)));
+        }
+        self.seed = Some(seed);
+        Ok(())
+    }
+    fn add_input(&mut self, expression: Expression) -> Result<(), HCLDataError> {
+        if self.input.is_some() {
+            return Err(HCLDataError::InputError(format!("uuid-create-method: multiple input in method with output '{}'", self.output)));
+        }
+        let mut input = vec![];
+        for expr in expression.to_vec()? {
+            input.push(expr.to_header_value()?);
+        }
+        self.input = Some(input);
+        Ok(())
+    }
+    fn add_when(&mut self, when: When) -> Result<(), HCLDataError> {
+        if self.when.is_some() {
+            return Err(HCLDataError::InputError(format!("uuid-create-method: multiple when-blocks in method with output '{}'", self.output)));
+        }
+        self.when = Some(when);
+        Ok(())
+    }
+    fn is_complete(&self) -> Result<(), HCLDataError> {
+        if self.input.is_some() && self.seed.is_none() {
+            return Err(HCLDataError::InputError(format!("Uuid-create-method: 'input' needs a 'seed' in '{:?}'", self)));
+        }
+        if self.seed.is_some() && self.input.as_ref().is_none_or(|input| input.is_empty()) {
+            // without input all rows would get the same uuid
+            return Err(HCLDataError::InputError(format!("Uuid-create-method: 'seed' needs an 'input' in '{:?}'", self)));
+        }
+        Ok(())
+    }
+}
+
+impl WrapperUuidCreate {
+    pub(crate) fn to_uuid_create_method(&self, output: String) -> Result<UuidCreate, HCLDataError> {
+        let mut transient_structure = TransientUuidCreate::new(output);
+        for attribute in self.0.body.attributes() {
+            match attribute.key.as_str() {
+                "prefix" => {
+                    transient_structure.add_prefix(attribute.expr.to_string_2()?)?;
+                }
+                "seed" => {
+                    transient_structure.add_seed(attribute.expr.to_string_2()?)?;
+                }
+                "input" => {
+                    transient_structure.add_input(attribute.expr.to_owned())?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("uuid-create-method: found this unknown attribute '{:?}' in method '{:?}'.", attribute, self.0.labels)));
+                }
+            }
+        }
+        for block in self.0.blocks() {
+            match block.identifier.as_str() {
+                "when" => {
+                    transient_structure.add_when(WrapperWhen(block.to_owned()).to_when()?)?;
+                }
+                _ => {
+                    return Err(HCLDataError::ParsingError(format!("uuid-create-method: found this unknown block '{:?}' in method '{:?}'.", block.identifier, self.0.labels)));
+                }
+            }
+        }
+        transient_structure.is_complete()?;
+        Ok(UuidCreate::new(transient_structure))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcl::block;
+    use crate::parse_hcl::methods_domain::uuid_create::WrapperUuidCreate;
+
+    #[test]
+    fn test_uuid_create_seed() {
+        let block = block!(create "uuid" "id" {
+            seed = "letters-2024"
+            input = ["signature"]
+        });
+        let result = WrapperUuidCreate(block.to_owned()).to_uuid_create_method("id".to_string());
+        assert!(result.is_ok());
+        let uuid_create = result.unwrap();
+        let signature = vec!["Ms. 12".to_string()];
+        assert_eq!(uuid_create.uuid(&[&signature]), uuid_create.uuid(&[&signature]));
+        assert_eq!(uuid_create.uuid(&[&signature]).len(), 36);
+        let block = block!(create "uuid" "id" {
+            seed = "letters-2024"
+        });
+        assert!(WrapperUuidCreate(block.to_owned()).to_uuid_create_method("id".to_string()).is_err());
+    }
+}
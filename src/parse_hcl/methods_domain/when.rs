@@ -0,0 +1,39 @@
+use hcl::Block;
+use crate::parse_hcl::domain::filter::{FilterCondition, TransientFilterCondition};
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::{HeaderMethods, HeaderValue};
+use crate::parse_hcl::methods_domain::wrapper_trait_block::Wrapper;
+
+#[derive(Debug, Clone)]
+pub struct When {
+    pub condition: FilterCondition,
+    pub fallback: Option<HeaderValue>,
+}
+
+pub struct WrapperWhen(pub(crate) Block);
+
+impl WrapperWhen {
+    pub(crate) fn to_when(&self) -> Result<When, HCLDataError> {
+        self.0.no_blocks()?;
+        if !self.0.labels.is_empty() {
+            return Err(HCLDataError::ParsingError(format!("when: labels are not allowed, but found: '{:?}'", self.0.labels)));
+        }
+        let mut transient_condition = TransientFilterCondition::new();
+        let mut fallback: Option<HeaderValue> = None;
+        for attribute in self.0.attributes() {
+            match attribute.key.as_str() {
+                "fallback" => {
+                    if fallback.is_some() {
+                        return Err(HCLDataError::ParsingError(format!("when: found multiple fallback-attributes: First: '{:?}', Second: '{:?}'", fallback.as_ref().unwrap(), attribute.expr)));
+                    }
+                    fallback = Some(attribute.expr.to_header_value()?);
+                }
+                _ => {
+                    transient_condition.add_attribute(attribute)?;
+                }
+            }
+        }
+        transient_condition.is_complete()?;
+        Ok(When { condition: FilterCondition::new(transient_condition), fallback })
+    }
+}
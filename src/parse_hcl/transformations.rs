@@ -4,14 +4,29 @@ use crate::parse_hcl::errors::HCLDataError;
 use crate::parse_hcl::header_value::HeaderValue;
 use crate::parse_hcl::methods_domain::combine_method::{CombineMethod, WrapperCombineMethod};
 use crate::parse_hcl::methods_domain::create_method::{CreateMethod, WrapperCreateMethod};
+use crate::parse_hcl::methods_domain::distinct_method::{DistinctMethod, WrapperDistinctMethod};
 use crate::parse_hcl::methods_domain::identify_method::{IdentifyMethod, WrapperIdentifyMethod};
-use crate::parse_hcl::methods_domain::lower_upper_method::{LowerMethod, UpperMethod, WrapperLowerUpperMethod};
+use crate::parse_hcl::methods_domain::lower_upper_method::{CaseMethod, LowerMethod, UpperMethod, WrapperLowerUpperMethod};
+use crate::parse_hcl::methods_domain::map_method::{MapMethod, WrapperMapMethod};
 use crate::parse_hcl::methods_domain::method::Method;
+use crate::parse_hcl::methods_domain::normalize_method::{NormalizeMethod, WrapperNormalizeMethod};
 use crate::parse_hcl::methods_domain::replace_label_name::{ReplaceLabelNameMethod, WrapperReplaceLabelNameMethod};
 use crate::parse_hcl::methods_domain::replace_method::{ReplaceMethod, WrapperReplaceMethod};
 use crate::parse_hcl::methods_domain::separate_method::{SeparateMethod, WrapperSeparateMethod};
+use crate::parse_hcl::methods_domain::split_method::{SplitMethod, WrapperSplitMethod};
 use crate::parse_hcl::methods_domain::to_alter_method::{AlterMethod, WrapperAlterMethod};
 use crate::parse_hcl::methods_domain::to_date_method::{ToDateMethod, WrapperToDateMethod};
+use crate::parse_hcl::methods_domain::to_number_method::{ToNumberMethod, WrapperToNumberMethod};
+use crate::parse_hcl::methods_domain::to_boolean_method::{ToBooleanMethod, WrapperToBooleanMethod};
+use crate::parse_hcl::methods_domain::to_color_method::{ToColorMethod, WrapperToColorMethod};
+use crate::parse_hcl::methods_domain::to_time_method::{ToTimeMethod, WrapperToTimeMethod};
+use crate::parse_hcl::methods_domain::to_geoname_method::{ToGeonameMethod, WrapperToGeonameMethod};
+use crate::parse_hcl::methods_domain::join_method::{JoinMethod, WrapperJoinMethod};
+use crate::parse_hcl::methods_domain::extract_method::{ExtractMethod, WrapperExtractMethod};
+use crate::parse_hcl::methods_domain::slugify_method::{SlugifyMethod, WrapperSlugifyMethod};
+use crate::parse_hcl::methods_domain::to_richtext_method::{ToRichtextMethod, WrapperToRichtextMethod};
+use crate::parse_hcl::methods_domain::to_uri_method::{ToUriMethod, WrapperToUriMethod};
+use crate::parse_hcl::methods_domain::script_method::{ScriptMethod, WrapperScriptMethod};
 use crate::parse_hcl::methods_domain::update_with_server_method::{UpdateWithServer, WrapperUpdateWithServer};
 
 #[derive(Debug)]
@@ -21,6 +36,7 @@ pub struct Transformations{
     // important, also add new method to check for duplicates!
     pub lower_methods:Vec<LowerMethod>,
     pub upper_methods:Vec<UpperMethod>,
+    pub case_methods: Vec<CaseMethod>,
     pub combine_methods:Vec<CombineMethod>,
     pub replace_methods:Vec<ReplaceMethod>,
     pub replace_label_name_methods: Vec<ReplaceLabelNameMethod>,
@@ -29,7 +45,22 @@ pub struct Transformations{
     pub alter_methods: Vec<AlterMethod>,
     pub identify_methods:Vec<IdentifyMethod>,
     pub update_with_server_methods: Vec<UpdateWithServer>,
-    pub separate_methods: Vec<SeparateMethod>
+    pub separate_methods: Vec<SeparateMethod>,
+    pub map_methods: Vec<MapMethod>,
+    pub normalize_methods: Vec<NormalizeMethod>,
+    pub split_methods: Vec<SplitMethod>,
+    pub distinct_methods: Vec<DistinctMethod>,
+    pub to_number_methods: Vec<ToNumberMethod>,
+    pub to_boolean_methods: Vec<ToBooleanMethod>,
+    pub to_color_methods: Vec<ToColorMethod>,
+    pub to_time_methods: Vec<ToTimeMethod>,
+    pub to_geoname_methods: Vec<ToGeonameMethod>,
+    pub join_methods: Vec<JoinMethod>,
+    pub extract_methods: Vec<ExtractMethod>,
+    pub slugify_methods: Vec<SlugifyMethod>,
+    pub to_richtext_methods: Vec<ToRichtextMethod>,
+    pub to_uri_methods: Vec<ToUriMethod>,
+    pub script_methods: Vec<ScriptMethod>,
 }
 
 impl Transformations {
@@ -40,6 +71,7 @@ impl Transformations {
         Transformations {
             lower_methods: vec![],
             upper_methods: vec![],
+            case_methods: vec![],
             combine_methods: vec![],
             replace_methods: vec![],
             replace_label_name_methods: vec![],
@@ -49,6 +81,21 @@ impl Transformations {
             identify_methods: vec![],
             update_with_server_methods: vec![],
             separate_methods: vec![],
+            map_methods: vec![],
+            normalize_methods: vec![],
+            split_methods: vec![],
+            distinct_methods: vec![],
+            to_number_methods: vec![],
+            to_boolean_methods: vec![],
+            to_color_methods: vec![],
+            to_time_methods: vec![],
+            to_geoname_methods: vec![],
+            join_methods: vec![],
+            extract_methods: vec![],
+            slugify_methods: vec![],
+            to_richtext_methods: vec![],
+            to_uri_methods: vec![],
+            script_methods: vec![],
         }
     }
     pub(crate) fn add_lower_method(&mut self, lower_method: LowerMethod) {
@@ -57,9 +104,15 @@ impl Transformations {
     pub(crate) fn add_separate_method(&mut self, separate_method: SeparateMethod) {
         self.separate_methods.push(separate_method);
     }
+    pub(crate) fn add_extract_method(&mut self, extract_method: ExtractMethod) {
+        self.extract_methods.push(extract_method);
+    }
     pub(crate) fn add_upper_method(&mut self, upper_method: UpperMethod) {
         self.upper_methods.push(upper_method);
     }
+    pub(crate) fn add_case_method(&mut self, case_method: CaseMethod) {
+        self.case_methods.push(case_method);
+    }
     pub(crate) fn add_alter_method(&mut self, alter_method: AlterMethod) {
         self.alter_methods.push(alter_method);
     }
@@ -82,6 +135,48 @@ impl Transformations {
     pub(crate) fn add_identify_method(&mut self, identify_method: IdentifyMethod) {
         self.identify_methods.push(identify_method);
     }
+    pub(crate) fn add_map_method(&mut self, map_method: MapMethod) {
+        self.map_methods.push(map_method);
+    }
+    pub(crate) fn add_normalize_method(&mut self, normalize_method: NormalizeMethod) {
+        self.normalize_methods.push(normalize_method);
+    }
+    pub(crate) fn add_split_method(&mut self, split_method: SplitMethod) {
+        self.split_methods.push(split_method);
+    }
+    pub(crate) fn add_distinct_method(&mut self, distinct_method: DistinctMethod) {
+        self.distinct_methods.push(distinct_method);
+    }
+    pub(crate) fn add_to_number_method(&mut self, to_number_method: ToNumberMethod) {
+        self.to_number_methods.push(to_number_method);
+    }
+    pub(crate) fn add_to_boolean_method(&mut self, to_boolean_method: ToBooleanMethod) {
+        self.to_boolean_methods.push(to_boolean_method);
+    }
+    pub(crate) fn add_to_color_method(&mut self, to_color_method: ToColorMethod) {
+        self.to_color_methods.push(to_color_method);
+    }
+    pub(crate) fn add_to_time_method(&mut self, to_time_method: ToTimeMethod) {
+        self.to_time_methods.push(to_time_method);
+    }
+    pub(crate) fn add_to_geoname_method(&mut self, to_geoname_method: ToGeonameMethod) {
+        self.to_geoname_methods.push(to_geoname_method);
+    }
+    pub(crate) fn add_slugify_method(&mut self, slugify_method: SlugifyMethod) {
+        self.slugify_methods.push(slugify_method);
+    }
+    pub(crate) fn add_to_richtext_method(&mut self, to_richtext_method: ToRichtextMethod) {
+        self.to_richtext_methods.push(to_richtext_method);
+    }
+    pub(crate) fn add_to_uri_method(&mut self, to_uri_method: ToUriMethod) {
+        self.to_uri_methods.push(to_uri_method);
+    }
+    pub(crate) fn add_script_method(&mut self, script_method: ScriptMethod) {
+        self.script_methods.push(script_method);
+    }
+    pub(crate) fn add_join_method(&mut self, join_method: JoinMethod) {
+        self.join_methods.push(join_method);
+    }
     pub(crate) fn add_update_with_server_method(&mut self, update_with_server_method: UpdateWithServer) {
         self.update_with_server_methods.push(update_with_server_method);
     }
@@ -207,6 +302,11 @@ impl TransformationsWrapper {
                     upper_method.is_correct()?;
                     transformations.add_upper_method(upper_method);
                 }
+                "case" => {
+                    let case_method = WrapperLowerUpperMethod(block.to_owned()).to_case_method()?;
+                    case_method.is_correct()?;
+                    transformations.add_case_method(case_method);
+                }
                 "combine"=> {
                     let combine_method = WrapperCombineMethod(block.to_owned()).to_combine_method()?;
                     combine_method.is_correct()?;
@@ -227,6 +327,36 @@ impl TransformationsWrapper {
                     to_date_method.is_correct()?;
                     transformations.add_to_date_method(to_date_method);
                 }
+                "to_number"=> {
+                    let to_number_method = WrapperToNumberMethod(block.to_owned()).to_number_method()?;
+                    to_number_method.is_correct()?;
+                    transformations.add_to_number_method(to_number_method);
+                }
+                "to_boolean"=> {
+                    let to_boolean_method = WrapperToBooleanMethod(block.to_owned()).to_boolean_method()?;
+                    to_boolean_method.is_correct()?;
+                    transformations.add_to_boolean_method(to_boolean_method);
+                }
+                "to_color"=> {
+                    let to_color_method = WrapperToColorMethod(block.to_owned()).to_color_method()?;
+                    to_color_method.is_correct()?;
+                    transformations.add_to_color_method(to_color_method);
+                }
+                "to_time"=> {
+                    let to_time_method = WrapperToTimeMethod(block.to_owned()).to_time_method()?;
+                    to_time_method.is_correct()?;
+                    transformations.add_to_time_method(to_time_method);
+                }
+                "to_geoname"=> {
+                    let to_geoname_method = WrapperToGeonameMethod(block.to_owned()).to_geoname_method()?;
+                    to_geoname_method.is_correct()?;
+                    transformations.add_to_geoname_method(to_geoname_method);
+                }
+                "join"=> {
+                    let join_method = WrapperJoinMethod(block.to_owned()).to_join_method()?;
+                    join_method.is_correct()?;
+                    transformations.add_join_method(join_method);
+                }
                 "create"=> {
                     let create_method = WrapperCreateMethod(block.to_owned()).to_create_method()?;
                     //create_method.is_correct()?;
@@ -245,10 +375,55 @@ impl TransformationsWrapper {
                      let update_with_server_method = WrapperUpdateWithServer(block.to_owned()).to_update_with_server_method()?;
                      transformations.add_update_with_server_method(update_with_server_method);
                  }
+                 "map"=> {
+                     let map_method = WrapperMapMethod(block.to_owned()).to_map_method()?;
+                     map_method.is_correct()?;
+                     transformations.add_map_method(map_method);
+                 }
+                 "normalize"=> {
+                     let normalize_method = WrapperNormalizeMethod(block.to_owned()).to_normalize_method()?;
+                     normalize_method.is_correct()?;
+                     transformations.add_normalize_method(normalize_method);
+                 }
+                 "split"=> {
+                     let split_method = WrapperSplitMethod(block.to_owned()).to_split_method()?;
+                     split_method.is_correct()?;
+                     transformations.add_split_method(split_method);
+                 }
+                 "distinct"=> {
+                     let distinct_method = WrapperDistinctMethod(block.to_owned()).to_distinct_method()?;
+                     distinct_method.is_correct()?;
+                     transformations.add_distinct_method(distinct_method);
+                 }
+                 "to_richtext"=> {
+                     let to_richtext_method = WrapperToRichtextMethod(block.to_owned()).to_richtext_method()?;
+                     to_richtext_method.is_correct()?;
+                     transformations.add_to_richtext_method(to_richtext_method);
+                 }
+                 "to_uri"=> {
+                     let to_uri_method = WrapperToUriMethod(block.to_owned()).to_uri_method()?;
+                     to_uri_method.is_correct()?;
+                     transformations.add_to_uri_method(to_uri_method);
+                 }
+                 "slugify"=> {
+                     let slugify_method = WrapperSlugifyMethod(block.to_owned()).to_slugify_method()?;
+                     slugify_method.is_correct()?;
+                     transformations.add_slugify_method(slugify_method);
+                 }
                  "separate"=> {
                      let separate_method = WrapperSeparateMethod(block.to_owned()).to_separate_method()?;
                      transformations.add_separate_method(separate_method);
                  }
+                 "extract"=> {
+                     let extract_method = WrapperExtractMethod(block.to_owned()).to_extract_method()?;
+                     extract_method.is_correct()?;
+                     transformations.add_extract_method(extract_method);
+                 }
+                 "script"=> {
+                     let script_method = WrapperScriptMethod(block.to_owned()).to_script_method()?;
+                     script_method.is_correct()?;
+                     transformations.add_script_method(script_method);
+                 }
                 _ => {
                     return Err(HCLDataError::ParsingError(format!("unknown method found in transformations: '{:?}'", block.identifier)));
                 }
@@ -261,7 +436,7 @@ impl TransformationsWrapper {
 mod test {
     use crate::parse_hcl::header_value::HeaderValue;
     use crate::parse_hcl::methods_domain::behavior_type::BehaviorType;
-    use crate::parse_hcl::methods_domain::combine_method::CombineMethod;
+    use crate::parse_hcl::methods_domain::combine_method::{CombineMethod, EmptyInput, MultipleValues};
     use crate::parse_hcl::methods_domain::date_type::DateType;
     use crate::parse_hcl::methods_domain::lower_upper_method::{LowerMethod, UpperMethod};
     use crate::parse_hcl::methods_domain::replace_method::ReplaceMethod;
@@ -278,6 +453,8 @@ mod test {
             new: "https".to_string(),
             behavior: BehaviorType::Lazy,
             target: TargetType::Part,
+            regex: None,
+            when: None,
         };
         let replace_method2 = ReplaceMethod {
             output: "hasValue".to_string(),
@@ -286,6 +463,8 @@ mod test {
             new: "https".to_string(),
             behavior: BehaviorType::Lazy,
             target: TargetType::Part,
+            regex: None,
+            when: None,
         };
         let combine_method = CombineMethod {
             input: vec![HeaderValue::Name("hasExternalLink2".to_string()), HeaderValue::Name("hasValue".to_string())],
@@ -293,6 +472,10 @@ mod test {
             middle: Option::from("_".to_string()),
             prefix: Option::from("my_project".to_string()),
             suffix: None,
+            template: None,
+            empty: EmptyInput::Keep,
+            multiple: MultipleValues::Pairwise,
+            when: None,
         };
         /*
         let transformations = Transformations {
@@ -319,6 +502,8 @@ mod test {
             new: "https".to_string(),
             behavior: BehaviorType::Lazy,
             target: TargetType::Part,
+            regex: None,
+            when: None,
         };
         let combine_method = CombineMethod {
             input: vec![HeaderValue::Name("hasExternalLink2".to_string()), HeaderValue::Number(4)],
@@ -326,12 +511,17 @@ mod test {
             middle: Option::from("_".to_string()),
             prefix: Option::from("my_project".to_string()),
             suffix: None,
+            template: None,
+            empty: EmptyInput::Keep,
+            multiple: MultipleValues::Pairwise,
+            when: None,
         };
         let to_date_method = ToDateMethod {
             output: "hasDate".to_string(),
             input: HeaderValue::Name("hasValue".to_string()),
             date_type: DateType::Gregorian,
             date_patterns: vec![],
+            when: None,
         };
         /*
         let transformations = Transformations {
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::num::ParseIntError;
 use std::ops::Index;
 use regex::Regex;
+use chrono::DateTime;
 use crate::parse_dm::domain::dasch_list::{DaSCHList, ListNode};
 use crate::parse_dm::domain::data_model::DataModel;
 use crate::parse_dm::domain::gui_element::GUIElement;
@@ -12,6 +13,7 @@ use crate::parse_xlsx::domain::subheader_value::{SubheaderValues};
 use crate::parse_xlsx::domain::dasch_value::{DaschValue, TransientDaschValue, WrapperDaschValue};
 use crate::parse_xlsx::domain::encoding::{Encoding, EncodingWrapper};
 use crate::parse_xlsx::domain::permissions::{Permissions, PermissionsWrapper};
+use crate::parse_xlsx::domain::uri::is_uri;
 use crate::parse_xlsx::errors::ExcelDataError;
 
 #[derive(Clone, Debug)]
@@ -125,7 +127,7 @@ struct DaschValueFieldWrapper( Vec<String>);
 
 
 impl DaschValueFieldWrapper {
-    fn to_dasch_value_field(&self, prop_name: &String, maybe_suppl_value: Option<&TransientSupplementValueField>, data_model: &DataModel, set_permissions: bool) -> Result<DaschValueField, ExcelDataError> {
+    fn to_dasch_value_field(&self, prop_name: &String, maybe_suppl_value: Option<&TransientSupplementValueField>, data_model: &DataModel, set_permissions: bool, origin: &str) -> Result<DaschValueField, ExcelDataError> {
         let curr_prop = match data_model.properties.iter().find(|property| property.name.eq(&prop_name.to_owned())) {
             None => {
                 // should never happen
@@ -133,7 +135,7 @@ impl DaschValueFieldWrapper {
             }
             Some(curr_prop) => { curr_prop }
         };
-        self.check_values(curr_prop, data_model)?;
+        self.check_values(curr_prop, data_model, origin)?;
         let mut dasch_values = vec![];
         for (pos, value) in self.0.iter().enumerate() {
             let dasch_value = WrapperDaschValue(value.to_owned()).to_dasch_value(pos, maybe_suppl_value, &curr_prop, set_permissions)?;
@@ -142,7 +144,8 @@ impl DaschValueFieldWrapper {
         Ok(DaschValueField::new(dasch_values, prop_name.to_owned()))
     }
 
-    fn check_values(&self, curr_prop: &Property, data_model: &DataModel) -> Result<(), ExcelDataError> {
+    fn check_values(&self, curr_prop: &Property, data_model: &DataModel, origin: &str) -> Result<(), ExcelDataError> {
+        // origin names the resource and its excel row, e.g. "resource 'letter_1' in row 3"
         match curr_prop.object {
             ValueObject::ListValue => {
                 let list: &DaSCHList = match
@@ -185,7 +188,12 @@ impl DaschValueFieldWrapper {
                 // check if date is valid DSP-Date
             }
             ValueObject::UriValue => {
-                // we don't check if URI is correct
+                // URIs must be valid according to RFC 3986; use to_uri to add a scheme or to encode spaces
+                for value in self.0.iter() {
+                    if !value.is_empty() && !is_uri(value) {
+                        return Err(ExcelDataError::InputError(format!("'{}' is not a valid URI of property '{}' of {}, expected e.g. 'https://www.example.org' (see to_uri).", value, curr_prop.name, origin)));
+                    }
+                }
             }
             ValueObject::GeonameValue => {
                 // we don't check if Geoname is correct, but we check if Geoname is a number
@@ -194,67 +202,72 @@ impl DaschValueFieldWrapper {
                         let _ = match value.parse::<usize>() {
                             Ok(_) => {}
                             Err(_) => {
-                                return Err(ExcelDataError::InputError(format!("Cannot parse Geoname-Number of '{:?}' to usize: {}", curr_prop, value)));
+                                return Err(ExcelDataError::InputError(format!("Cannot parse Geoname-Number of '{:?}' to usize: {} (see to_geoname).", curr_prop, value)));
                             }
                         };
                     }
                 }
             }
             ValueObject::DecimalValue => {
-                // check if parsing is possible
+                // only plain decimals are accepted, e.g. '-1234.5'; use to_number for other formats
+                let re = Regex::new(r"^-?\d+(\.\d+)?$").unwrap();
                 for value in self.0.iter() {
                     if !value.is_empty() {
+                        if !re.is_match(value) {
+                            return Err(ExcelDataError::InputError(format!("'{}' is not a decimal of property '{}' of {}, expected e.g. '-1234.5' (see to_number).", value, curr_prop.name, origin)));
+                        }
                         let _ = match value.parse::<rust_decimal::Decimal>() {
                             Ok(decimal) => { decimal }
                             Err(error) => {
-                                return Err(ExcelDataError::InputError(format!("cannot parse '{}' to decimal: {:?} in {:?}", value, error, curr_prop)));
+                                return Err(ExcelDataError::InputError(format!("cannot parse '{}' to decimal of property '{}' of {}: {:?}", value, curr_prop.name, origin, error)));
                             }
                         };
                     }
                 }
             }
             ValueObject::ColorValue => {
-                // we don't check if ColorValue is correct
+                // only '#rrggbb' is accepted; use to_color for names, 'rgb()' or '#rgb'
+                let re = Regex::new(r"^#[0-9a-fA-F]{6}$").unwrap();
+                for value in self.0.iter() {
+                    if !value.is_empty() && !re.is_match(value) {
+                        return Err(ExcelDataError::InputError(format!("'{}' is not a color of property '{}' of {}, expected e.g. '#ff0000' (see to_color).", value, curr_prop.name, origin)));
+                    }
+                }
             }
             ValueObject::IntValue => {
-                // check if parsing is possible
+                // only plain integers are accepted, e.g. '-1234'; use to_number for other formats
+                let re = Regex::new(r"^-?\d+$").unwrap();
                 for value in self.0.iter() {
                     if !value.is_empty() {
-                        let _ = match value.parse::<usize>() {
+                        if !re.is_match(value) {
+                            return Err(ExcelDataError::InputError(format!("'{}' is not an integer of property '{}' of {}, expected e.g. '-1234' (see to_number).", value, curr_prop.name, origin)));
+                        }
+                        let _ = match value.parse::<i64>() {
                             Ok(integer) => { integer }
                             Err(error) => {
-                                return Err(ExcelDataError::InputError(format!("cannot parse '{}' to integer: {:?} in {:?}", value, error, curr_prop)));
+                                return Err(ExcelDataError::InputError(format!("cannot parse '{}' to integer of property '{}' of {}: {:?}", value, curr_prop.name, origin, error)));
                             }
                         };
                     }
                 }
             }
             ValueObject::BooleanValue => {
-                if self.0.len() > 1 {
-                    return Err(ExcelDataError::ParsingError(format!("Boolean-values are only allowed single, but found multiple: '{:?}' for property '{}'", self.0, curr_prop.name)))
+                // only 'true' or 'false' is accepted; use to_boolean for other words
+                let values: Vec<&String> = self.0.iter().filter(|value| !value.trim().is_empty()).collect();
+                if values.len() > 1 {
+                    return Err(ExcelDataError::ParsingError(format!("Boolean-values are only allowed single, but found multiple: '{:?}' for property '{}' of {}", self.0, curr_prop.name, origin)))
                 }
-                if !self.0.is_empty() {
-                    let _: bool = match self.0.get(0).unwrap().trim().parse::<bool>()
-                    {
-                        Ok(bool_val) => { bool_val }
-                        Err(error) => {
-                            return Err(ExcelDataError::ParsingError(format!("cannot parse '{}' from string to bool. Error message: {}", self.0.get(0).unwrap(), error)));
-                        }
-                    };
+                if let Some(value) = values.first() {
+                    if value.as_str() != "true" && value.as_str() != "false" {
+                        return Err(ExcelDataError::InputError(format!("'{}' is not a boolean of property '{}' of {}, expected 'true' or 'false' (see to_boolean).", value, curr_prop.name, origin)));
+                    }
                 }
             }
             ValueObject::TimeValue => {
-                // yyyy-mm-ddThh:mm:ss
-                // 2021-11-30T12:00:00+00:00
-                let re = Regex::new(r"^\d{4}-\d{2}-\d{2}\D{1}\d{2}:\d{2}:\d{2}+\d{2}:\d{2}").unwrap();
+                // xsd:dateTime with time zone, e.g. '2021-11-30T12:00:00+00:00'; use to_time for local date-times
                 for value in self.0.iter() {
-                    if !value.is_empty() {
-                        let _ = match re.captures(value) {
-                            None => {
-                                return Err(ExcelDataError::ParsingError(format!("cannot parse '{}' to TimeValue", value)));
-                            }
-                            Some(_) => {}
-                        };
+                    if !value.is_empty() && DateTime::parse_from_rfc3339(value).is_err() {
+                        return Err(ExcelDataError::InputError(format!("'{}' is not a time of property '{}' of {}, expected e.g. '2021-11-30T12:00:00+01:00' (see to_time).", value, curr_prop.name, origin)));
                     }
                 }
             }
@@ -270,7 +283,7 @@ impl DaschValueFieldWrapper {
 }
 impl FieldsWrapper {
 
-    pub(crate) fn to_dasch_value_fields(&self, data_model: &DataModel, set_permissions: bool) -> Result<Vec<DaschValueField>, ExcelDataError> {
+    pub(crate) fn to_dasch_value_fields(&self, data_model: &DataModel, set_permissions: bool, origin: &str) -> Result<Vec<DaschValueField>, ExcelDataError> {
         let mut dasch_value_fields: Vec<DaschValueField> = vec![];
         let mut prop_name_to_transient_suppl_value = HashMap::new();
         for (prop_name, prop_suppl_values) in self.1.iter() {
@@ -285,7 +298,7 @@ impl FieldsWrapper {
         }
         for (prop_name, values) in self.0.iter() {
             let maybe_suppl_value =  prop_name_to_transient_suppl_value.get(prop_name);
-            let dasch_value_field = DaschValueFieldWrapper(values.to_vec()).to_dasch_value_field(prop_name, maybe_suppl_value, data_model, set_permissions)?;
+            let dasch_value_field = DaschValueFieldWrapper(values.to_vec()).to_dasch_value_field(prop_name, maybe_suppl_value, data_model, set_permissions, origin)?;
             dasch_value_fields.push(dasch_value_field);
         }
         Ok(dasch_value_fields)
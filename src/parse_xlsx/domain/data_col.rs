@@ -28,3 +28,21 @@ impl TransientDataCol {
     }
 
 }
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::collections::HashMap;
+    use crate::parse_xlsx::domain::data_col::DataCol;
+
+    // columns of a sheet for tests: header and values of every row
+    pub(crate) fn columns(columns: Vec<(&str, Vec<Vec<&str>>)>) -> (HashMap<usize, DataCol>, HashMap<String, usize>) {
+        let mut col_nr_to_cols = HashMap::new();
+        let mut header_to_col_nr = HashMap::new();
+        for (col_nr, (header, col)) in columns.into_iter().enumerate() {
+            let col = col.into_iter().map(|values| values.into_iter().map(|value| value.to_string()).collect()).collect();
+            col_nr_to_cols.insert(col_nr, DataCol::new(col, header.to_string()));
+            header_to_col_nr.insert(header.to_string(), col_nr);
+        }
+        (col_nr_to_cols, header_to_col_nr)
+    }
+}
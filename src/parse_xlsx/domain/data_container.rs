@@ -1,39 +1,51 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::parse_dm::domain::cardinality::Cardinality;
 use crate::parse_dm::domain::data_model::DataModel;
 use crate::parse_hcl::domain::parse_info::ParseInformation;
 use crate::parse_hcl::domain::prop_supplement::PropSupplement;
 use crate::parse_hcl::domain::resource_supplement::ResourceSupplement;
 use crate::parse_xlsx::domain::data_col::DataCol;
-use crate::parse_xlsx::domain::data_header::{discern_label_id_propnames_and_supplements, DataHeader, DataHeaderWrapper};
+use crate::parse_dm::domain::resource::DMResource;
+use crate::parse_xlsx::domain::data_header::{check_row_propnames, discern_label_id_propnames_and_supplements, propnames_of_resource, DataHeader, DataHeaderWrapper};
 use crate::parse_xlsx::domain::data_row::DataRow;
 use crate::parse_xlsx::domain::header::Header;
 use crate::parse_xlsx::domain::instance::{InstanceWrapper, Instance};
+use crate::parse_xlsx::domain::intermediate_sheet::SheetSource;
+use crate::parse_xlsx::domain::row_filter::{deduplicate_rows, group_rows, position_in_row, DifferingDuplicate, GroupConflict};
 use crate::parse_xlsx::domain::updated_data_sheet::UpdatedDataSheet;
 use crate::parse_xlsx::errors::ExcelDataError;
 
 pub struct DataContainer {
     pub res_name: String,
     pub data_header: DataHeader,
-    pub resources: Vec<Instance>
+    pub resources: Vec<Instance>,
+    // values of these properties were sanitized by to_richtext, they are written as they are
+    pub richtext_propnames: HashSet<String>,
 }
 
 impl DataContainer {
-    fn new(data_header: DataHeader, data_resources: Vec<Instance>, res_name: String) -> Self {
-        DataContainer{data_header, res_name,resources:  data_resources}
+    fn new(data_header: DataHeader, data_resources: Vec<Instance>, res_name: String, richtext_propnames: HashSet<String>) -> Self {
+        DataContainer{data_header, res_name,resources:  data_resources, richtext_propnames}
     }
 }
 
 pub struct DataContainerWrapper (pub(crate) UpdatedDataSheet);
 
 impl DataContainerWrapper {
-    pub(crate) fn to_data_container(&self, data_model: &DataModel, parse_info: &ParseInformation) -> Result<DataContainer, ExcelDataError> {
-        let mut data_instances: Vec<Instance> = vec![];
-        let (rows, col_nr_to_row_nr) = _to_rows(&self.0.col_nr_to_cols);
-        let supplements = parse_info.res_name_to_supplements.get(self.0.res_name.as_str());
-        let resource = match data_model.resources.iter().find(|resource| resource.name.eq(&self.0.res_name)) {
-            None => { return Err(ExcelDataError::ParsingError(format!("not found resource with name '{}' in data-model with resources: {:?}", self.0.res_name, data_model.resources.iter().map(|resource| &resource.name).collect::<Vec<_>>()))) }
-            Some(dm_resource) => { dm_resource }
+    pub(crate) fn to_data_containers(&self, data_model: &DataModel, parse_info: &ParseInformation) -> Result<Vec<DataContainer>, ExcelDataError> {
+        let (mut rows, col_nr_to_row_nr) = _to_rows(&self.0.col_nr_to_cols, &self.0.source);
+        if let Some(id) = parse_info.res_name_to_deduplicate.get(self.0.res_name.as_str()) {
+            let (unique_rows, duplicates, differing) = deduplicate_rows(rows, id.as_ref(), &self.0.header_to_col_nr, &col_nr_to_row_nr)?;
+            println!("Deduplicate resource '{}': removed {} rows with an already existing id.", self.0.res_name, duplicates);
+            report_differing_duplicates(&self.0.res_name, &differing);
+            rows = unique_rows;
+        }
+        let group_pos = match parse_info.res_name_to_group_by.get(self.0.res_name.as_str()) {
+            None => { None }
+            Some(group_by) => { Some(position_in_row(group_by, &self.0.header_to_col_nr, &col_nr_to_row_nr)?) }
         };
+        let supplements = parse_info.res_name_to_supplements.get(self.0.res_name.as_str());
+        let richtext_propnames = richtext_propnames(parse_info, &self.0.res_name, &self.0.header_to_col_nr);
         /*
         let supplements = match parse_info.res_name_to_supplements.get(self.0.res_name.as_str()) {
             None => {
@@ -41,18 +53,161 @@ impl DataContainerWrapper {
             }
             Some(supplements) => {supplements}
         };*/
+        let resource_column = parse_info.res_name_to_resource_column.get(self.0.res_name.as_str());
+        let class_pos = match resource_column {
+            None => { None }
+            Some(resource_column) => { Some(position_in_row(&resource_column.input, &self.0.header_to_col_nr, &col_nr_to_row_nr)?) }
+        };
 
         let(col_nr_to_propname, col_nr_to_prop_suppl, col_nr_to_res_suppl, col_nr_to_id_label) = discern_label_id_propnames_and_supplements(&self.0.header_to_col_nr, &data_model.properties, supplements)?;
         let (row_nr_to_propname, row_nr_to_prop_suppl, row_nr_to_res_suppl, row_nr_to_id_label) = change_col_nr_to_row_nr(col_nr_to_propname, col_nr_to_prop_suppl, col_nr_to_res_suppl, col_nr_to_row_nr, col_nr_to_id_label);
-        let data_header = DataHeaderWrapper(self.0.header_to_col_nr.to_owned()).to_data_header(&resource, &row_nr_to_propname, &row_nr_to_prop_suppl, &row_nr_to_res_suppl, &row_nr_to_id_label)?;
-        for row in rows.iter() {
-            let instance = InstanceWrapper(row.to_owned()).to_instance(&data_model, &parse_info.separator, &row_nr_to_propname, &row_nr_to_prop_suppl, &row_nr_to_res_suppl, &row_nr_to_id_label, &resource, parse_info.set_permissions)?;
-            data_instances.push(instance);
+        if resource_column.is_none() {
+            let resource = find_resource(data_model, &self.0.res_name)?;
+            let data_header = DataHeaderWrapper(self.0.header_to_col_nr.to_owned()).to_data_header(&resource, &row_nr_to_propname, &row_nr_to_prop_suppl, &row_nr_to_res_suppl, &row_nr_to_id_label)?;
+            if let Some(group_pos) = group_pos {
+                let (grouped_rows, merged, conflicts) = group_rows(rows, group_pos, &multi_value_positions(&row_nr_to_propname, resource), &supplement_positions(&row_nr_to_propname, &row_nr_to_prop_suppl));
+                println!("Group resource '{}' by id: merged {} rows into rows with the same id.", self.0.res_name, merged);
+                report_conflicts(&self.0.res_name, &conflicts, &row_nr_to_propname);
+                rows = grouped_rows;
+            }
+            let mut data_instances: Vec<Instance> = vec![];
+            for row in rows.iter() {
+                let instance = InstanceWrapper(row.to_owned()).to_instance(&data_model, &parse_info.separator, &row_nr_to_propname, &row_nr_to_prop_suppl, &row_nr_to_res_suppl, &row_nr_to_id_label, &resource, parse_info.set_permissions)?;
+                data_instances.push(instance);
+            }
+            return Ok(vec![DataContainer::new(data_header, data_instances, self.0.res_name.to_owned(), richtext_propnames)]);
         }
-        Ok(DataContainer::new(data_header, data_instances, self.0.res_name.to_owned()))
+        let resource_column = resource_column.unwrap();
+        // resource-class is chosen per row: group the rows by class and create one data-container per class
+        let class_to_rows = group_by_class(rows, class_pos.unwrap(), |value| resource_column.class(value))?;
+        let mut data_containers = vec![];
+        for (class, mut class_rows) in class_to_rows {
+            let resource = find_resource(data_model, &class)?;
+            let class_row_nr_to_propname = propnames_of_resource(&row_nr_to_propname, resource);
+            if let Some(group_pos) = group_pos {
+                let (grouped_rows, merged, conflicts) = group_rows(class_rows, group_pos, &multi_value_positions(&class_row_nr_to_propname, resource), &supplement_positions(&class_row_nr_to_propname, &row_nr_to_prop_suppl));
+                println!("Group resource-class '{}' by id: merged {} rows into rows with the same id.", class, merged);
+                report_conflicts(&class, &conflicts, &class_row_nr_to_propname);
+                class_rows = grouped_rows;
+            }
+            let data_header = DataHeaderWrapper(self.0.header_to_col_nr.to_owned()).to_data_header(&resource, &class_row_nr_to_propname, &row_nr_to_prop_suppl, &row_nr_to_res_suppl, &row_nr_to_id_label)?;
+            let mut data_instances: Vec<Instance> = vec![];
+            for row in class_rows.iter() {
+                check_row_propnames(row, &row_nr_to_propname, resource)?;
+                let instance = InstanceWrapper(row.to_owned()).to_instance(&data_model, &parse_info.separator, &class_row_nr_to_propname, &row_nr_to_prop_suppl, &row_nr_to_res_suppl, &row_nr_to_id_label, &resource, parse_info.set_permissions)?;
+                data_instances.push(instance);
+            }
+            println!("Resource-mapping '{}': {} rows of resource-class '{}'.", self.0.res_name, data_instances.len(), class);
+            data_containers.push(DataContainer::new(data_header, data_instances, class, richtext_propnames.to_owned()));
+        }
+        Ok(data_containers)
     }
 }
 
+fn richtext_propnames(parse_info: &ParseInformation, res_name: &str, header_to_col_nr: &HashMap<String, usize>) -> HashSet<String> {
+    // the outputs of to_richtext and all headers assigned to them
+    let col_nrs: HashSet<&usize> = match parse_info.res_name_to_updates.get(res_name) {
+        None => { return HashSet::new() }
+        Some(transformations) => {
+            transformations.to_richtext_methods.iter().filter_map(|to_richtext_method| header_to_col_nr.get(&to_richtext_method.output)).collect()
+        }
+    };
+    header_to_col_nr.iter().filter(|(_, col_nr)| col_nrs.contains(col_nr)).map(|(header, _)| header.to_owned()).collect()
+}
+
+fn find_resource<'a>(data_model: &'a DataModel, res_name: &String) -> Result<&'a DMResource, ExcelDataError> {
+    match data_model.resources.iter().find(|resource| resource.name.eq(res_name)) {
+        None => { Err(ExcelDataError::ParsingError(format!("not found resource with name '{}' in data-model with resources: {:?}", res_name, data_model.resources.iter().map(|resource| &resource.name).collect::<Vec<_>>()))) }
+        Some(dm_resource) => { Ok(dm_resource) }
+    }
+}
+
+fn multi_value_positions(row_nr_to_propname: &HashMap<usize, Vec<String>>, resource: &DMResource) -> HashSet<usize> {
+    // positions of properties that allow multiple values (0-n, 1-n)
+    row_nr_to_propname.iter()
+        .filter(|(_, propnames)| propnames.iter().all(|propname| resource.properties.iter().any(|prop| prop.propname.eq(propname) && matches!(prop.cardinality, Cardinality::ZeroToN | Cardinality::OneToN))))
+        .map(|(pos, _)| pos.to_owned())
+        .collect()
+}
+
+fn supplement_positions(row_nr_to_propname: &HashMap<usize, Vec<String>>, row_nr_to_prop_suppl: &HashMap<usize, Vec<PropSupplement>>) -> HashMap<usize, Vec<usize>> {
+    // positions of the supplements of every property, they are merged together with the values of the property
+    let mut supplement_positions: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (suppl_pos, prop_suppls) in row_nr_to_prop_suppl.iter() {
+        for prop_suppl in prop_suppls.iter() {
+            if let Some((pos, _)) = row_nr_to_propname.iter().find(|(_, propnames)| propnames.contains(&prop_suppl.part_of)) {
+                supplement_positions.entry(pos.to_owned()).or_default().push(suppl_pos.to_owned());
+            }
+        }
+    }
+    supplement_positions
+}
+
+fn report_differing_duplicates(res_name: &str, differing: &[DifferingDuplicate]) {
+    if differing.is_empty() {
+        return;
+    }
+    println!("Deduplicate resource '{}': {} removed rows differ from the kept row with the same id:", res_name, differing.len());
+    for duplicate in differing.iter() {
+        println!("  id '{}': kept row {}, removed row {}.", duplicate.id, duplicate.kept_row_nr, duplicate.row_nr);
+    }
+}
+
+fn report_conflicts(res_name: &str, conflicts: &[GroupConflict], row_nr_to_propname: &HashMap<usize, Vec<String>>) {
+    if conflicts.is_empty() {
+        return;
+    }
+    println!("Group resource '{}' by id: {} single values differ between rows with the same id, the first value is kept:", res_name, conflicts.len());
+    for conflict in conflicts.iter() {
+        let column = row_nr_to_propname.get(&conflict.pos).map(|propnames| propnames.join(", ")).unwrap_or(format!("column {}", conflict.pos));
+        println!("  id '{}', '{}': kept {:?} of row {}, dropped {:?} of row {}.", conflict.id, column, conflict.kept, conflict.kept_row_nr, conflict.dropped, conflict.row_nr);
+    }
+}
+
+fn group_by_class<F: Fn(&str) -> String>(rows: Vec<DataRow>, class_pos: usize, to_class: F) -> Result<BTreeMap<String, Vec<DataRow>>, ExcelDataError> {
+    let mut class_to_rows: BTreeMap<String, Vec<DataRow>> = BTreeMap::new();
+    for row in rows {
+        let values = row.row[class_pos].iter().map(|value| value.trim()).filter(|value| !value.is_empty()).collect::<Vec<&str>>();
+        if values.len() != 1 {
+            return Err(ExcelDataError::InputError(format!("Row with values '{:?}' should have exactly one resource-class, but found: '{:?}'", row.row, values)));
+        }
+        let class = to_class(values[0]);
+        class_to_rows.entry(class).or_insert_with(Vec::new).push(row);
+    }
+    Ok(class_to_rows)
+}
+
+pub(crate) fn merge_data_containers(data_containers: Vec<DataContainer>) -> Result<Vec<DataContainer>, ExcelDataError> {
+    // data-containers of the same resource (e.g. from different sheets) are grouped into one
+    let mut merged: Vec<DataContainer> = vec![];
+    for data_container in data_containers {
+        match merged.iter_mut().find(|existing| existing.res_name.eq(&data_container.res_name)) {
+            None => { merged.push(data_container) }
+            Some(existing) => {
+                check_compatible(existing, &data_container)?;
+                existing.richtext_propnames.extend(data_container.richtext_propnames);
+                existing.resources.extend(data_container.resources);
+            }
+        }
+    }
+    Ok(merged)
+}
+
+fn check_compatible(existing: &DataContainer, other: &DataContainer) -> Result<(), ExcelDataError> {
+    // the positions of the data-headers belong to the rows of their own sheet, only the kind of columns must agree
+    if existing.data_header.bitstream.is_some() != other.data_header.bitstream.is_some() {
+        return Err(ExcelDataError::InputError(format!("Resource '{}' is mapped from several sheets, but only some of them have a bitstream.", existing.res_name)));
+    }
+    // a property sanitized by to_richtext in one sheet is written as it is, so the other sheet must sanitize it as well
+    let differing = existing.richtext_propnames.symmetric_difference(&other.richtext_propnames)
+        .filter(|propname| existing.data_header.propname_to_pos.contains_key(*propname) && other.data_header.propname_to_pos.contains_key(*propname))
+        .collect::<Vec<&String>>();
+    if !differing.is_empty() {
+        return Err(ExcelDataError::InputError(format!("Resource '{}' is mapped from several sheets, but only some of them create the properties {:?} with to_richtext.", existing.res_name, differing)));
+    }
+    Ok(())
+}
+
 
 fn change_col_nr_to_row_nr(col_nr_to_propname: HashMap<usize, Vec<String>>, col_nr_to_prop_suppl: HashMap<usize, Vec<PropSupplement>>, col_nr_to_res_suppl: HashMap<usize, Vec<ResourceSupplement>>, col_nr_to_row_nr: HashMap<usize, usize>, col_nr_to_id_label: HashMap<usize, Vec<Header>>) -> (HashMap<usize, Vec<String>>, HashMap<usize, Vec<PropSupplement>>, HashMap<usize, Vec<ResourceSupplement>>, HashMap<usize, Vec<Header>>) {
     (
@@ -63,12 +218,14 @@ fn change_col_nr_to_row_nr(col_nr_to_propname: HashMap<usize, Vec<String>>, col_
     )
 }
 
-fn _to_rows(col_nr_to_cols: &HashMap<usize, DataCol>) -> (Vec<DataRow>, HashMap<usize, usize>) {
+fn _to_rows(col_nr_to_cols: &HashMap<usize, DataCol>, source: &SheetSource) -> (Vec<DataRow>, HashMap<usize, usize>) {
     let mut rows = vec![];
     // it is supposed we have at least one col, if no columns exist it breaks here
     let row_length = col_nr_to_cols.get(&0).unwrap().col.len();
-    for _ in 0..row_length {
-        rows.push(DataRow::new());
+    for row_nr in 0..row_length {
+        let mut row = DataRow::new();
+        row.row_nr = source.excel_row(row_nr);
+        rows.push(row);
     }
     // dictionary makes sure the correct column corresponds to the respective row
     let mut col_nr_to_row_nr: HashMap<usize, usize> = HashMap::new();
@@ -82,3 +239,31 @@ fn _to_rows(col_nr_to_cols: &HashMap<usize, DataCol>) -> (Vec<DataRow>, HashMap<
     (rows, col_nr_to_row_nr)
 }
 
+#[cfg(test)]
+mod test {
+    use std::collections::{HashMap, HashSet};
+    use crate::parse_xlsx::domain::data_container::{merge_data_containers, DataContainer};
+    use crate::parse_xlsx::domain::data_header::DataHeader;
+
+    fn data_container(propnames: Vec<&str>, richtext_propnames: Vec<&str>) -> DataContainer {
+        let data_header = DataHeader {
+            id: 0,
+            label: 1,
+            res_permission: None,
+            bitstream: None,
+            bitstream_permissions: None,
+            propname_to_pos: propnames.iter().enumerate().map(|(pos, propname)| (propname.to_string(), pos + 2)).collect(),
+            propname_to_pos_prop_supplement: HashMap::new(),
+        };
+        DataContainer::new(data_header, vec![], "Letter".to_string(), richtext_propnames.iter().map(|propname| propname.to_string()).collect())
+    }
+
+    #[test]
+    fn test_merge_data_containers() {
+        let merged = merge_data_containers(vec![data_container(vec!["hasTitle"], vec![]), data_container(vec!["hasTitle", "hasText"], vec!["hasText"])]).unwrap();
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].richtext_propnames, HashSet::from(["hasText".to_string()]));
+        // the same property is sanitized in one sheet only
+        assert!(merge_data_containers(vec![data_container(vec!["hasText"], vec![]), data_container(vec!["hasText"], vec!["hasText"])]).is_err());
+    }
+}
@@ -6,6 +6,7 @@ use crate::parse_dm::domain::resource::DMResource;
 use crate::parse_hcl::domain::supplements::{Supplements};
 use crate::parse_hcl::domain::prop_supplement::PropSupplement;
 use crate::parse_hcl::domain::resource_supplement::{ResourceSupplType, ResourceSupplement};
+use crate::parse_xlsx::domain::data_row::DataRow;
 use crate::parse_xlsx::domain::header::Header;
 use crate::parse_xlsx::domain::transient_data_header::TransientDataHeader;
 use crate::parse_xlsx::errors::ExcelDataError;
@@ -212,6 +213,31 @@ fn _add_propnames(transient_data_header: &mut TransientDataHeader, pos_to_propna
     Ok(())
 }
 
+pub(crate) fn propnames_of_resource(row_nr_to_propname: &HashMap<usize, Vec<String>>, resource: &DMResource) -> HashMap<usize, Vec<String>> {
+    // keep only the propnames that are part of the resource
+    let propnames_dm: Vec<&String> = resource.properties.iter().map(|prop|&prop.propname).collect();
+    row_nr_to_propname.iter()
+        .map(|(pos, propnames)| (pos.to_owned(), propnames.iter().filter(|propname| propnames_dm.contains(propname)).map(|propname| propname.to_owned()).collect::<Vec<String>>()))
+        .filter(|(_, propnames)| !propnames.is_empty())
+        .collect()
+}
+
+pub(crate) fn check_row_propnames(row: &DataRow, row_nr_to_propname: &HashMap<usize, Vec<String>>, resource: &DMResource) -> Result<(), ExcelDataError> {
+    // a row with a resource-class chosen per row must not have values for properties of other resources
+    let propnames_dm: Vec<&String> = resource.properties.iter().map(|prop|&prop.propname).collect();
+    for (pos, propnames) in row_nr_to_propname.iter() {
+        if row.row[*pos].iter().all(|value| value.trim().is_empty()) {
+            continue;
+        }
+        for propname in propnames {
+            if !propnames_dm.contains(&propname) {
+                return Err(ExcelDataError::InputError(format!("Row with values '{:?}' has resource-class '{}', but has a value for propname '{}' that is not part of this resource.", row.row, resource.name, propname)));
+            }
+        }
+    }
+    Ok(())
+}
+
 pub(crate) fn add_prop_suppl(transient_data_header: &mut TransientDataHeader, row_nr_to_prop_suppl: &&HashMap<usize, Vec<PropSupplement>>) {
     for (pos, prop_suppls) in row_nr_to_prop_suppl.iter() {
         for prop_suppl in prop_suppls {
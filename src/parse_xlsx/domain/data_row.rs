@@ -2,11 +2,13 @@ use calamine::Data;
 
 #[derive(Clone, Debug)]
 pub struct DataRow {
-    pub row: Vec<Vec<String>>
+    pub row: Vec<Vec<String>>,
+    // excel row number of the row, 0 if it isn't read from a sheet
+    pub row_nr: usize,
 }
 impl DataRow {
     pub fn new() -> DataRow {
-        DataRow{ row: vec![] }
+        DataRow{ row: vec![], row_nr: 0 }
     }
     pub fn add_data(&mut self, data: Vec<String>) {
         self.row.push(data);
@@ -1,5 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::vec;
+use regex::Regex;
 use crate::parse_dm::domain::data_model::DataModel;
 use crate::parse_hcl::domain::assignments::Assignments;
 use crate::parse_hcl::domain::parse_info::ParseInformation;
@@ -8,23 +9,26 @@ use crate::parse_hcl::errors::HCLDataError;
 use crate::parse_hcl::header_value::HeaderValue;
 use crate::parse_xlsx::domain::data_col::DataCol;
 use crate::parse_xlsx::domain::data_row::DataRow;
-use crate::parse_xlsx::domain::intermediate_sheet::IntermediateSheet;
-use crate::parse_xlsx::domain::manipulations::{perform_combine, perform_alter, perform_create, perform_lower, perform_replace, perform_to_date, perform_upper, perform_replace_label_name, perform_replace_with_iri, perform_separate};
+use crate::parse_xlsx::domain::intermediate_sheet::{IntermediateSheet, SheetSource};
+use crate::parse_xlsx::domain::row_filter::filter_rows;
+use crate::parse_xlsx::domain::manipulations::{perform_combine, perform_alter, perform_create, perform_lower, perform_replace, perform_to_date, perform_upper, perform_replace_label_name, perform_replace_with_iri, perform_separate, perform_when, perform_map, perform_normalize, perform_split, perform_distinct, perform_to_number, perform_to_boolean, perform_to_color, perform_to_time, perform_to_geoname, perform_extract, perform_case, perform_slugify, perform_to_richtext, perform_to_uri, perform_script, find_header_number};
 
 #[derive(Clone)]
 pub struct ExpandedDataSheet {
     pub res_name: String,
     pub col_nr_to_cols: HashMap<usize, DataCol>,
-    pub header_to_col_nr: HashMap<String, usize>
+    pub header_to_col_nr: HashMap<String, usize>,
+    pub source: SheetSource,
 }
 
 
 impl ExpandedDataSheet {
-    fn new(res_name: String, col_nr_to_cols: HashMap<usize, DataCol>, header_to_col_nr: HashMap<String, usize>) -> Self {
+    fn new(res_name: String, col_nr_to_cols: HashMap<usize, DataCol>, header_to_col_nr: HashMap<String, usize>, source: SheetSource) -> Self {
         ExpandedDataSheet {
             res_name,
             col_nr_to_cols,
-            header_to_col_nr
+            header_to_col_nr,
+            source,
         }
     }
 }
@@ -76,15 +80,35 @@ impl ExpandedDataSheetWrapper {
     pub(crate) fn to_expanded_data_sheet(&self, sheet_info: &SheetInfo, data_model: &DataModel, res_name_iri: &HashMap<String, HashMap<String, String>>, separator: &String) -> Result<ExpandedDataSheet, HCLDataError> {
         // this is where the changes requested in the parse-information file should be processed
         let header_to_col_nr = header_to_col_nr(&sheet_info.assignments, &self.0.col_nr_to_data_cols)?;
+        let mut col_nr_to_data_cols = self.0.col_nr_to_data_cols.to_owned();
+        let mut source = self.0.source.to_owned();
+        if let Some(normalization) = &sheet_info.normalize {
+            // sheet-wide default: every value is normalized before transformations
+            for data_col in col_nr_to_data_cols.values_mut() {
+                data_col.col = data_col.col.iter()
+                    .map(|values| values.iter().map(|value| normalization.normalize(value)).collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+            }
+        }
+        if let Some(filter) = &sheet_info.filter {
+            // excluded rows don't reach the transformations, so they cannot fail or advance counters
+            let kept_rows = filter_rows(&col_nr_to_data_cols, filter, &header_to_col_nr)?;
+            let total = col_nr_to_data_cols.get(&0).map(|data_col| data_col.col.len()).unwrap_or(0);
+            println!("Filter of resource '{}': excluded {} of {} rows.", sheet_info.resource_name, total - kept_rows.len(), total);
+            for data_col in col_nr_to_data_cols.values_mut() {
+                data_col.col = kept_rows.iter().map(|row_nr| data_col.col[*row_nr].to_owned()).collect();
+            }
+            source.kept_rows = Some(kept_rows);
+        }
         let (col_nr_to_cols, header_to_col_nr) = match sheet_info.transformations {
             None => {
-                (self.0.col_nr_to_data_cols.to_owned(), header_to_col_nr)
+                (col_nr_to_data_cols, header_to_col_nr)
             }
             Some(_) => {
-                create_data(self.0.col_nr_to_data_cols.to_owned(), header_to_col_nr, sheet_info, &data_model, res_name_iri, separator)?
+                create_data(col_nr_to_data_cols, header_to_col_nr, sheet_info, &source, &data_model, res_name_iri, separator)?
             }
         };
-        Ok(ExpandedDataSheet::new(sheet_info.resource_name.to_owned(), col_nr_to_cols, header_to_col_nr))
+        Ok(ExpandedDataSheet::new(sheet_info.resource_name.to_owned(), col_nr_to_cols, header_to_col_nr, source))
     }
 }
 
@@ -120,10 +144,37 @@ fn header_to_col_nr(assignments: &Assignments, col_nr_to_data_col: &HashMap<usiz
     Ok(header_to_col_nr)
 }
 
-fn create_data(mut col_nr_to_cols_expanded: HashMap<usize, DataCol>, mut header_to_col_nr_expanded: HashMap<String, usize>, sheet_info: &SheetInfo, data_model: &&DataModel, res_name_iri: &HashMap<String, HashMap<String, String>>, separator: &String) -> Result<(HashMap<usize, DataCol>, HashMap<String, usize>), HCLDataError> {
+fn create_data(mut col_nr_to_cols_expanded: HashMap<usize, DataCol>, mut header_to_col_nr_expanded: HashMap<String, usize>, sheet_info: &SheetInfo, source: &SheetSource, data_model: &&DataModel, res_name_iri: &HashMap<String, HashMap<String, String>>, separator: &String) -> Result<(HashMap<usize, DataCol>, HashMap<String, usize>), HCLDataError> {
     let transformations = sheet_info.transformations.as_ref().unwrap();
+    // infer length for create method and when-conditions
+    let length = match col_nr_to_cols_expanded.get(&0usize) {
+        None => {
+            if !transformations.create_methods.is_empty() {
+                return Err(HCLDataError::ParsingError("Create-methods: No other columns exist, so I cannot infer the length of the column that should be created.".to_string()))
+            }
+            0usize
+        }
+        Some(data_col) => {data_col.col.len()}
+    };
+    // constant, row number and source can be used as input by all other methods
+    for create_method in transformations.create_methods.iter().filter(|create_method| create_method.is_sheet_value()) {
+        let data_col = perform_when(create_method.when(), None, length, &col_nr_to_cols_expanded, &header_to_col_nr_expanded, |col_nr_to_cols, rows| {
+            perform_create(create_method, rows, length, source, col_nr_to_cols, &header_to_col_nr_expanded)
+        })?;
+        add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+    }
+    for normalize_method in &transformations.normalize_methods {
+        let data_col = perform_normalize(normalize_method, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+    }
     for replace_method in &transformations.replace_methods {
-        let data_col = perform_replace(replace_method, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        let data_col = perform_when(&replace_method.when, Some(&replace_method.input), length, &col_nr_to_cols_expanded, &header_to_col_nr_expanded, |col_nr_to_cols, _| {
+            perform_replace(replace_method, col_nr_to_cols, &header_to_col_nr_expanded)
+        })?;
+        add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+    }
+    for map_method in &transformations.map_methods {
+        let data_col = perform_map(map_method, source, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
         add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
     }
     for replace_with_iri in &transformations.update_with_server_methods {
@@ -144,39 +195,96 @@ fn create_data(mut col_nr_to_cols_expanded: HashMap<usize, DataCol>, mut header_
         add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
 
     }
+    for case_method in &transformations.case_methods {
+        let data_col = perform_case(case_method, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+    }
     for combine_method in &transformations.combine_methods {
-        let data_col = perform_combine(combine_method, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        let data_col = perform_when(&combine_method.when, combine_method.input.first(), length, &col_nr_to_cols_expanded, &header_to_col_nr_expanded, |col_nr_to_cols, _| {
+            perform_combine(combine_method, source, col_nr_to_cols, &header_to_col_nr_expanded)
+        })?;
         add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
 
     }
     for to_date_method in &transformations.to_date_methods {
-        let data_col = perform_to_date(to_date_method, &col_nr_to_cols_expanded, &header_to_col_nr_expanded, separator)?;
+        let data_col = perform_when(&to_date_method.when, Some(&to_date_method.input), length, &col_nr_to_cols_expanded, &header_to_col_nr_expanded, |col_nr_to_cols, _| {
+            perform_to_date(to_date_method, source, col_nr_to_cols, &header_to_col_nr_expanded)
+        })?;
         add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
     }
-    // infer length for create method
-    let mut length = 0usize;
-    if !transformations.create_methods.is_empty() {
-        length = match col_nr_to_cols_expanded.get(&0usize) {
-            None => {
-                return Err(HCLDataError::ParsingError("Create-methods: No other columns exist, so I cannot infer the length of the column that should be created.".to_string()))
-            }
-            Some(data_col) => {data_col.col.len()}
-        };
+    for to_number_method in &transformations.to_number_methods {
+        let data_col = perform_to_number(to_number_method, source, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
     }
-    for create_method in &transformations.create_methods {
-        let data_col = perform_create(create_method, length);
+    for to_boolean_method in &transformations.to_boolean_methods {
+        let data_col = perform_to_boolean(to_boolean_method, source, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+    }
+    for to_color_method in &transformations.to_color_methods {
+        let data_col = perform_to_color(to_color_method, source, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+    }
+    for to_time_method in &transformations.to_time_methods {
+        let data_col = perform_to_time(to_time_method, source, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+    }
+    for to_geoname_method in &transformations.to_geoname_methods {
+        let data_col = perform_to_geoname(to_geoname_method, source, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+    }
+    for to_richtext_method in &transformations.to_richtext_methods {
+        let data_col = perform_to_richtext(to_richtext_method, source, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+    }
+    for to_uri_method in &transformations.to_uri_methods {
+        let data_col = perform_to_uri(to_uri_method, source, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+    }
+    for slugify_method in &transformations.slugify_methods {
+        let data_col = perform_slugify(slugify_method, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+    }
+    for create_method in transformations.create_methods.iter().filter(|create_method| !create_method.is_sheet_value()) {
+        let data_col = perform_when(create_method.when(), None, length, &col_nr_to_cols_expanded, &header_to_col_nr_expanded, |col_nr_to_cols, rows| {
+            perform_create(create_method, rows, length, source, col_nr_to_cols, &header_to_col_nr_expanded)
+        })?;
         add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
     }
     for alter_method in &transformations.alter_methods {
-        let data_col = perform_alter(alter_method, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        let data_col = perform_when(&alter_method.when, Some(&alter_method.input), length, &col_nr_to_cols_expanded, &header_to_col_nr_expanded, |col_nr_to_cols, _| {
+            perform_alter(alter_method, col_nr_to_cols, &header_to_col_nr_expanded)
+        })?;
         add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
     }
+    for split_method in &transformations.split_methods {
+        let data_cols = perform_split(split_method, source, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        for data_col in data_cols {
+            add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+        }
+    }
+    for distinct_method in &transformations.distinct_methods {
+        let data_cols = perform_distinct(distinct_method, source, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        for data_col in data_cols {
+            add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+        }
+    }
     for separate_method in &transformations.separate_methods {
         let data_cols = perform_separate(separate_method, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
         for data_col in data_cols {
             add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
         }
     }
+    for extract_method in &transformations.extract_methods {
+        let data_cols = perform_extract(extract_method, source, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        for data_col in data_cols {
+            add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+        }
+    }
+    // scripts run last, so they can use the outputs of all other methods
+    for script_method in &transformations.script_methods {
+        let data_col = perform_script(script_method, source, &col_nr_to_cols_expanded, &header_to_col_nr_expanded)?;
+        add_to_header_cols(&mut header_to_col_nr_expanded, &mut col_nr_to_cols_expanded, data_col);
+    }
     Ok((col_nr_to_cols_expanded, header_to_col_nr_expanded))
 }
 
@@ -224,12 +332,147 @@ fn assign_headers(raw_headers: &DataRow, assignments: &HashMap<String, HeaderVal
 
 pub fn expanded_data_sheets(sheets: Vec<IntermediateSheet>, parse_info: &ParseInformation, data_model: &DataModel, res_name_iri: HashMap<String, HashMap<String, String>>, separator: &String) -> Result<Vec<ExpandedDataSheet>, HCLDataError> {
     let mut expanded_data_sheets = vec![];
+    let mut sheet_infos = vec![];
     for sheet in sheets.iter() {
-        let sheet_info = parse_info.rel_path_to_xlsx_workbooks.get(&sheet.rel_path).unwrap().sheet_infos.get(&sheet.sheet_info_nr).unwrap();
+        let sheet_info = parse_info.rel_path_to_xlsx_workbooks.get(&sheet.rel_path).unwrap().sheet_infos.get(&sheet.sheet_info_nr).unwrap()
+            .iter()
+            .find(|sheet_info|sheet_info.resource_name.eq(&sheet.res_name))
+            .unwrap();
         let expanded_data_sheet = ExpandedDataSheetWrapper(sheet.to_owned()).to_expanded_data_sheet(sheet_info, data_model, &res_name_iri, separator)?;
         expanded_data_sheets.push(expanded_data_sheet);
+        sheet_infos.push(sheet_info);
     }
+    add_links(&mut expanded_data_sheets, &sheets, &sheet_infos)?;
+    check_id_collisions(&expanded_data_sheets, &sheet_infos)?;
+    check_richtext_links(&expanded_data_sheets, &sheet_infos)?;
     Ok(expanded_data_sheets)
 }
 
+struct IdOrigin<'a> {
+    res_name: &'a String,
+    sheet_nr: usize,
+    row_nr: usize,
+    input: Vec<&'a Vec<String>>,
+}
+
+fn check_id_collisions(expanded_data_sheets: &[ExpandedDataSheet], sheet_infos: &[&SheetInfo]) -> Result<(), HCLDataError> {
+    // ids created by hash- or uuid-create must be unique across all sheets of the run;
+    // the same input creates the same id, this is only allowed in a sheet whose rows are merged by deduplicate or group_by
+    let mut id_to_origin: HashMap<&String, IdOrigin> = HashMap::new();
+    for (sheet_nr, (expanded_data_sheet, sheet_info)) in expanded_data_sheets.iter().zip(sheet_infos.iter()).enumerate() {
+        let transformations = match &sheet_info.transformations {
+            None => { continue }
+            Some(transformations) => { transformations }
+        };
+        let merges_rows = sheet_info.deduplicate || sheet_info.group_by.is_some();
+        for create_method in transformations.create_methods.iter() {
+            let input = match create_method.unique_id_input() {
+                None => { continue }
+                Some(input) => { input }
+            };
+            let mut input_cols: Vec<&DataCol> = vec![];
+            for header in input.iter() {
+                let col_nr = find_header_number(header, &expanded_data_sheet.col_nr_to_cols, &expanded_data_sheet.header_to_col_nr)?;
+                input_cols.push(expanded_data_sheet.col_nr_to_cols.get(&col_nr).unwrap());
+            }
+            let col_nr = expanded_data_sheet.header_to_col_nr.get(create_method.output()).unwrap();
+            let data_col = expanded_data_sheet.col_nr_to_cols.get(col_nr).unwrap();
+            for (row_nr, values) in data_col.col.iter().enumerate() {
+                let row_input: Vec<&Vec<String>> = input_cols.iter().map(|input_col| &input_col.col[row_nr]).collect();
+                for id in values.iter().filter(|id| !id.is_empty()) {
+                    if let Some(origin) = id_to_origin.get(id) {
+                        let same_input = !input.is_empty() && origin.input == row_input;
+                        if same_input && merges_rows && origin.sheet_nr == sheet_nr {
+                            continue;
+                        }
+                        let hint = if same_input { "the inputs are the same, use deduplicate or group_by to merge these rows" } else { "different inputs created the same id" };
+                        return Err(HCLDataError::InputError(format!("id-collision: '{}' of resource '{}' in row {} was already created for resource '{}' in row {}; {}.", id, expanded_data_sheet.res_name, expanded_data_sheet.source.excel_row(row_nr), origin.res_name, expanded_data_sheets[origin.sheet_nr].source.excel_row(origin.row_nr), hint)));
+                    }
+                    id_to_origin.insert(id, IdOrigin { res_name: &expanded_data_sheet.res_name, sheet_nr, row_nr, input: row_input.to_owned() });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_richtext_links(expanded_data_sheets: &Vec<ExpandedDataSheet>, sheet_infos: &Vec<&SheetInfo>) -> Result<(), HCLDataError> {
+    // links of to_richtext to other resources must point to an id of a resource of the run
+    let has_richtext = sheet_infos.iter().any(|sheet_info| sheet_info.transformations.as_ref().is_some_and(|transformations| !transformations.to_richtext_methods.is_empty()));
+    if !has_richtext {
+        return Ok(());
+    }
+    let mut ids: HashSet<&String> = HashSet::new();
+    for (expanded_data_sheet, sheet_info) in expanded_data_sheets.iter().zip(sheet_infos.iter()) {
+        if let Some(col_nr) = id_col_nr(expanded_data_sheet, sheet_info)? {
+            ids.extend(expanded_data_sheet.col_nr_to_cols.get(&col_nr).unwrap().col.iter().flatten());
+        }
+    }
+    let link = Regex::new("href=\"IRI:(.*?):IRI\"").unwrap();
+    for (expanded_data_sheet, sheet_info) in expanded_data_sheets.iter().zip(sheet_infos.iter()) {
+        let transformations = match &sheet_info.transformations {
+            None => { continue }
+            Some(transformations) => { transformations }
+        };
+        for to_richtext_method in transformations.to_richtext_methods.iter() {
+            let col_nr = expanded_data_sheet.header_to_col_nr.get(&to_richtext_method.output).unwrap();
+            for (row_nr, values) in expanded_data_sheet.col_nr_to_cols.get(col_nr).unwrap().col.iter().enumerate() {
+                for captures in values.iter().flat_map(|value| link.captures_iter(value)) {
+                    if !ids.contains(&captures[1].to_string()) {
+                        return Err(HCLDataError::InputError(format!("to_richtext '{}' of resource '{}': link to resource '{}' in row {}, but no resource of this run has this id.", to_richtext_method.output, expanded_data_sheet.res_name, &captures[1], expanded_data_sheet.source.excel_row(row_nr))));
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn id_col_nr(expanded_data_sheet: &ExpandedDataSheet, sheet_info: &SheetInfo) -> Result<Option<usize>, HCLDataError> {
+    // the id-column declared in the sheet, otherwise the column with the header 'id'
+    match &sheet_info.id {
+        Some(id) => { Ok(Some(find_header_number(id, &expanded_data_sheet.col_nr_to_cols, &expanded_data_sheet.header_to_col_nr)?)) }
+        None => {
+            Ok(expanded_data_sheet.header_to_col_nr.iter().find(|(header, _)| header.to_lowercase() == "id").map(|(_, col_nr)| col_nr.to_owned()))
+        }
+    }
+}
+
+fn add_links(expanded_data_sheets: &mut Vec<ExpandedDataSheet>, sheets: &Vec<IntermediateSheet>, sheet_infos: &Vec<&SheetInfo>) -> Result<(), HCLDataError> {
+    // a link takes the id of the resource that another mapping of the same sheet creates from the same row
+    for (pos, sheet_info) in sheet_infos.iter().enumerate() {
+        let links = match &sheet_info.links {
+            None => { continue }
+            Some(links) => { links }
+        };
+        for (propname, res_name) in links.propname_to_res_name.iter() {
+            let linked_pos = match sheets.iter().position(|other|other.rel_path.eq(&sheets[pos].rel_path) && other.sheet_info_nr == sheets[pos].sheet_info_nr && other.res_name.eq(res_name)) {
+                None => {
+                    return Err(HCLDataError::InputError(format!("Link '{}' of resource '{}': not found resource '{}' in the same sheet.", propname, sheet_info.resource_name, res_name)));
+                }
+                Some(linked_pos) => { linked_pos }
+            };
+            let linked_sheet = &expanded_data_sheets[linked_pos];
+            let id_col_nr = match id_col_nr(linked_sheet, sheet_infos[linked_pos])? {
+                None => {
+                    return Err(HCLDataError::InputError(format!("Link '{}' of resource '{}': resource '{}' has no 'id'-column that could be referenced. Declare it with 'id' in its sheet.", propname, sheet_info.resource_name, res_name)));
+                }
+                Some(col_nr) => { col_nr }
+            };
+            // both mappings may have filtered different rows, so the rows are matched by their position in the sheet
+            let sheet_row_to_ids: HashMap<usize, &Vec<String>> = linked_sheet.col_nr_to_cols.get(&id_col_nr).unwrap().col.iter().enumerate()
+                .map(|(row_nr, ids)| (linked_sheet.source.sheet_row(row_nr), ids))
+                .collect();
+            let expanded_data_sheet = &expanded_data_sheets[pos];
+            let length = expanded_data_sheet.col_nr_to_cols.get(&0).map(|data_col| data_col.col.len()).unwrap_or(0);
+            let link_col: Vec<Vec<String>> = (0..length)
+                .map(|row_nr| sheet_row_to_ids.get(&expanded_data_sheet.source.sheet_row(row_nr)).map(|ids| ids.to_vec()).unwrap_or(vec!["".to_string()]))
+                .collect();
+            let expanded_data_sheet = &mut expanded_data_sheets[pos];
+            add_to_header_cols(&mut expanded_data_sheet.header_to_col_nr, &mut expanded_data_sheet.col_nr_to_cols, DataCol::new(link_col, propname.to_owned()));
+        }
+    }
+    Ok(())
+}
+
 
@@ -167,7 +167,11 @@ impl InstanceWrapper {
             }
         }
         transient_instance.found_id_label()?;
-        let dasch_value_fields = FieldsWrapper(transient_instance.propname_to_values.to_owned(), transient_instance.prop_name_to_prop_suppl_values.to_owned()).to_dasch_value_fields(data_model, set_permissions)?;
+        let origin = match self.0.row_nr {
+            0 => { format!("resource '{}'", transient_instance.id.as_ref().unwrap()) }
+            row_nr => { format!("resource '{}' in row {}", transient_instance.id.as_ref().unwrap(), row_nr) }
+        };
+        let dasch_value_fields = FieldsWrapper(transient_instance.propname_to_values.to_owned(), transient_instance.prop_name_to_prop_suppl_values.to_owned()).to_dasch_value_fields(data_model, set_permissions, &origin)?;
         let resource_data = to_resource_data(&transient_instance.res_suppl_values, &resource.super_field, set_permissions, separator)?;
         Ok(Instance::new(dasch_value_fields, resource_data, transient_instance.id.unwrap(), transient_instance.label.unwrap()))
     }
@@ -10,15 +10,41 @@ pub struct IntermediateSheet {
     pub(crate) res_name: String,
     pub rel_path: String,
     pub sheet_info_nr: usize,
+    pub source: SheetSource,
     pub col_nr_to_data_cols: HashMap<usize, DataCol>,
 }
 
+#[derive(Clone, Debug)]
+pub struct SheetSource {
+    pub rel_path: String,
+    pub sheet_name: String,
+    // excel row number of the first row after the headers
+    pub first_row: usize,
+    // positions of the rows that are left after filtering, None if all rows are kept
+    pub kept_rows: Option<Vec<usize>>,
+}
+
+impl SheetSource {
+    pub(crate) fn sheet_row(&self, row_nr: usize) -> usize {
+        // position of the n-th row of data in the sheet before filtering
+        match &self.kept_rows {
+            None => { row_nr }
+            Some(kept_rows) => { kept_rows[row_nr] }
+        }
+    }
+    pub(crate) fn excel_row(&self, row_nr: usize) -> usize {
+        // excel row number of the n-th row of data
+        self.first_row + self.sheet_row(row_nr)
+    }
+}
+
 impl IntermediateSheet {
-    fn new(res_name: String, rel_path: String, sheet_info_nr: usize) -> Self {
+    fn new(res_name: String, rel_path: String, sheet_info_nr: usize, source: SheetSource) -> Self {
         IntermediateSheet {
             res_name,
             rel_path,
             sheet_info_nr,
+            source,
             col_nr_to_data_cols: Default::default(),
         }
     }
@@ -30,10 +56,13 @@ pub(crate) struct IntermediateSheetWrapper(pub(crate) Sheet);
 
 impl IntermediateSheetWrapper {
     pub(crate) fn to_intermediate_sheet(&self, separator: &String) -> Result<IntermediateSheet, ExcelDataError> {
-        let mut data_sheet: IntermediateSheet = IntermediateSheet::new(self.0.res_name.to_owned(), self.0.rel_path.to_owned(), self.0.sheet_info_nr);
         if self.0.table.is_empty() {
             return Err(ExcelDataError::InputError("table cannot be empty".to_string()));
         }
+        // the table starts at its first used cell; rows count from 1 in excel and the first row holds the headers
+        let first_row = self.0.table.start().map(|(row, _)| row as usize).unwrap_or(0) + 2;
+        let source = SheetSource { rel_path: self.0.rel_path.to_owned(), sheet_name: self.0.sheet_name.to_owned(), first_row, kept_rows: None };
+        let mut data_sheet: IntermediateSheet = IntermediateSheet::new(self.0.res_name.to_owned(), self.0.rel_path.to_owned(), self.0.sheet_info_nr, source);
         // prepare cols
         let mut cols: Vec<Vec<String>> = vec![];
         for _ in 0..self.0.table.width() {
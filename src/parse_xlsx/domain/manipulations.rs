@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::ops::Index;
+use regex::Regex;
 use clap::builder::TypedValueParser;
 use crate::parse_dm::domain::dasch_list::{DaSCHList, ListNode};
 use crate::parse_dm::domain::data_model::DataModel;
@@ -11,20 +12,39 @@ use crate::parse_hcl::errors::HCLDataError::MethodError;
 use crate::parse_hcl::errors::MethodError::Combine;
 use crate::parse_hcl::header_value::HeaderValue;
 use crate::parse_hcl::methods_domain::behavior_type::BehaviorType;
-use crate::parse_hcl::methods_domain::combine_method::CombineMethod;
+use crate::parse_hcl::methods_domain::combine_method::{CombineMethod, MultipleValues};
 use crate::parse_hcl::methods_domain::create_method::{CreateMethod};
 use crate::parse_hcl::methods_domain::date_pattern::DatePattern;
 use crate::parse_hcl::methods_domain::date_type::DateType;
+use crate::parse_hcl::methods_domain::distinct_method::DistinctMethod;
 use crate::parse_hcl::methods_domain::integer_create::IntegerCreate;
-use crate::parse_hcl::methods_domain::lower_upper_method::{LowerMethod, UpperMethod};
-use crate::parse_hcl::methods_domain::permissions_create::PermissionsCreate;
+use crate::parse_hcl::methods_domain::hash_create::HashCreate;
+use crate::parse_hcl::methods_domain::uuid_create::UuidCreate;
+use crate::parse_xlsx::domain::intermediate_sheet::SheetSource;
+use crate::parse_hcl::methods_domain::lower_upper_method::{CaseMethod, LowerMethod, UpperMethod};
+use crate::parse_hcl::methods_domain::map_method::MapMethod;
+use crate::parse_hcl::methods_domain::normalize_method::NormalizeMethod;
 use crate::parse_hcl::methods_domain::replace_label_name::ReplaceLabelNameMethod;
 use crate::parse_hcl::methods_domain::replace_method::ReplaceMethod;
 use crate::parse_hcl::methods_domain::separate_method::SeparateMethod;
-use crate::parse_hcl::methods_domain::step::{StepMethod};
+use crate::parse_hcl::methods_domain::split_method::SplitMethod;
+use crate::parse_hcl::methods_domain::slugify_method::SlugifyMethod;
+use crate::parse_hcl::methods_domain::to_richtext_method::ToRichtextMethod;
+use crate::parse_hcl::methods_domain::to_uri_method::ToUriMethod;
+use crate::parse_hcl::methods_domain::script_method::{ScriptMethod, ScriptPer};
+use crate::parse_hcl::methods_domain::extract_method::{ExtractMethod, Unmatched};
+use crate::parse_hcl::methods_domain::target_type::TargetType;
 use crate::parse_hcl::methods_domain::to_alter_method::AlterMethod;
 use crate::parse_hcl::methods_domain::to_date_method::ToDateMethod;
+use crate::parse_hcl::methods_domain::to_number_method::ToNumberMethod;
+use crate::parse_hcl::methods_domain::to_boolean_method::ToBooleanMethod;
+use crate::parse_hcl::methods_domain::to_color_method::ToColorMethod;
+use crate::parse_hcl::methods_domain::to_time_method::ToTimeMethod;
+use crate::parse_hcl::methods_domain::to_geoname_method::ToGeonameMethod;
+use crate::parse_hcl::methods_domain::join_method::{JoinMethod, MissingKeys};
+use crate::parse_xlsx::domain::expanded_data_sheet::ExpandedDataSheet;
 use crate::parse_hcl::methods_domain::update_with_server_method::UpdateWithServer;
+use crate::parse_hcl::methods_domain::when::When;
 use crate::parse_xlsx::domain::data_col::{DataCol, TransientDataCol};
 use crate::parse_xlsx::domain::data_domain::date_period::DatePeriodWrapper;
 
@@ -45,6 +65,49 @@ pub fn perform_identify(key_value_map: HashMap<String, String>, base_col: &Vec<S
 }
 
 
+pub fn perform_join(join_method: &JoinMethod, source: &SheetSource, other_sheet: &ExpandedDataSheet, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    // collects the values of all rows of the other sheet that reference the key of a row
+    let key_col = &col_nr_to_cols.get(&find_header_number(&join_method.input, col_nr_to_cols, header_to_col_nr)?).unwrap().col;
+    let foreign_key_col = &other_sheet.col_nr_to_cols.get(&find_header_number(&join_method.foreign_key, &other_sheet.col_nr_to_cols, &other_sheet.header_to_col_nr)?).unwrap().col;
+    let value_col = &other_sheet.col_nr_to_cols.get(&find_header_number(&join_method.value, &other_sheet.col_nr_to_cols, &other_sheet.header_to_col_nr)?).unwrap().col;
+    let mut key_to_values: HashMap<&str, Vec<String>> = HashMap::new();
+    for (foreign_keys, values) in foreign_key_col.iter().zip(value_col.iter()) {
+        let values: Vec<String> = values.iter().filter(|value| !value.trim().is_empty()).map(|value| value.trim().to_string()).collect();
+        for foreign_key in foreign_keys.iter().map(|foreign_key| foreign_key.trim()).filter(|foreign_key| !foreign_key.is_empty()) {
+            key_to_values.entry(foreign_key).or_default().extend(values.to_owned());
+        }
+    }
+    let keys: HashSet<&str> = key_col.iter().flatten().map(|key| key.trim()).collect();
+    let orphans: Vec<&&str> = key_to_values.keys().filter(|foreign_key| !keys.contains(*foreign_key)).collect();
+    if !orphans.is_empty() {
+        println!("Join '{}': rows of resource '{}' reference keys that don't exist: {:?}", join_method.output, join_method.resource, orphans);
+    }
+    let mut new_column = vec![];
+    for (row_nr, keys) in key_col.iter().enumerate() {
+        let mut values = vec![];
+        for key in keys.iter().map(|key| key.trim()).filter(|key| !key.is_empty()) {
+            if let Some(joined_values) = key_to_values.get(key) {
+                values.extend(joined_values.to_owned());
+            }
+        }
+        let mut values = join_method.aggregate(values);
+        if values.is_empty() {
+            match join_method.missing {
+                MissingKeys::Empty => {}
+                MissingKeys::Warn => {
+                    println!("Join '{}': no rows of resource '{}' reference key {:?} in row {}.", join_method.output, join_method.resource, keys, source.excel_row(row_nr));
+                }
+                MissingKeys::Error => {
+                    return Err(HCLDataError::InputError(format!("Join '{}': no rows of resource '{}' reference key {:?} in row {}.", join_method.output, join_method.resource, keys, source.excel_row(row_nr))));
+                }
+            }
+            values.push("".to_string());
+        }
+        new_column.push(values);
+    }
+    Ok(DataCol::new(new_column, join_method.output.to_owned()))
+}
+
 pub fn perform_replace_label_name(replace_label_name_method: &ReplaceLabelNameMethod, col_nr_to_cols_expanded: &HashMap<usize, DataCol>, header_to_col_nr_expanded: &HashMap<String, usize>, data_model: &&DataModel, separator: &String) -> Result<DataCol, HCLDataError> {
     let header_number = find_header_number(&replace_label_name_method.input, col_nr_to_cols_expanded, header_to_col_nr_expanded)?;
     let col = &col_nr_to_cols_expanded.get(&header_number).unwrap();
@@ -142,38 +205,316 @@ pub fn perform_replace(replace_method: &ReplaceMethod, col_nr_to_cols_expanded:
     let header_number = find_header_number(&replace_method.input, col_nr_to_cols_expanded, existing_header_to_col_nr)?;
     let col = &col_nr_to_cols_expanded.get(&header_number).unwrap();
 
-    let new_column = _replace(&col.col, &replace_method.new, &replace_method.old, &replace_method.behavior);
+    let new_column = match &replace_method.regex {
+        None => { _replace(&col.col, &replace_method.new, &replace_method.old, &replace_method.behavior) }
+        Some(regex) => { _replace_regex(&col.col, regex, &replace_method.new, &replace_method.behavior, &replace_method.target) }
+    };
     Ok(DataCol::new(new_column, replace_method.output.to_owned()))
 }
-pub fn perform_to_date(to_date_method: &ToDateMethod, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>, separator: &String) -> Result<DataCol, HCLDataError> {
-    /*
+pub fn perform_normalize(normalize_method: &NormalizeMethod, col_nr_to_cols_expanded: &HashMap<usize, DataCol>, existing_header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    let header_number = find_header_number(&normalize_method.input, col_nr_to_cols_expanded, existing_header_to_col_nr)?;
+    let col = &col_nr_to_cols_expanded.get(&header_number).unwrap();
+    let new_column = col.col.iter()
+        .map(|values| values.iter().map(|value| normalize_method.steps.normalize(value)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    Ok(DataCol::new(new_column, normalize_method.output.to_owned()))
+}
+pub fn perform_distinct(distinct_method: &DistinctMethod, source: &SheetSource, col_nr_to_cols_expanded: &HashMap<usize, DataCol>, existing_header_to_col_nr: &HashMap<String, usize>) -> Result<Vec<DataCol>, HCLDataError> {
+    let header_number = find_header_number(&distinct_method.input, col_nr_to_cols_expanded, existing_header_to_col_nr)?;
+    let col = &col_nr_to_cols_expanded.get(&header_number).unwrap();
+    let mut aligned_cols: Vec<(&String, &DataCol)> = vec![];
+    for (header, output) in distinct_method.aligned.iter() {
+        let number = find_header_number(&HeaderValue::Name(header.to_owned()), col_nr_to_cols_expanded, existing_header_to_col_nr)?;
+        aligned_cols.push((output, col_nr_to_cols_expanded.get(&number).unwrap()));
+    }
+    let mut new_column = vec![];
+    let mut new_aligned_cols: Vec<Vec<Vec<String>>> = aligned_cols.iter().map(|_| vec![]).collect();
+    for (row_nr, values) in col.col.iter().enumerate() {
+        let (positions, duplicates) = distinct_method.distinct(values);
+        if !duplicates.is_empty() {
+            println!("Distinct '{}': removed duplicates {:?} in row {}.", distinct_method.output, duplicates, source.excel_row(row_nr));
+        }
+        if positions.is_empty() {
+            new_column.push(vec!["".to_string()]);
+            new_aligned_cols.iter_mut().for_each(|aligned_col| aligned_col.push(vec!["".to_string()]));
+            continue;
+        }
+        new_column.push(positions.iter().map(|pos| values[*pos].trim().to_string()).collect());
+        for (nr, (header, aligned_col)) in aligned_cols.iter().enumerate() {
+            // a supplement-value belongs to the value at the same position, a single supplement-value to all values
+            let aligned_values = &aligned_col.col[row_nr];
+            if aligned_values.len() == values.len() {
+                new_aligned_cols[nr].push(positions.iter().map(|pos| aligned_values[*pos].to_owned()).collect());
+            } else if aligned_values.len() == 1 {
+                new_aligned_cols[nr].push(aligned_values.to_owned());
+            } else {
+                return Err(HCLDataError::InputError(format!("distinct '{}': '{}' has {} values, but '{:?}' has {} values in row {}.", distinct_method.output, header, aligned_values.len(), distinct_method.input, values.len(), source.excel_row(row_nr))));
+            }
+        }
+    }
+    let mut data_cols = vec![DataCol::new(new_column, distinct_method.output.to_owned())];
+    for ((output, _), new_aligned_col) in aligned_cols.iter().zip(new_aligned_cols) {
+        data_cols.push(DataCol::new(new_aligned_col, output.to_string()));
+    }
+    Ok(data_cols)
+}
+pub fn perform_to_richtext(to_richtext_method: &ToRichtextMethod, source: &SheetSource, col_nr_to_cols_expanded: &HashMap<usize, DataCol>, existing_header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    let header_number = find_header_number(&to_richtext_method.input, col_nr_to_cols_expanded, existing_header_to_col_nr)?;
+    let col = &col_nr_to_cols_expanded.get(&header_number).unwrap();
+    let mut new_column = vec![];
+    for (row_nr, values) in col.col.iter().enumerate() {
+        let mut new_values = vec![];
+        for value in values.iter() {
+            let richtext = to_richtext_method.to_richtext(value);
+            if !richtext.removed.is_empty() {
+                println!("to_richtext '{}': removed {:?}, which DSP doesn't accept, in row {}.", to_richtext_method.output, richtext.removed, source.excel_row(row_nr));
+            }
+            new_values.push(richtext.text);
+        }
+        new_column.push(new_values);
+    }
+    Ok(DataCol::new(new_column, to_richtext_method.output.to_owned()))
+}
+pub fn perform_to_uri(to_uri_method: &ToUriMethod, source: &SheetSource, col_nr_to_cols_expanded: &HashMap<usize, DataCol>, existing_header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    let header_number = find_header_number(&to_uri_method.input, col_nr_to_cols_expanded, existing_header_to_col_nr)?;
+    let col = &col_nr_to_cols_expanded.get(&header_number).unwrap();
+    let mut new_column = vec![];
+    for (row_nr, values) in col.col.iter().enumerate() {
+        let mut new_values = vec![];
+        for value in values.iter() {
+            match to_uri_method.to_uri(value) {
+                Ok(uri) => { new_values.push(uri) }
+                Err(message) => {
+                    return Err(HCLDataError::InputError(format!("to_uri '{}': {}. Problem happens in row {}.", to_uri_method.output, message, source.excel_row(row_nr))));
+                }
+            }
+        }
+        new_column.push(new_values);
+    }
+    Ok(DataCol::new(new_column, to_uri_method.output.to_owned()))
+}
+pub fn perform_slugify(slugify_method: &SlugifyMethod, col_nr_to_cols_expanded: &HashMap<usize, DataCol>, existing_header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    let header_number = find_header_number(&slugify_method.input, col_nr_to_cols_expanded, existing_header_to_col_nr)?;
+    let col = &col_nr_to_cols_expanded.get(&header_number).unwrap();
+    let mut used: HashSet<String> = HashSet::new();
+    let mut suffixed = 0usize;
+    let mut new_column = vec![];
+    for values in col.col.iter() {
+        let mut new_values = vec![];
+        for value in values.iter() {
+            let mut slug = slugify_method.slugify(value);
+            if slugify_method.deduplicate && !slug.is_empty() {
+                // the first slug is kept, the following get the next free number
+                if used.contains(&slug) {
+                    let mut number = 2usize;
+                    while used.contains(&format!("{}{}{}", slug, slugify_method.separator, number)) {
+                        number += 1;
+                    }
+                    slug = format!("{}{}{}", slug, slugify_method.separator, number);
+                    suffixed += 1;
+                }
+                used.insert(slug.to_owned());
+            }
+            new_values.push(slug);
+        }
+        new_column.push(new_values);
+    }
+    if suffixed > 0 {
+        println!("Slugify '{}': added a numeric suffix to {} values that already occurred.", slugify_method.output, suffixed);
+    }
+    Ok(DataCol::new(new_column, slugify_method.output.to_owned()))
+}
+pub fn perform_to_number(to_number_method: &ToNumberMethod, source: &SheetSource, col_nr_to_cols_expanded: &HashMap<usize, DataCol>, existing_header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    let header_number = find_header_number(&to_number_method.input, col_nr_to_cols_expanded, existing_header_to_col_nr)?;
+    let col = &col_nr_to_cols_expanded.get(&header_number).unwrap();
+    let mut new_column = vec![];
+    for (row_nr, values) in col.col.iter().enumerate() {
+        let mut new_values = vec![];
+        for value in values.iter() {
+            if value.trim().is_empty() {
+                new_values.push(value.to_owned());
+                continue;
+            }
+            match to_number_method.to_number(value) {
+                Ok(number) => { new_values.push(number) }
+                Err(HCLDataError::InputError(message)) => {
+                    return Err(HCLDataError::InputError(format!("{} Problem happens in row {}.", message, source.excel_row(row_nr))));
+                }
+                Err(error) => { return Err(error) }
+            }
+        }
+        new_column.push(new_values);
+    }
+    Ok(DataCol::new(new_column, to_number_method.output.to_owned()))
+}
+pub fn perform_to_boolean(to_boolean_method: &ToBooleanMethod, source: &SheetSource, col_nr_to_cols_expanded: &HashMap<usize, DataCol>, existing_header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    let header_number = find_header_number(&to_boolean_method.input, col_nr_to_cols_expanded, existing_header_to_col_nr)?;
+    let col = &col_nr_to_cols_expanded.get(&header_number).unwrap();
+    let mut new_column = vec![];
+    for (row_nr, values) in col.col.iter().enumerate() {
+        let mut new_values = vec![];
+        for value in values.iter() {
+            match to_boolean_method.to_boolean(value) {
+                Some(boolean) => { new_values.push(boolean) }
+                // empty values stay empty if they are not one of the words
+                None if value.trim().is_empty() => { new_values.push(value.to_owned()) }
+                None => {
+                    return Err(HCLDataError::InputError(format!("to_boolean '{}': '{}' is neither truthy {:?} nor falsy {:?}. Problem happens in row {}.", to_boolean_method.output, value, to_boolean_method.truthy, to_boolean_method.falsy, source.excel_row(row_nr))));
+                }
+            }
+        }
+        new_column.push(new_values);
+    }
+    Ok(DataCol::new(new_column, to_boolean_method.output.to_owned()))
+}
+pub fn perform_to_color(to_color_method: &ToColorMethod, source: &SheetSource, col_nr_to_cols_expanded: &HashMap<usize, DataCol>, existing_header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    let header_number = find_header_number(&to_color_method.input, col_nr_to_cols_expanded, existing_header_to_col_nr)?;
+    let col = &col_nr_to_cols_expanded.get(&header_number).unwrap();
+    let mut new_column = vec![];
+    for (row_nr, values) in col.col.iter().enumerate() {
+        let mut new_values = vec![];
+        for value in values.iter() {
+            if value.trim().is_empty() {
+                new_values.push(value.to_owned());
+                continue;
+            }
+            match to_color_method.to_color(value) {
+                Some(color) => { new_values.push(color) }
+                None => {
+                    return Err(HCLDataError::InputError(format!("to_color '{}': cannot read '{}' as color. Problem happens in row {}.", to_color_method.output, value, source.excel_row(row_nr))));
+                }
+            }
+        }
+        new_column.push(new_values);
+    }
+    Ok(DataCol::new(new_column, to_color_method.output.to_owned()))
+}
+pub fn perform_to_time(to_time_method: &ToTimeMethod, source: &SheetSource, col_nr_to_cols_expanded: &HashMap<usize, DataCol>, existing_header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    let header_number = find_header_number(&to_time_method.input, col_nr_to_cols_expanded, existing_header_to_col_nr)?;
+    let col = &col_nr_to_cols_expanded.get(&header_number).unwrap();
+    let mut new_column = vec![];
+    for (row_nr, values) in col.col.iter().enumerate() {
+        let mut new_values = vec![];
+        for value in values.iter() {
+            if value.trim().is_empty() {
+                new_values.push(value.to_owned());
+                continue;
+            }
+            match to_time_method.to_time(value) {
+                Ok(time) => { new_values.push(time) }
+                Err(HCLDataError::InputError(message)) => {
+                    return Err(HCLDataError::InputError(format!("{} Problem happens in row {}.", message, source.excel_row(row_nr))));
+                }
+                Err(error) => { return Err(error) }
+            }
+        }
+        new_column.push(new_values);
+    }
+    Ok(DataCol::new(new_column, to_time_method.output.to_owned()))
+}
+pub fn perform_to_geoname(to_geoname_method: &ToGeonameMethod, source: &SheetSource, col_nr_to_cols_expanded: &HashMap<usize, DataCol>, existing_header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    let header_number = find_header_number(&to_geoname_method.input, col_nr_to_cols_expanded, existing_header_to_col_nr)?;
+    let col = &col_nr_to_cols_expanded.get(&header_number).unwrap();
+    let country_col = match &to_geoname_method.country {
+        None => { None }
+        Some(country) => {
+            let country_number = find_header_number(country, col_nr_to_cols_expanded, existing_header_to_col_nr)?;
+            Some(&col_nr_to_cols_expanded.get(&country_number).unwrap().col)
+        }
+    };
+    // values that are already geoname-ids are kept
+    let names: HashSet<String> = col.col.iter().flatten()
+        .filter(|value| !value.trim().is_empty() && value.trim().parse::<usize>().is_err())
+        .map(|value| value.trim().to_lowercase())
+        .collect();
+    let name_to_candidates = to_geoname_method.candidates(&names)?;
+    let mut new_column = vec![];
+    let mut unresolved = 0usize;
+    for (row_nr, values) in col.col.iter().enumerate() {
+        let countries = country_col.map(|country_col| &country_col[row_nr]);
+        let mut new_values = vec![];
+        for (pos, value) in values.iter().enumerate() {
+            let value = value.trim();
+            if value.is_empty() || value.parse::<usize>().is_ok() {
+                new_values.push(value.to_string());
+                continue;
+            }
+            // a single country is used for all values of the row
+            let country = countries.and_then(|countries| if countries.len() == 1 { countries.first() } else { countries.get(pos) });
+            let candidates = name_to_candidates.get(&value.to_lowercase()).map(|candidates| candidates.as_slice()).unwrap_or(&[]);
+            match to_geoname_method.resolve(candidates, country.map(|country| country.as_str())) {
+                Ok(candidate) => { new_values.push(candidate.id.to_owned()) }
+                Err(remaining) => {
+                    unresolved += 1;
+                    if remaining.is_empty() {
+                        println!("to_geoname '{}': '{}' (country {:?}) not found in row {}, candidates of other countries: {:?}.", to_geoname_method.output, value, country, source.excel_row(row_nr), candidates.iter().map(|candidate| format!("{} ({})", candidate.id, candidate.country)).collect::<Vec<String>>());
+                    } else {
+                        println!("to_geoname '{}': '{}' is ambiguous in row {}, candidates: {:?}.", to_geoname_method.output, value, source.excel_row(row_nr), remaining.iter().map(|candidate| format!("{} ({}, {}, {}, population {})", candidate.id, candidate.name, candidate.country, candidate.feature_code, candidate.population)).collect::<Vec<String>>());
+                    }
+                    new_values.push("".to_string());
+                }
+            }
+        }
+        new_column.push(new_values);
+    }
+    if unresolved > 0 && to_geoname_method.fail_on_unresolved {
+        return Err(HCLDataError::InputError(format!("to_geoname '{}': {} values are unresolved or ambiguous (see above).", to_geoname_method.output, unresolved)));
+    }
+    Ok(DataCol::new(new_column, to_geoname_method.output.to_owned()))
+}
+pub fn perform_map(map_method: &MapMethod, source: &SheetSource, col_nr_to_cols_expanded: &HashMap<usize, DataCol>, existing_header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    let header_number = find_header_number(&map_method.input, col_nr_to_cols_expanded, existing_header_to_col_nr)?;
+    let col = &col_nr_to_cols_expanded.get(&header_number).unwrap();
+    let lookup_table = map_method.lookup_table()?;
+    let mut new_column = vec![];
+    for (row_nr, values) in col.col.iter().enumerate() {
+        let mut new_values = vec![];
+        for value in values.iter() {
+            // empty values stay empty
+            if value.trim().is_empty() {
+                new_values.push(value.to_owned());
+                continue;
+            }
+            let new_value = match lookup_table.get(&map_method.lookup_key(value)) {
+                Some(mapped) => { mapped.to_owned() }
+                None => {
+                    if map_method.fail_on_unmapped {
+                        return Err(HCLDataError::InputError(format!("map '{}': value '{}' in row {} has no entry in the table.", map_method.output, value, source.excel_row(row_nr))));
+                    }
+                    match &map_method.default {
+                        None => { value.to_owned() }
+                        Some(default) => { default.to_owned() }
+                    }
+                }
+            };
+            new_values.push(new_value);
+        }
+        new_column.push(new_values);
+    }
+    Ok(DataCol::new(new_column, map_method.output.to_owned()))
+}
+pub fn perform_to_date(to_date_method: &ToDateMethod, source: &SheetSource, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
     let header_number = find_header_number(&to_date_method.input, col_nr_to_cols, header_to_col_nr)?;
     let col = &col_nr_to_cols.get(&header_number).unwrap();
-    let new_col = _to_date(col, &to_date_method.date_patterns, &to_date_method.date_type, separator)?;
+    let mut new_col = vec![];
+    for (row_nr, values) in col.col.iter().enumerate() {
+        new_col.push(_to_date(values, &to_date_method.date_patterns, &to_date_method.date_type)
+            .map_err(|error| HCLDataError::InputError(format!("to_date '{}': {:?}. Problem happens in row {}.", to_date_method.output, error, source.excel_row(row_nr))))?);
+    }
     Ok(DataCol::new(new_col, to_date_method.output.to_owned()))
-     */
-    todo!()
 }
 
-fn _to_date(data_col: &&DataCol, date_patterns: &Vec<DatePattern>, date_type: &DateType, separator: &String) -> Result<Vec<String>, HCLDataError> {
-    /*
-    let mut new_col = vec![];
-    for value in data_col.col.iter() {
-        if value.is_empty() {
-            new_col.push("".to_string());
-        } else {
-            let mut dates = vec![];
-            for val in value.split(separator).into_iter() {
-                let date_period = DatePeriodWrapper(val.to_owned()).to_date_period(date_patterns, date_type)?.to_date_period_string();
-                dates.push(date_period);
-            }
-            let date_periods = dates.join(separator);
-            new_col.push(date_periods);
+fn _to_date(values: &[String], date_patterns: &Vec<DatePattern>, date_type: &DateType) -> Result<Vec<String>, HCLDataError> {
+    // every value of a multi-value cell is a date on its own, empty values stay empty
+    let mut dates = vec![];
+    for value in values.iter() {
+        if value.trim().is_empty() {
+            dates.push(value.to_owned());
+            continue;
         }
+        dates.push(DatePeriodWrapper(value.trim().to_owned()).to_date_period(date_patterns, date_type)?.to_date_period_string());
     }
-    Ok(new_col)
-     */
-    todo!()
+    Ok(dates)
 }
 
 pub fn perform_separate(separate_method: &SeparateMethod, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<Vec<DataCol>, HCLDataError> {
@@ -182,6 +523,139 @@ pub fn perform_separate(separate_method: &SeparateMethod, col_nr_to_cols: &HashM
     _separate(&col.col, &separate_method.separator, &separate_method.outputs)
 }
 
+pub fn perform_split(split_method: &SplitMethod, source: &SheetSource, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<Vec<DataCol>, HCLDataError> {
+    let header_number = find_header_number(&split_method.input, col_nr_to_cols, header_to_col_nr)?;
+    let col = &col_nr_to_cols.get(&header_number).unwrap();
+    let mut aligned_cols: Vec<(&String, &DataCol)> = vec![];
+    for (header, output) in split_method.aligned.iter() {
+        let number = find_header_number(&HeaderValue::Name(header.to_owned()), col_nr_to_cols, header_to_col_nr)?;
+        aligned_cols.push((output, col_nr_to_cols.get(&number).unwrap()));
+    }
+    let mut new_col: Vec<Vec<String>> = vec![];
+    let mut new_aligned_cols: Vec<Vec<Vec<String>>> = aligned_cols.iter().map(|_| vec![]).collect();
+    for (row_nr, values) in col.col.iter().enumerate() {
+        let mut new_values: Vec<String> = vec![];
+        let mut new_aligned_values: Vec<Vec<String>> = aligned_cols.iter().map(|_| vec![]).collect();
+        for (pos, value) in values.iter().enumerate() {
+            let parts = split_method.split(value);
+            for (nr, (header, aligned_col)) in aligned_cols.iter().enumerate() {
+                // a supplement-value belongs to the value at the same position, a single supplement-value to all values
+                let aligned_values = &aligned_col.col[row_nr];
+                let aligned_value = if aligned_values.len() == values.len() { &aligned_values[pos] } else if aligned_values.len() == 1 { &aligned_values[0] } else {
+                    return Err(HCLDataError::InputError(format!("split '{}': '{}' has {} values, but '{:?}' has {} values in row {}.", split_method.output, header, aligned_values.len(), split_method.input, values.len(), source.excel_row(row_nr))));
+                };
+                let aligned_parts = split_method.split(aligned_value);
+                if aligned_parts.len() == parts.len() {
+                    new_aligned_values[nr].extend(aligned_parts);
+                } else if aligned_parts.len() == 1 {
+                    new_aligned_values[nr].extend(parts.iter().map(|_| aligned_parts[0].to_owned()));
+                } else {
+                    return Err(HCLDataError::InputError(format!("split '{}': value '{}' of '{}' splits into {} parts, but value '{}' into {} parts in row {}.", split_method.output, aligned_value, header, aligned_parts.len(), value, parts.len(), source.excel_row(row_nr))));
+                }
+            }
+            new_values.extend(parts);
+        }
+        // empty parts are removed together with their supplement-values
+        let keep: Vec<bool> = new_values.iter().map(|value| !value.is_empty()).collect();
+        if keep.iter().all(|keep| !keep) {
+            new_col.push(vec!["".to_string()]);
+            new_aligned_cols.iter_mut().for_each(|aligned_col| aligned_col.push(vec!["".to_string()]));
+            continue;
+        }
+        new_col.push(new_values.into_iter().zip(keep.iter()).filter(|(_, keep)| **keep).map(|(value, _)| value).collect());
+        for (nr, aligned_values) in new_aligned_values.into_iter().enumerate() {
+            new_aligned_cols[nr].push(aligned_values.into_iter().zip(keep.iter()).filter(|(_, keep)| **keep).map(|(value, _)| value).collect());
+        }
+    }
+    let mut data_cols = vec![DataCol::new(new_col, split_method.output.to_owned())];
+    for ((output, _), new_aligned_col) in aligned_cols.iter().zip(new_aligned_cols) {
+        data_cols.push(DataCol::new(new_aligned_col, output.to_string()));
+    }
+    Ok(data_cols)
+}
+
+pub fn perform_extract(extract_method: &ExtractMethod, source: &SheetSource, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<Vec<DataCol>, HCLDataError> {
+    let header_number = find_header_number(&extract_method.input, col_nr_to_cols, header_to_col_nr)?;
+    let col = &col_nr_to_cols.get(&header_number).unwrap();
+    let mut new_cols: Vec<Vec<Vec<String>>> = extract_method.outputs.iter().map(|_| vec![]).collect();
+    let mut unmatched = 0usize;
+    for (row_nr, values) in col.col.iter().enumerate() {
+        // every value of a multi-value cell is matched on its own
+        let mut new_values: Vec<Vec<String>> = extract_method.outputs.iter().map(|_| vec![]).collect();
+        for value in values.iter() {
+            if value.trim().is_empty() {
+                new_values.iter_mut().for_each(|new_values| new_values.push("".to_string()));
+                continue;
+            }
+            match extract_method.extract(value) {
+                Some(parts) => {
+                    for (nr, part) in parts.into_iter().enumerate() {
+                        new_values[nr].push(part);
+                    }
+                }
+                None => {
+                    unmatched += 1;
+                    match extract_method.unmatched {
+                        Unmatched::Empty => {
+                            new_values.iter_mut().for_each(|new_values| new_values.push("".to_string()));
+                        }
+                        Unmatched::Keep => {
+                            new_values[0].push(value.to_owned());
+                            new_values.iter_mut().skip(1).for_each(|new_values| new_values.push("".to_string()));
+                        }
+                        Unmatched::Error => {
+                            return Err(HCLDataError::InputError(format!("extract: regex '{}' doesn't match '{}'. Problem happens in row {}.", extract_method.regex.as_str(), value, source.excel_row(row_nr))));
+                        }
+                    }
+                }
+            }
+        }
+        for (nr, new_values) in new_values.into_iter().enumerate() {
+            new_cols[nr].push(new_values);
+        }
+    }
+    if unmatched > 0 {
+        println!("extract '{:?}': regex '{}' didn't match {} values.", extract_method.input, extract_method.regex.as_str(), unmatched);
+    }
+    Ok(new_cols.into_iter().zip(extract_method.outputs.iter()).map(|(new_col, output)| DataCol::new(new_col, output.to_owned())).collect())
+}
+
+pub fn perform_script(script_method: &ScriptMethod, source: &SheetSource, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    let mut cols: Vec<&DataCol> = vec![];
+    for input in script_method.input.iter() {
+        let header_number = find_header_number(input, col_nr_to_cols, header_to_col_nr)?;
+        cols.push(col_nr_to_cols.get(&header_number).unwrap());
+    }
+    let runner = script_method.runner();
+    let mut new_data_col = vec![];
+    for row_nr in 0..cols[0].col.len() {
+        let row: Vec<&Vec<String>> = cols.iter().map(|col| &col.col[row_nr]).collect();
+        let mut new_values = vec![];
+        match script_method.per {
+            ScriptPer::Value => {
+                for value in row[0].iter() {
+                    match runner.run(Some(value), &row) {
+                        Ok(values) => { new_values.extend(values) }
+                        Err(message) => {
+                            return Err(HCLDataError::InputError(format!("script '{}': {} for value '{}'. Problem happens in row {}.", script_method.output, message, value, source.excel_row(row_nr))));
+                        }
+                    }
+                }
+            }
+            ScriptPer::Row => {
+                match runner.run(None, &row) {
+                    Ok(values) => { new_values.extend(values) }
+                    Err(message) => {
+                        return Err(HCLDataError::InputError(format!("script '{}': {} for the values {:?}. Problem happens in row {}.", script_method.output, message, row, source.excel_row(row_nr))));
+                    }
+                }
+            }
+        }
+        new_data_col.push(new_values);
+    }
+    Ok(DataCol::new(new_data_col, script_method.output.to_owned()))
+}
+
 fn _separate(col: &Vec<Vec<String>>, separator: &String, outputs: &Vec<String>) -> Result<Vec<DataCol>, HCLDataError>{
     let mut new_cols: Vec<Vec<Vec<String>>> = vec![];
     for values in col.iter() {
@@ -237,158 +711,185 @@ fn same_length(sep_values: &Vec<Vec<String>>) -> Result<(), HCLDataError> {
 }
 
 pub fn perform_alter(alter_method: &AlterMethod, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
-    /*
     let header_number = find_header_number(&alter_method.input, col_nr_to_cols, header_to_col_nr)?;
     let data_col = &col_nr_to_cols.get(&header_number).unwrap();
-    let mut new_col = vec![];
-    for value in data_col.col.iter() {
-        let mut new_value = "".to_string();
-        if alter_method.prefix.is_some() {
-            new_value += alter_method.prefix.as_ref().unwrap().as_str();
-        }
-        new_value += value.as_str();
-        if alter_method.suffix.is_some() {
-            new_value += alter_method.suffix.as_ref().unwrap().as_str();
-        }
-        new_col.push(new_value);
-    }
+    let new_col = data_col.col.iter()
+        .map(|values| values.iter()
+            // empty values stay empty
+            .map(|value| if value.trim().is_empty() { value.to_owned() } else { format!("{}{}{}", alter_method.prefix.as_deref().unwrap_or(""), value, alter_method.suffix.as_deref().unwrap_or("")) })
+            .collect())
+        .collect();
     Ok(DataCol::new(new_col, alter_method.output.to_owned()))
-     */
-    todo!()
 }
-pub fn perform_create(create_method: &CreateMethod, length: usize) -> DataCol {
-    match create_method {
+pub fn perform_create(create_method: &CreateMethod, rows: &[usize], length: usize, source: &SheetSource, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    // values are only created for 'rows', the other rows stay empty
+    let (output, values) = match create_method {
         CreateMethod::IntegerCreateMethod(int_create) => {
-            perform_int_create(int_create, length)
-
+            (&int_create.output, perform_int_create(int_create, rows, source, col_nr_to_cols, header_to_col_nr)?)
         }
         CreateMethod::PermissionsCreateMethod(permissions_create) => {
-            perform_permissions_create(permissions_create, length)
+            (&permissions_create.output, rows.iter().map(|_| permissions_create.permissions.to_string()).collect())
+        }
+        CreateMethod::HashCreateMethod(hash_create) => {
+            (&hash_create.output, perform_hash_create(hash_create, rows, source, col_nr_to_cols, header_to_col_nr)?)
+        }
+        CreateMethod::UuidCreateMethod(uuid_create) => {
+            (&uuid_create.output, perform_uuid_create(uuid_create, rows, col_nr_to_cols, header_to_col_nr)?)
+        }
+        CreateMethod::ConstantCreateMethod(constant_create) => {
+            (&constant_create.output, rows.iter().map(|_| constant_create.value.to_owned()).collect())
+        }
+        CreateMethod::RowNumberCreateMethod(row_number_create) => {
+            (&row_number_create.output, rows.iter().map(|row_nr| row_number_create.row_number(source.excel_row(*row_nr))).collect())
+        }
+        CreateMethod::SourceCreateMethod(source_create) => {
+            (&source_create.output, rows.iter().map(|_| source_create.source(source)).collect())
+        }
+    };
+    let mut new_data_col = vec![vec!["".to_string()]; length];
+    for (row_nr, value) in rows.iter().zip(values) {
+        new_data_col[*row_nr] = vec![value];
+    }
+    Ok(DataCol::new(new_data_col, output.to_owned()))
+}
 
+fn perform_hash_create(hash_create: &HashCreate, rows: &[usize], source: &SheetSource, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<Vec<String>, HCLDataError> {
+    let mut cols: Vec<&DataCol> = vec![];
+    for input in hash_create.input.iter() {
+        let header_number = find_header_number(input, col_nr_to_cols, header_to_col_nr)?;
+        cols.push(col_nr_to_cols.get(&header_number).unwrap());
+    }
+    let mut new_values = vec![];
+    for row_nr in rows.iter() {
+        let values: Vec<&Vec<String>> = cols.iter().map(|col| &col.col[*row_nr]).collect();
+        if values.iter().all(|values| values.iter().all(|value| value.trim().is_empty())) {
+            return Err(HCLDataError::InputError(format!("hash-create '{}': all inputs are empty in row {}.", hash_create.output, source.excel_row(*row_nr))));
         }
+        new_values.push(hash_create.hash(&values));
     }
+    Ok(new_values)
 }
 
-fn perform_permissions_create(permissions_create: &PermissionsCreate, length: usize) -> DataCol {
-    /*
-    let mut new_data_col = vec![];
-    for _ in 0..length {
-        new_data_col.push(permissions_create.permissions.to_string())
+fn perform_uuid_create(uuid_create: &UuidCreate, rows: &[usize], col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<Vec<String>, HCLDataError> {
+    let mut cols: Vec<&DataCol> = vec![];
+    for input in uuid_create.input.iter() {
+        let header_number = find_header_number(input, col_nr_to_cols, header_to_col_nr)?;
+        cols.push(col_nr_to_cols.get(&header_number).unwrap());
     }
-    DataCol::new(new_data_col, permissions_create.output.to_owned())
-     */
-    todo!()
+    Ok(rows.iter().map(|row_nr| {
+        let values: Vec<&Vec<String>> = cols.iter().map(|col| &col.col[*row_nr]).collect();
+        uuid_create.uuid(&values)
+    }).collect())
 }
-fn perform_int_create(int_create: &IntegerCreate, length: usize) -> DataCol {
-    todo!()
-    /*
-let mut new_data_col = vec![];
-let mut curr = int_create.start;
-match int_create.step.step_method {
-    StepMethod::Plus => {
-        for _ in 0..length {
-            curr = curr + int_create.step.step_rate;
-            let mut value = "".to_string();
-            if int_create.prefix.is_some() {
-                value.push_str(int_create.prefix.as_ref().unwrap())
-            }
-            value.push_str(curr.to_string().as_str());
-            if int_create.suffix.is_some() {
-                value.push_str(int_create.prefix.as_ref().unwrap())
-            }
-            new_data_col.push(value);
+
+fn perform_int_create(int_create: &IntegerCreate, rows: &[usize], source: &SheetSource, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<Vec<String>, HCLDataError> {
+    let restart_col = match &int_create.restart_on {
+        None => { None }
+        Some(restart_on) => {
+            let header_number = find_header_number(restart_on, col_nr_to_cols, header_to_col_nr)?;
+            Some(col_nr_to_cols.get(&header_number).unwrap())
         }
-    }
-    _ => {todo!("not added")}
-    StepMethod::Multiplication => {
-        for _ in 0..length {
-            curr = curr * int_create.step.step_rate;
-            let mut value = "".to_string();
-            if int_create.prefix.is_some() {
-                value.push_str(int_create.prefix.as_ref().unwrap())
-            }
-            value.push_str(curr.to_string().as_str());
-            if int_create.suffix.is_some() {
-                value.push_str(int_create.prefix.as_ref().unwrap())
+    };
+    // the last number of every group, without restart_on all rows are one group
+    let mut group_to_curr: HashMap<String, usize> = HashMap::new();
+    let mut new_values = vec![];
+    for row_nr in rows.iter().copied() {
+        let group = restart_col.map(|col| col.col[row_nr].iter().map(|value| value.trim()).collect::<Vec<&str>>().join("\u{1e}")).unwrap_or_default();
+        let curr = match group_to_curr.get(&group) {
+            None => { int_create.start }
+            Some(prev) => {
+                match int_create.step.next(*prev) {
+                    Some(curr) => { curr }
+                    None => {
+                        return Err(HCLDataError::InputError(format!("integer-create '{}': step '{:?}' after {} leaves the range of positive numbers in row {}.", int_create.output, int_create.step, prev, source.excel_row(row_nr))));
+                    }
+                }
             }
-            new_data_col.push(value);
-        }
+        };
+        group_to_curr.insert(group, curr);
+        new_values.push(int_create.value(curr));
     }
-
-}
-DataCol::new(new_data_col, int_create.output.to_owned())
-     */
+    Ok(new_values)
 }
 
-pub fn perform_combine(combine_method: &CombineMethod, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
-    let first_number = find_header_number(combine_method.input.get(0).unwrap(),col_nr_to_cols, header_to_col_nr)?;
-    let second_number = find_header_number(combine_method.input.get(1).unwrap(), col_nr_to_cols, header_to_col_nr)?;
-    let first_col = &col_nr_to_cols.get(&first_number).unwrap();
-    let second_col = &col_nr_to_cols.get(&second_number).unwrap();
-    let new_col = _combine(first_col, second_col, &combine_method)?;
+pub fn perform_combine(combine_method: &CombineMethod, source: &SheetSource, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    let mut cols: Vec<&DataCol> = vec![];
+    for input in combine_method.input.iter() {
+        let number = find_header_number(input, col_nr_to_cols, header_to_col_nr)?;
+        cols.push(col_nr_to_cols.get(&number).unwrap());
+    }
+    let new_col = _combine(&cols, combine_method, source)?;
     Ok(DataCol::new(new_col, combine_method.output.to_owned()))
 }
 
-fn _combine(first_col: &&DataCol, second_col: &&DataCol, combine_method: &CombineMethod) -> Result<Vec<Vec<String>>, HCLDataError>{
+fn _combine(cols: &Vec<&DataCol>, combine_method: &CombineMethod, source: &SheetSource) -> Result<Vec<Vec<String>>, HCLDataError>{
     let mut new_col:Vec<Vec<String>> = vec![];
-    // it is assumed that first col and second col have the same length
-    for i in 0..first_col.col.len() {
-        let field_first = first_col.col.get(i).unwrap();
-        let field_second = second_col.col.get(i).unwrap();
-        // the number of values in a field must match in both columns; it is assumed that the relation is bijective
-        if field_first.len() != field_second.len() {
-            return Err(MethodError(Combine(format!("Fields have different length: First field: '{:?}', second field: '{:?}'. Problem happens in '{:?}'", field_first, field_second, combine_method))));
-        }
+    // it is assumed that all cols have the same length
+    for i in 0..cols[0].col.len() {
+        let fields: Vec<&Vec<String>> = cols.iter().map(|col| col.col.get(i).unwrap()).collect();
         let mut new_fields = vec![];
-        for j in 0..field_first.len() {
-            let mut new_combined_value = "".to_string();
-            if combine_method.prefix.is_some() {
-                new_combined_value += &combine_method.prefix.as_ref().unwrap().as_str();
-            }
-            new_combined_value += field_first[j].as_str();
-            if combine_method.middle.is_some() {
-                new_combined_value += &combine_method.middle.as_ref().unwrap().as_str();
-            }
-            new_combined_value += field_second[j].as_str();
-            if combine_method.suffix.is_some() {
-                new_combined_value += &combine_method.suffix.as_ref().unwrap().as_str();
-            }
-            new_fields.push(new_combined_value);
+        for values in _combinations(&fields, &combine_method.multiple).map_err(|message| MethodError(Combine(format!("{} Problem happens in row {} of '{:?}'", message, source.excel_row(i), combine_method))))? {
+            new_fields.push(combine_method.combine_values(&values));
         }
         new_col.push(new_fields);
     }
     Ok(new_col)
 }
 
+fn _combinations<'a>(fields: &Vec<&'a Vec<String>>, multiple: &MultipleValues) -> Result<Vec<Vec<&'a String>>, String> {
+    // one entry per value of the new field, holding one value of every input
+    match multiple {
+        MultipleValues::Pairwise => {
+            // fields with a single value are combined with every value of the other fields
+            let count = fields.iter().map(|field| field.len()).max().unwrap_or(0);
+            if fields.iter().any(|field| field.len() != 1 && field.len() != count) {
+                return Err(format!("Fields have different number of values: '{:?}'.", fields));
+            }
+            Ok((0..count).map(|j| fields.iter().map(|field| if field.len() == 1 { &field[0] } else { &field[j] }).collect()).collect())
+        }
+        MultipleValues::Product => {
+            let mut combinations: Vec<Vec<&String>> = vec![vec![]];
+            for field in fields.iter() {
+                combinations = combinations.iter()
+                    .flat_map(|combination| field.iter().map(move |value| {
+                        let mut combination = combination.to_owned();
+                        combination.push(value);
+                        combination
+                    }))
+                    .collect();
+            }
+            Ok(combinations)
+        }
+    }
+}
+
 pub fn perform_upper(upper_method: &UpperMethod, col_nr_to_cols: &HashMap<usize, DataCol>, headers_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
-    todo!()
-    /*
     let header_number = find_header_number(&upper_method.input, col_nr_to_cols, headers_to_col_nr)?;
     let data_col = &col_nr_to_cols.get(&header_number).unwrap();
     let col = _upper(data_col);
     Ok(DataCol::new(col, upper_method.output.to_owned()))
-
-     */
 }
 
-fn _upper(data_col: &DataCol) -> Vec<String> {
-    //data_col.col.iter().map(|value| value.to_lowercase()).collect()
-    todo!("add separator");
+fn _upper(data_col: &DataCol) -> Vec<Vec<String>> {
+    data_col.col.iter().map(|values| values.iter().map(|value| value.to_uppercase()).collect()).collect()
 }
 
 pub fn perform_lower(lower_method: &LowerMethod, col_nr_to_cols: &HashMap<usize, DataCol>, headers_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
-    /*
     let header_number = find_header_number(&lower_method.input, col_nr_to_cols, headers_to_col_nr)?;
     let data_col = &col_nr_to_cols.get(&header_number).unwrap();
     let col = _lower(data_col);
     Ok(DataCol::new(col, lower_method.output.to_owned()))
-     */
-    todo!()
 }
 
-fn _lower(data_col: &DataCol) -> Vec<String> {
-    //data_col.col.iter().map(|value| value.to_lowercase()).collect()
-    todo!("add separator");
+fn _lower(data_col: &DataCol) -> Vec<Vec<String>> {
+    data_col.col.iter().map(|values| values.iter().map(|value| value.to_lowercase()).collect()).collect()
+}
+
+pub fn perform_case(case_method: &CaseMethod, col_nr_to_cols: &HashMap<usize, DataCol>, headers_to_col_nr: &HashMap<String, usize>) -> Result<DataCol, HCLDataError> {
+    let header_number = find_header_number(&case_method.input, col_nr_to_cols, headers_to_col_nr)?;
+    let data_col = &col_nr_to_cols.get(&header_number).unwrap();
+    let col = data_col.col.iter().map(|values| values.iter().map(|value| case_method.change_case(value)).collect()).collect();
+    Ok(DataCol::new(col, case_method.output.to_owned()))
 }
 fn _replace(col: &Vec<Vec<String>>, new: &String, old: &String, behavior: &BehaviorType) -> Vec<Vec<String>> {
      match behavior {
@@ -396,7 +897,7 @@ fn _replace(col: &Vec<Vec<String>>, new: &String, old: &String, behavior: &Behav
             //let _ = data_column.column.iter().map(|value| transient_column.add_data(value.replacen(new, old, 1)));
             col.iter()
                 .map(|values|values.into_iter()
-                    .map(|label| label.replacen(new, old, 1))
+                    .map(|label| label.replacen(old, new, 1))
                     .collect::<Vec<_>>()).collect::<Vec<_>>()
         }
 
@@ -410,7 +911,65 @@ fn _replace(col: &Vec<Vec<String>>, new: &String, old: &String, behavior: &Behav
     }
 }
 
-fn find_header_number(input: &HeaderValue, col_nr_to_data_col: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<usize, HCLDataError> {
+fn _replace_regex(col: &Vec<Vec<String>>, regex: &Regex, new: &String, behavior: &BehaviorType, target: &TargetType) -> Vec<Vec<String>> {
+    // every value of a multi-value cell is replaced on its own; target 'whole' only replaces values matched completely
+    col.iter()
+        .map(|values| values.iter()
+            .map(|value| {
+                if let TargetType::Whole = target {
+                    match regex.find(value) {
+                        Some(found) if found.start() == 0 && found.end() == value.len() => {}
+                        _ => { return value.to_owned() }
+                    }
+                }
+                match behavior {
+                    BehaviorType::Lazy => { regex.replacen(value, 1, new.as_str()).to_string() }
+                    BehaviorType::Greedy => { regex.replace_all(value, new.as_str()).to_string() }
+                }
+            })
+            .collect::<Vec<_>>()).collect::<Vec<_>>()
+}
+
+pub fn perform_when<F>(when: &Option<When>, input: Option<&HeaderValue>, length: usize, col_nr_to_cols: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>, method: F) -> Result<DataCol, HCLDataError>
+    where F: FnOnce(&HashMap<usize, DataCol>, &[usize]) -> Result<DataCol, HCLDataError> {
+    // the method only gets the values of the rows that meet the condition, so it cannot fail on the other rows;
+    // these rows take the value of the fallback-column, else pass the input through unchanged;
+    // methods without an input (e.g. create) leave these rows empty
+    let when = match when {
+        None => { return method(col_nr_to_cols, &(0..length).collect::<Vec<usize>>()) }
+        Some(when) => { when }
+    };
+    let condition_number = find_header_number(&when.condition.input, col_nr_to_cols, header_to_col_nr)?;
+    let condition_col = col_nr_to_cols.get(&condition_number).unwrap();
+    let met: Vec<bool> = (0..length).map(|row_nr| when.condition.is_met(&condition_col.col[row_nr])).collect();
+    let met_col_nr_to_cols: HashMap<usize, DataCol> = col_nr_to_cols.iter()
+        .map(|(col_nr, data_col)| {
+            let col = data_col.col.iter().zip(met.iter()).map(|(values, met)| if *met { values.to_owned() } else { vec![] }).collect();
+            (col_nr.to_owned(), DataCol::new(col, data_col.head.to_owned()))
+        })
+        .collect();
+    let rows: Vec<usize> = (0..length).filter(|row_nr| met[*row_nr]).collect();
+    let mut data_col = method(&met_col_nr_to_cols, &rows)?;
+    let otherwise_col = match (&when.fallback, input) {
+        (Some(header), _) | (None, Some(header)) => {
+            let header_number = find_header_number(header, col_nr_to_cols, header_to_col_nr)?;
+            Some(col_nr_to_cols.get(&header_number).unwrap())
+        }
+        (None, None) => { None }
+    };
+    for (row_nr, field) in data_col.col.iter_mut().enumerate() {
+        if met[row_nr] {
+            continue;
+        }
+        *field = match otherwise_col {
+            None => { vec!["".to_string()] }
+            Some(otherwise_col) => { otherwise_col.col[row_nr].to_owned() }
+        };
+    }
+    Ok(data_col)
+}
+
+pub(crate) fn find_header_number(input: &HeaderValue, col_nr_to_data_col: &HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<usize, HCLDataError> {
     match &input {
         HeaderValue::Name(name) => {
             match header_to_col_nr.get(name) {
@@ -430,11 +989,86 @@ fn find_header_number(input: &HeaderValue, col_nr_to_data_col: &HashMap<usize, D
     }
 #[cfg(test)]
 mod test {
+    use std::collections::HashMap;
+    use hcl::block;
     use crate::parse_hcl::header_value::HeaderValue;
+    use crate::parse_hcl::methods_domain::create_method::WrapperCreateMethod;
     use crate::parse_hcl::methods_domain::date_bricks::{DateBricks, DateInfo, DateName};
     use crate::parse_hcl::methods_domain::date_pattern::DatePattern;
     use crate::parse_hcl::methods_domain::date_type::DateType;
+    use crate::parse_hcl::methods_domain::to_alter_method::AlterMethod;
     use crate::parse_hcl::methods_domain::to_date_method::ToDateMethod;
+    use crate::parse_hcl::methods_domain::when::WrapperWhen;
+    use crate::parse_xlsx::domain::data_col::test::columns;
+    use crate::parse_xlsx::domain::intermediate_sheet::SheetSource;
+    use crate::parse_hcl::methods_domain::distinct_method::WrapperDistinctMethod;
+    use crate::parse_hcl::methods_domain::replace_method::WrapperReplaceMethod;
+    use crate::parse_xlsx::domain::manipulations::{perform_alter, perform_create, perform_distinct, perform_replace, perform_to_date, perform_when};
+
+    #[test]
+    fn test_perform_when() {
+        let (col_nr_to_cols, header_to_col_nr) = columns(vec![
+            ("calendar", vec![vec!["julian"], vec!["gregorian"], vec!["julian"]]),
+            ("date", vec![vec!["01.01.1700"], vec!["circa 1750"], vec!["3.2.1701"]]),
+            ("date_gregorian", vec![vec![""], vec!["GREGORIAN:CE:1750:CE:1750"], vec![""]]),
+        ]);
+        let source = SheetSource { rel_path: "letters.xlsx".to_string(), sheet_name: "letters".to_string(), first_row: 2, kept_rows: None };
+        let when = WrapperWhen(block!(when {
+            input = "calendar"
+            equals = "julian"
+            fallback = "date_gregorian"
+        })).to_when().unwrap();
+        let to_date_method = ToDateMethod {
+            output: "hasDate".to_string(),
+            input: HeaderValue::Name("date".to_string()),
+            date_type: DateType::Julian,
+            date_patterns: vec![DatePattern {
+                nr: 1,
+                first_date: None,
+                date: DateBricks {
+                    month_word: Option::from(false),
+                    day: Option::from(DateInfo { nr: 1, name: DateName::Day }),
+                    month: Option::from(DateInfo { nr: 2, name: DateName::Month }),
+                    year: Option::from(DateInfo { nr: 3, name: DateName::Year }),
+                },
+            }],
+            when: Some(when),
+        };
+        // 'circa 1750' isn't a date, but its row doesn't meet the condition, so to_date doesn't fail and the fallback is taken
+        let data_col = perform_when(&to_date_method.when, Some(&to_date_method.input), 3, &col_nr_to_cols, &header_to_col_nr, |col_nr_to_cols, _| {
+            perform_to_date(&to_date_method, &source, col_nr_to_cols, &header_to_col_nr)
+        }).unwrap();
+        assert_eq!(data_col.col, vec![vec!["JULIAN:CE:1700-01-01:CE:1700-01-01"], vec!["GREGORIAN:CE:1750:CE:1750"], vec!["JULIAN:CE:1701-02-03:CE:1701-02-03"]]);
+        // without fallback the input is passed through
+        let alter_method = AlterMethod {
+            prefix: Some("[".to_string()),
+            suffix: Some("]".to_string()),
+            input: HeaderValue::Name("date".to_string()),
+            output: "hasDateRaw".to_string(),
+            when: Some(WrapperWhen(block!(when {
+                input = "calendar"
+                equals = "julian"
+            })).to_when().unwrap()),
+        };
+        let data_col = perform_when(&alter_method.when, Some(&alter_method.input), 3, &col_nr_to_cols, &header_to_col_nr, |col_nr_to_cols, _| {
+            perform_alter(&alter_method, col_nr_to_cols, &header_to_col_nr)
+        }).unwrap();
+        assert_eq!(data_col.col, vec![vec!["[01.01.1700]"], vec!["circa 1750"], vec!["[3.2.1701]"]]);
+        // create has no input: the other rows stay empty and the counter only advances on rows that meet the condition
+        let create_method = WrapperCreateMethod(block!(create "integer" "nr" {
+            start = 1
+            step = "+1"
+            when {
+                input = "calendar"
+                equals = "julian"
+            }
+        })).to_create_method().unwrap();
+        let source = SheetSource { rel_path: "letters.xlsx".to_string(), sheet_name: "letters".to_string(), first_row: 2, kept_rows: None };
+        let data_col = perform_when(create_method.when(), None, 3, &col_nr_to_cols, &header_to_col_nr, |col_nr_to_cols, rows| {
+            perform_create(&create_method, rows, 3, &source, col_nr_to_cols, &header_to_col_nr)
+        }).unwrap();
+        assert_eq!(data_col.col, vec![vec!["1"], vec![""], vec!["2"]]);
+    }
 
     #[test]
     fn test_to_date() {
@@ -465,14 +1099,55 @@ mod test {
                     },
                 },
             ].to_vec(),
+            when: None,
         };
-        todo!()
-        /*
-        let mut data_column  = TransientDataCol::new();
-        data_column.column = vec_1;
-        let result = _to_date(&data_column, &date_method.date_patterns, &date_method.date_type).unwrap();
-        assert_eq!(result.column, ["GREGORIAN:CE:1991:01:01:CE:1991:01:01", "GREGORIAN:CE:0400:02:03:CE:0400:02:03", "GREGORIAN:CE:1991:01:02:CE:1991:01:02"]);
-
-         */
+        let result = super::_to_date(&vec_1, &date_method.date_patterns, &date_method.date_type).unwrap();
+        assert_eq!(result, ["GREGORIAN:CE:1991-01-01:CE:1991-01-01", "GREGORIAN:CE:0400-02-03:CE:0400-02-03", "GREGORIAN:CE:1991-01-02:CE:1991-01-02"]);
+    }
+    #[test]
+    fn test_perform_distinct_aligned() {
+        let (col_nr_to_cols, header_to_col_nr) = columns(vec![
+            ("keywords", vec![vec!["poem", "drama", "Poem", "letter"], vec!["novel"], vec![""]]),
+            ("keywords_comment", vec![vec!["first", "second", "third", "fourth"], vec!["all"], vec![""]]),
+        ]);
+        let distinct_method = WrapperDistinctMethod(block!(distinct "hasKeyword" {
+            input = "keywords"
+            case_insensitive = true
+            sort = true
+            aligned = { "keywords_comment" = "hasKeyword_comment" }
+        })).to_distinct_method().unwrap();
+        let source = SheetSource { rel_path: "letters.xlsx".to_string(), sheet_name: "letters".to_string(), first_row: 2, kept_rows: None };
+        let data_cols = perform_distinct(&distinct_method, &source, &col_nr_to_cols, &header_to_col_nr).unwrap();
+        assert_eq!(data_cols[0].col, vec![vec!["drama", "letter", "poem"], vec!["novel"], vec![""]]);
+        assert_eq!(data_cols[1].head, "hasKeyword_comment");
+        assert_eq!(data_cols[1].col, vec![vec!["second", "fourth", "first"], vec!["all"], vec![""]]);
+    }
+
+    #[test]
+    fn test_perform_replace_regex() {
+        let (col_nr_to_cols, header_to_col_nr) = columns(vec![
+            ("pages", vec![vec!["p. 12", "p. 14-16 and p. 20"], vec!["", "pages 3"]]),
+        ]);
+        let cases = vec![
+            ("greedy", "part", vec![vec!["S. 12", "S. 14-16 and S. 20"], vec!["", "pages 3"]]),
+            // lazy replaces the first match of every value of the cell
+            ("lazy", "part", vec![vec!["S. 12", "S. 14-16 and p. 20"], vec!["", "pages 3"]]),
+            // whole only replaces values that are matched completely
+            ("greedy", "whole", vec![vec!["S. 12", "p. 14-16 and p. 20"], vec!["", "pages 3"]]),
+        ];
+        for (behavior, target, expected) in cases {
+            let replace_method = WrapperReplaceMethod(block!(replace "pages_new" {
+                input = "pages"
+                old = "p\\. (\\d+)"
+                new = "S. $1"
+                condition {
+                    behavior = (behavior)
+                    target = (target)
+                    mode = "regex"
+                }
+            })).to_replace_method().unwrap();
+            let data_col = perform_replace(&replace_method, &col_nr_to_cols, &header_to_col_nr).unwrap();
+            assert_eq!(data_col.col, expected, "behavior '{}', target '{}'", behavior, target);
+        }
     }
 }
@@ -20,3 +20,6 @@ mod data_col;
 mod resource_data;
 mod license;
 mod hashmap_wrapper;
+mod row_filter;
+pub(crate) mod richtext;
+pub(crate) mod uri;
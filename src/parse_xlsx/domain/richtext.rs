@@ -0,0 +1,268 @@
+use std::sync::LazyLock;
+use regex::Regex;
+
+// tags of the standard mapping of DSP; i and b are written as em and strong
+const RICHTEXT_TAGS: [&str; 29] = ["p", "em", "strong", "u", "s", "strike", "del", "sub", "sup", "code", "pre", "blockquote", "cite", "br", "hr", "a", "ol", "ul", "li", "h1", "h2", "h3", "h4", "h5", "h6", "table", "tbody", "tr", "td"];
+const VOID_TAGS: [&str; 2] = ["br", "hr"];
+// removed together with their content
+const DROPPED_TAGS: [&str; 2] = ["script", "style"];
+// the regexes are used for every value, so they are compiled once
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"^<(/?)([a-zA-Z][a-zA-Z0-9]*)((?:\s+[^\s=/>]+(?:\s*=\s*(?:"[^"]*"|'[^']*'|[^\s"'>]+))?)*)\s*/?>"#).unwrap());
+static ATTRIBUTE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"([^\s=/>]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+)))?"#).unwrap());
+static ENTITY: LazyLock<Regex> = LazyLock::new(|| Regex::new("^&(amp|lt|gt|quot|apos|#[0-9]+|#x[0-9a-fA-F]+);").unwrap());
+static HEADING: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(#{1,6})\s+(.*)$").unwrap());
+static UNORDERED: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*[-*+]\s+(.*)$").unwrap());
+static ORDERED: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*\d+[.)]\s+(.*)$").unwrap());
+static INLINE: LazyLock<Vec<(Regex, &str)>> = LazyLock::new(|| {
+    [
+        (r"`([^`]+)`", "<code>$1</code>"),
+        (r#"\[([^\]]+)\]\(([^)\s"]+)\)"#, "<a href=\"$2\">$1</a>"),
+        (r"\*\*(.+?)\*\*", "<strong>$1</strong>"),
+        (r"\b__(.+?)__\b", "<strong>$1</strong>"),
+        (r"\*(.+?)\*", "<em>$1</em>"),
+        (r"\b_([^_]+?)_\b", "<em>$1</em>"),
+        (r"~~(.+?)~~", "<strike>$1</strike>"),
+    ].into_iter().map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), replacement)).collect()
+});
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Start(String, Vec<(String, String)>),
+    End(String),
+    Text(String),
+}
+
+#[derive(Debug, Default)]
+pub struct Richtext {
+    pub text: String,
+    // tags or links that were removed, e.g. "span", "a href 'javascript:...'"
+    pub removed: Vec<String>,
+    // ids of resources the text links to
+    pub links: Vec<String>,
+}
+
+pub(crate) fn sanitize(input: &str) -> Richtext {
+    // returns well-formed xml with whitelisted tags only, other tags are removed and their text is kept
+    let mut richtext = Richtext::default();
+    let mut open: Vec<String> = vec![];
+    let mut dropped: Option<String> = None;
+    for token in tokenize(input) {
+        if let Some(name) = &dropped {
+            if token == Token::End(name.to_owned()) {
+                dropped = None;
+            }
+            continue;
+        }
+        match token {
+            Token::Text(text) => { richtext.text.push_str(&escape_text(&text)) }
+            Token::Start(name, attributes) => {
+                let name = rename(&name);
+                if DROPPED_TAGS.contains(&name.as_str()) {
+                    richtext.removed.push(name.to_owned());
+                    dropped = Some(name);
+                    continue;
+                }
+                if !RICHTEXT_TAGS.contains(&name.as_str()) {
+                    richtext.removed.push(name);
+                    continue;
+                }
+                if VOID_TAGS.contains(&name.as_str()) {
+                    richtext.text.push_str(&format!("<{}/>", name));
+                    continue;
+                }
+                if name == "a" {
+                    let href = attributes.iter().find(|(key, _)| key == "href").map(|(_, value)| value.trim()).unwrap_or("");
+                    match link(href) {
+                        Some(Link::External(href)) => {
+                            richtext.text.push_str(&format!("<a href=\"{}\">", escape_attribute(&href)));
+                        }
+                        Some(Link::Internal(id)) => {
+                            richtext.text.push_str(&format!("<a class=\"salsah-link\" href=\"IRI:{}:IRI\">", escape_attribute(&id)));
+                            richtext.links.push(id);
+                        }
+                        None => {
+                            richtext.removed.push(format!("a href '{}'", href));
+                            continue;
+                        }
+                    }
+                } else {
+                    richtext.text.push_str(&format!("<{}>", name));
+                }
+                open.push(name);
+            }
+            Token::End(name) => {
+                let name = rename(&name);
+                // closing tags of removed or not opened tags are ignored, tags opened in between are closed
+                if let Some(pos) = open.iter().rposition(|open_name| open_name.eq(&name)) {
+                    for open_name in open.drain(pos..).rev() {
+                        richtext.text.push_str(&format!("</{}>", open_name));
+                    }
+                }
+            }
+        }
+    }
+    for open_name in open.drain(..).rev() {
+        richtext.text.push_str(&format!("</{}>", open_name));
+    }
+    richtext
+}
+
+enum Link {
+    External(String),
+    Internal(String),
+}
+
+fn link(href: &str) -> Option<Link> {
+    // links to resources of the same run are written as "resource:<id>"
+    if let Some(id) = href.strip_prefix("resource:") {
+        return if id.trim().is_empty() { None } else { Some(Link::Internal(id.trim().to_string())) };
+    }
+    if let Some(id) = href.strip_prefix("IRI:").and_then(|rest| rest.strip_suffix(":IRI")) {
+        return Some(Link::Internal(id.to_string()));
+    }
+    let lowercase = href.to_lowercase();
+    if lowercase.starts_with("http://") || lowercase.starts_with("https://") || lowercase.starts_with("mailto:") {
+        return Some(Link::External(href.to_string()));
+    }
+    None
+}
+
+fn rename(name: &str) -> String {
+    match name.to_lowercase().as_str() {
+        "i" => { "em".to_string() }
+        "b" => { "strong".to_string() }
+        name => { name.to_string() }
+    }
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut text = String::new();
+    let mut rest = input;
+    while let Some(pos) = rest.find('<') {
+        text.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = match comment.find("-->") {
+                None => { "" }
+                Some(end) => { &comment[end + 3..] }
+            };
+            continue;
+        }
+        match read_tag(rest) {
+            None => {
+                // a '<' that doesn't start a tag is text, e.g. "a < b"
+                text.push('<');
+                rest = &rest[1..];
+            }
+            Some((token, length)) => {
+                if !text.is_empty() {
+                    tokens.push(Token::Text(std::mem::take(&mut text)));
+                }
+                tokens.push(token);
+                rest = &rest[length..];
+            }
+        }
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        tokens.push(Token::Text(text));
+    }
+    tokens
+}
+
+fn read_tag(input: &str) -> Option<(Token, usize)> {
+    // returns the tag and its length in bytes, input starts with '<'
+    let captures = TAG.captures(input)?;
+    let name = captures[2].to_lowercase();
+    let length = captures[0].len();
+    if !captures[1].is_empty() {
+        return Some((Token::End(name), length));
+    }
+    let attributes = ATTRIBUTE.captures_iter(&captures[3])
+        .map(|attribute| {
+            let value = attribute.get(2).or(attribute.get(3)).or(attribute.get(4)).map(|value| value.as_str()).unwrap_or("");
+            (attribute[1].to_lowercase(), value.to_string())
+        })
+        .collect();
+    Some((Token::Start(name, attributes), length))
+}
+
+fn escape_text(input: &str) -> String {
+    // entity references that are valid in xml are kept, '&nbsp;' is written as character reference
+    let mut escaped = String::new();
+    let mut rest = input;
+    while let Some(character) = rest.chars().next() {
+        if character == '&' {
+            if let Some(entity) = ENTITY.find(rest) {
+                escaped.push_str(entity.as_str());
+                rest = &rest[entity.end()..];
+                continue;
+            }
+            if let Some(after) = rest.strip_prefix("&nbsp;") {
+                escaped.push_str("&#160;");
+                rest = after;
+                continue;
+            }
+        }
+        match character {
+            '&' => { escaped.push_str("&amp;") }
+            '<' => { escaped.push_str("&lt;") }
+            '>' => { escaped.push_str("&gt;") }
+            _ => { escaped.push(character) }
+        }
+        rest = &rest[character.len_utf8()..];
+    }
+    escaped
+}
+
+fn escape_attribute(input: &str) -> String {
+    escape_text(input).replace('"', "&quot;")
+}
+
+pub(crate) fn markdown_to_html(input: &str) -> String {
+    // subset of markdown: paragraphs, line breaks, headings, lists, emphasis, code and links
+    let mut blocks: Vec<String> = vec![];
+    let mut paragraph: Vec<String> = vec![];
+    let mut list: Option<(&str, Vec<String>)> = None;
+    for line in input.lines() {
+        let line = line.trim_end();
+        let list_item = if let Some(captures) = UNORDERED.captures(line) { Some(("ul", captures[1].to_string())) } else { ORDERED.captures(line).map(|captures| ("ol", captures[1].to_string())) };
+        if (line.trim().is_empty() || HEADING.is_match(line) || list_item.is_some()) && !paragraph.is_empty() {
+            blocks.push(format!("<p>{}</p>", paragraph.join("<br/>")));
+            paragraph.clear();
+        }
+        if list_item.is_none() || list.as_ref().is_some_and(|(kind, _)| *kind != list_item.as_ref().unwrap().0) {
+            if let Some((kind, items)) = list.take() {
+                blocks.push(format!("<{}>{}</{}>", kind, items.iter().map(|item| format!("<li>{}</li>", item)).collect::<String>(), kind));
+            }
+        }
+        if let Some(captures) = HEADING.captures(line) {
+            let level = captures[1].len();
+            blocks.push(format!("<h{}>{}</h{}>", level, inline(&captures[2]), level));
+        } else if let Some((kind, item)) = list_item {
+            list.get_or_insert((kind, vec![])).1.push(inline(&item));
+        } else if !line.trim().is_empty() {
+            paragraph.push(inline(line.trim()));
+        }
+    }
+    if !paragraph.is_empty() {
+        blocks.push(format!("<p>{}</p>", paragraph.join("<br/>")));
+    }
+    if let Some((kind, items)) = list.take() {
+        blocks.push(format!("<{}>{}</{}>", kind, items.iter().map(|item| format!("<li>{}</li>", item)).collect::<String>(), kind));
+    }
+    // a single paragraph is written without <p>
+    if blocks.len() == 1 && blocks[0].starts_with("<p>") {
+        return blocks[0]["<p>".len()..blocks[0].len() - "</p>".len()].to_string();
+    }
+    blocks.concat()
+}
+
+fn inline(text: &str) -> String {
+    let mut html = escape_text(text);
+    for (regex, replacement) in INLINE.iter() {
+        html = regex.replace_all(&html, *replacement).to_string();
+    }
+    html
+}
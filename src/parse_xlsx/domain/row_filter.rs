@@ -0,0 +1,309 @@
+use std::collections::{HashMap, HashSet};
+use crate::parse_hcl::domain::filter::{Filter, FilterCondition};
+use crate::parse_hcl::errors::HCLDataError;
+use crate::parse_hcl::header_value::HeaderValue;
+use crate::parse_xlsx::domain::data_col::DataCol;
+use crate::parse_xlsx::domain::data_row::DataRow;
+use crate::parse_xlsx::domain::manipulations::find_header_number;
+use crate::parse_xlsx::errors::ExcelDataError;
+
+pub(crate) fn filter_rows(col_nr_to_cols: &HashMap<usize, DataCol>, filter: &Filter, header_to_col_nr: &HashMap<String, usize>) -> Result<Vec<usize>, HCLDataError> {
+    // returns the rows that meet all include-conditions and none of the exclude-conditions
+    let includes = cols_of_conditions(&filter.includes, col_nr_to_cols, header_to_col_nr)?;
+    let excludes = cols_of_conditions(&filter.excludes, col_nr_to_cols, header_to_col_nr)?;
+    let length = col_nr_to_cols.get(&0).map(|data_col| data_col.col.len()).unwrap_or(0);
+    let kept_rows = (0..length)
+        .filter(|row_nr| includes.iter().all(|(data_col, condition)| condition.is_met(&data_col.col[*row_nr])))
+        .filter(|row_nr| !excludes.iter().any(|(data_col, condition)| condition.is_met(&data_col.col[*row_nr])))
+        .collect();
+    Ok(kept_rows)
+}
+
+fn cols_of_conditions<'a>(conditions: &'a [FilterCondition], col_nr_to_cols: &'a HashMap<usize, DataCol>, header_to_col_nr: &HashMap<String, usize>) -> Result<Vec<(&'a DataCol, &'a FilterCondition)>, HCLDataError> {
+    let mut cols = vec![];
+    for condition in conditions.iter() {
+        let col_nr = find_header_number(&condition.input, col_nr_to_cols, header_to_col_nr)?;
+        cols.push((col_nr_to_cols.get(&col_nr).unwrap(), condition));
+    }
+    Ok(cols)
+}
+
+pub(crate) fn position_in_row(input: &HeaderValue, header_to_col_nr: &HashMap<String, usize>, col_nr_to_row_nr: &HashMap<usize, usize>) -> Result<usize, ExcelDataError> {
+    let col_nr = match input {
+        HeaderValue::Name(name) => {
+            match header_to_col_nr.get(name) {
+                None => {
+                    return Err(ExcelDataError::InputError(format!("not found header '{}' in headers: '{:?}'.", name, header_to_col_nr.keys().collect::<Vec<&String>>())));
+                }
+                Some(col_nr) => { col_nr.to_owned() }
+            }
+        }
+        HeaderValue::Number(number) => { number.to_owned() as usize }
+    };
+    match col_nr_to_row_nr.get(&col_nr) {
+        None => {
+            Err(ExcelDataError::InputError(format!("not found column with number '{}'. Probably out of bounds.", col_nr)))
+        }
+        Some(pos) => { Ok(pos.to_owned()) }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct DifferingDuplicate {
+    pub(crate) id: String,
+    // excel rows of the kept and the skipped row
+    pub(crate) kept_row_nr: usize,
+    pub(crate) row_nr: usize,
+}
+
+pub(crate) fn deduplicate_rows(rows: Vec<DataRow>, id: Option<&HeaderValue>, header_to_col_nr: &HashMap<String, usize>, col_nr_to_row_nr: &HashMap<usize, usize>) -> Result<(Vec<DataRow>, usize, Vec<DifferingDuplicate>), ExcelDataError> {
+    // rows with an id that already occurred describe the same resource, only the first one is kept
+    let pos = match id {
+        Some(id) => { position_in_row(id, header_to_col_nr, col_nr_to_row_nr)? }
+        None => {
+            match header_to_col_nr.iter().find(|(header, _)| header.to_lowercase() == "id") {
+                None => {
+                    return Err(ExcelDataError::InputError(format!("Deduplicate: not found 'id' in headers: '{:?}'. Declare the id-column with 'id' in the sheet.", header_to_col_nr.keys().collect::<Vec<&String>>())));
+                }
+                Some((_, col_nr)) => { col_nr_to_row_nr.get(col_nr).unwrap().to_owned() }
+            }
+        }
+    };
+    let total = rows.len();
+    let mut id_to_index: HashMap<Vec<String>, usize> = HashMap::new();
+    let mut unique_rows: Vec<DataRow> = vec![];
+    let mut differing = vec![];
+    for row in rows {
+        let id = row.row[pos].iter().map(|value| value.trim().to_string()).collect::<Vec<String>>();
+        if id.iter().all(|value| value.is_empty()) {
+            unique_rows.push(row);
+            continue;
+        }
+        match id_to_index.get(&id) {
+            None => {
+                id_to_index.insert(id, unique_rows.len());
+                unique_rows.push(row);
+            }
+            Some(index) => {
+                let kept = &unique_rows[*index];
+                if trimmed(&kept.row) != trimmed(&row.row) {
+                    differing.push(DifferingDuplicate { id: id.join(", "), kept_row_nr: kept.row_nr, row_nr: row.row_nr });
+                }
+            }
+        }
+    }
+    let duplicates = total - unique_rows.len();
+    Ok((unique_rows, duplicates, differing))
+}
+
+fn trimmed(row: &[Vec<String>]) -> Vec<Vec<&str>> {
+    row.iter().map(|values| values.iter().map(|value| value.trim()).collect()).collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct GroupConflict {
+    pub(crate) id: String,
+    // position of the single-valued column
+    pub(crate) pos: usize,
+    // excel rows of the kept and the dropped values
+    pub(crate) kept_row_nr: usize,
+    pub(crate) kept: Vec<String>,
+    pub(crate) row_nr: usize,
+    pub(crate) dropped: Vec<String>,
+}
+
+pub(crate) fn group_rows(rows: Vec<DataRow>, id_pos: usize, multi_value_positions: &HashSet<usize>, supplement_positions: &HashMap<usize, Vec<usize>>) -> (Vec<DataRow>, usize, Vec<GroupConflict>) {
+    // rows with the same id describe the same resource and are merged into the first one (rows without id are kept)
+    let total = rows.len();
+    let mut id_to_index: HashMap<Vec<String>, usize> = HashMap::new();
+    let mut grouped_rows: Vec<DataRow> = vec![];
+    let mut conflicts = vec![];
+    for row in rows {
+        let id = row.row[id_pos].iter().map(|value| value.trim().to_string()).collect::<Vec<String>>();
+        if id.iter().all(|value| value.is_empty()) {
+            grouped_rows.push(row);
+            continue;
+        }
+        match id_to_index.get(&id) {
+            None => {
+                id_to_index.insert(id, grouped_rows.len());
+                grouped_rows.push(row);
+            }
+            Some(index) => {
+                conflicts.extend(merge_row(&mut grouped_rows[*index], row, &id, multi_value_positions, supplement_positions));
+            }
+        }
+    }
+    let merged = total - grouped_rows.len();
+    (grouped_rows, merged, conflicts)
+}
+
+fn merge_row(grouped_row: &mut DataRow, row: DataRow, id: &[String], multi_value_positions: &HashSet<usize>, supplement_positions: &HashMap<usize, Vec<usize>>) -> Vec<GroupConflict> {
+    // supplements (comment, encoding, permissions) are merged together with the values of their property
+    let part_of_property: HashSet<&usize> = supplement_positions.values().flatten().collect();
+    let mut conflicts = vec![];
+    for pos in 0..row.row.len() {
+        if part_of_property.contains(&pos) {
+            continue;
+        }
+        let supplements = supplement_positions.get(&pos).map(|positions| positions.as_slice()).unwrap_or(&[]);
+        let values = aligned_values(&row.row, pos, supplements);
+        if values.is_empty() {
+            continue;
+        }
+        let mut existing = aligned_values(&grouped_row.row, pos, supplements);
+        if existing.is_empty() {
+            existing = values;
+        } else if multi_value_positions.contains(&pos) {
+            // values of multi-valued properties are unioned
+            for value in values {
+                if !existing.iter().any(|(existing_value, _)| existing_value.eq(&value.0)) {
+                    existing.push(value);
+                }
+            }
+        } else if existing.iter().map(|(value, _)| value).ne(values.iter().map(|(value, _)| value)) {
+            // single values are taken from the first row with a value
+            conflicts.push(GroupConflict {
+                id: id.join(", "),
+                pos,
+                kept_row_nr: grouped_row.row_nr,
+                kept: existing.iter().map(|(value, _)| value.to_owned()).collect(),
+                row_nr: row.row_nr,
+                dropped: values.into_iter().map(|(value, _)| value).collect(),
+            });
+        }
+        set_aligned_values(&mut grouped_row.row, pos, supplements, existing);
+    }
+    conflicts
+}
+
+fn aligned_values(row: &[Vec<String>], pos: usize, supplement_positions: &[usize]) -> Vec<(String, Vec<String>)> {
+    // the non-empty values of a column with the supplement-values of the same index
+    row[pos].iter().enumerate()
+        .filter(|(_, value)| !value.trim().is_empty())
+        .map(|(index, value)| (value.to_owned(), supplement_positions.iter().map(|suppl_pos| row[*suppl_pos].get(index).cloned().unwrap_or_default()).collect()))
+        .collect()
+}
+
+fn set_aligned_values(row: &mut [Vec<String>], pos: usize, supplement_positions: &[usize], values: Vec<(String, Vec<String>)>) {
+    for (nr, suppl_pos) in supplement_positions.iter().enumerate() {
+        let suppl_values: Vec<String> = values.iter().map(|(_, supplements)| supplements[nr].to_owned()).collect();
+        // a supplement without any value stays empty
+        row[*suppl_pos] = if suppl_values.iter().all(|value| value.trim().is_empty()) { vec![] } else { suppl_values };
+    }
+    row[pos] = values.into_iter().map(|(value, _)| value).collect();
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{HashMap, HashSet};
+    use hcl::block;
+    use crate::parse_hcl::domain::filter::FilterWrapper;
+    use crate::parse_xlsx::domain::data_col::test::columns;
+    use crate::parse_xlsx::domain::data_row::DataRow;
+    use crate::parse_hcl::header_value::HeaderValue;
+    use crate::parse_xlsx::domain::row_filter::{deduplicate_rows, filter_rows, group_rows, DifferingDuplicate, GroupConflict};
+
+    fn data_row(row_nr: usize, row: Vec<Vec<&str>>) -> DataRow {
+        DataRow { row: row.iter().map(|values| values.iter().map(|value| value.to_string()).collect()).collect(), row_nr }
+    }
+
+    #[test]
+    fn test_deduplicate_rows() {
+        let header_to_col_nr = HashMap::from([("ID".to_string(), 0), ("signature".to_string(), 1), ("title".to_string(), 2)]);
+        let col_nr_to_row_nr = HashMap::from([(0, 0), (1, 1), (2, 2)]);
+        let rows = || vec![
+            data_row(2, vec![vec!["1"], vec!["A-1"], vec!["Faust"]]),
+            data_row(3, vec![vec!["1"], vec!["A-1 "], vec!["Faust"]]),
+            data_row(4, vec![vec!["2"], vec!["A-1"], vec!["Werther"]]),
+            data_row(5, vec![vec![""], vec![""], vec!["Tasso"]]),
+        ];
+        // without a declared id the header 'id' is used
+        let (unique_rows, duplicates, differing) = deduplicate_rows(rows(), None, &header_to_col_nr, &col_nr_to_row_nr).unwrap();
+        assert_eq!(unique_rows.iter().map(|row| row.row_nr).collect::<Vec<usize>>(), vec![2, 4, 5]);
+        assert_eq!(duplicates, 1);
+        assert!(differing.is_empty());
+        // a declared id by name or by number
+        for id in [HeaderValue::Name("signature".to_string()), HeaderValue::Number(1)] {
+            let (unique_rows, duplicates, differing) = deduplicate_rows(rows(), Some(&id), &header_to_col_nr, &col_nr_to_row_nr).unwrap();
+            assert_eq!(unique_rows.iter().map(|row| row.row_nr).collect::<Vec<usize>>(), vec![2, 5]);
+            assert_eq!(duplicates, 2);
+            assert_eq!(differing, vec![DifferingDuplicate { id: "A-1".to_string(), kept_row_nr: 2, row_nr: 4 }]);
+        }
+        assert!(deduplicate_rows(rows(), Some(&HeaderValue::Name("shelfmark".to_string())), &header_to_col_nr, &col_nr_to_row_nr).is_err());
+        let without_id = HashMap::from([("signature".to_string(), 1), ("title".to_string(), 2)]);
+        assert!(deduplicate_rows(rows(), None, &without_id, &col_nr_to_row_nr).is_err());
+    }
+
+    #[test]
+    fn test_filter_rows() {
+        let (col_nr_to_cols, header_to_col_nr) = columns(vec![
+            ("status", vec![vec!["published"], vec!["do not publish"], vec!["published"], vec!["published"], vec!["draft"]]),
+            ("title", vec![vec!["Faust"], vec!["Werther"], vec![""], vec!["Iphigenie"], vec!["Tasso"]]),
+            ("type", vec![vec!["letter"], vec!["letter"], vec!["letter"], vec!["internal note"], vec!["letter"]]),
+        ]);
+        let filter = FilterWrapper(block!(filter {
+            include {
+                input = "title"
+                not_empty = true
+            }
+            exclude {
+                input = "status"
+                equals = "do not publish"
+            }
+            exclude {
+                input = 2
+                one_of = ["internal note", "draft"]
+            }
+        })).to_filter().unwrap();
+        assert_eq!(filter_rows(&col_nr_to_cols, &filter, &header_to_col_nr).unwrap(), vec![0, 4]);
+        let filter = FilterWrapper(block!(filter {
+            include {
+                input = "status"
+                regex = "^pub"
+            }
+            include {
+                input = "type"
+                one_of = ["letter"]
+            }
+        })).to_filter().unwrap();
+        assert_eq!(filter_rows(&col_nr_to_cols, &filter, &header_to_col_nr).unwrap(), vec![0, 2]);
+        let filter = FilterWrapper(block!(filter {
+            include {
+                input = "year"
+                not_empty = true
+            }
+        })).to_filter().unwrap();
+        assert!(filter_rows(&col_nr_to_cols, &filter, &header_to_col_nr).is_err());
+    }
+    #[test]
+    fn test_group_rows() {
+        // id, title (single), keyword (multi), comment of keyword
+        let rows = vec![
+            data_row(2, vec![vec!["letter_1"], vec!["Faust"], vec!["poem"], vec!["first"]]),
+            data_row(3, vec![vec!["letter_1"], vec![""], vec!["drama"], vec!["second"]]),
+            data_row(4, vec![vec![""], vec!["Werther"], vec!["novel"], vec![""]]),
+            data_row(5, vec![vec!["letter_1"], vec!["Faust II"], vec!["poem", "tragedy"], vec!["again", "third"]]),
+        ];
+        let multi_value_positions = HashSet::from([2]);
+        let supplement_positions = HashMap::from([(2, vec![3])]);
+        let (grouped_rows, merged, conflicts) = group_rows(rows, 0, &multi_value_positions, &supplement_positions);
+        assert_eq!(merged, 2);
+        assert_eq!(grouped_rows.len(), 2);
+        assert_eq!(grouped_rows[0].row, vec![vec!["letter_1"], vec!["Faust"], vec!["poem", "drama", "tragedy"], vec!["first", "second", "third"]]);
+        assert_eq!(grouped_rows[1].row_nr, 4);
+        assert_eq!(conflicts, vec![GroupConflict { id: "letter_1".to_string(), pos: 1, kept_row_nr: 2, kept: vec!["Faust".to_string()], row_nr: 5, dropped: vec!["Faust II".to_string()] }]);
+    }
+    #[test]
+    fn test_group_rows_without_supplements() {
+        // supplements that are empty in all rows stay empty
+        let rows = vec![
+            data_row(2, vec![vec!["a"], vec!["x"], vec![""]]),
+            data_row(3, vec![vec!["a"], vec!["y"], vec![""]]),
+        ];
+        let (grouped_rows, merged, conflicts) = group_rows(rows, 0, &HashSet::from([1]), &HashMap::from([(1, vec![2])]));
+        assert_eq!(merged, 1);
+        assert!(conflicts.is_empty());
+        assert_eq!(grouped_rows[0].row, vec![vec!["a".to_string()], vec!["x".to_string(), "y".to_string()], vec![]]);
+    }
+}
@@ -5,21 +5,24 @@ use crate::parse_hcl::transformations::Transformations;
 use crate::parse_xlsx::domain::data_col::DataCol;
 use crate::parse_xlsx::domain::data_row::DataRow;
 use crate::parse_xlsx::domain::expanded_data_sheet::{add_to_header_cols, ExpandedDataSheet};
-use crate::parse_xlsx::domain::manipulations::perform_identify;
+use crate::parse_xlsx::domain::intermediate_sheet::SheetSource;
+use crate::parse_xlsx::domain::manipulations::{perform_identify, perform_join};
 
 #[derive(Clone)]
 pub struct UpdatedDataSheet {
     pub res_name: String,
     pub col_nr_to_cols: HashMap<usize, DataCol>,
     pub header_to_col_nr: HashMap<String, usize>,
+    pub source: SheetSource,
 }
 
 impl UpdatedDataSheet {
-    fn new(col_nr_to_cols: HashMap<usize, DataCol>, header_to_col_nr: HashMap<String, usize>, res_name: String) -> Self {
+    fn new(col_nr_to_cols: HashMap<usize, DataCol>, header_to_col_nr: HashMap<String, usize>, res_name: String, source: SheetSource) -> Self {
         UpdatedDataSheet{
             res_name,
             col_nr_to_cols,
             header_to_col_nr,
+            source,
         }
     }
 }
@@ -35,21 +38,26 @@ impl UpdatedDataSheetWrapper {
                 (self.0.col_nr_to_cols.to_owned(), self.0.header_to_col_nr.to_owned())
             }
             Some(transformations) => {
-                update_sheet(self.0.col_nr_to_cols.to_owned(), self.0.header_to_col_nr.to_owned(), expanded_data_sheets, transformations, separator)?
+                update_sheet(self.0.col_nr_to_cols.to_owned(), self.0.header_to_col_nr.to_owned(), &self.0.source, expanded_data_sheets, transformations, separator)?
             }
         };
-        let updated_sheet = UpdatedDataSheet::new(col_nr_to_cols, headers_to_col_nr, self.0.res_name.to_owned());
+        let updated_sheet = UpdatedDataSheet::new(col_nr_to_cols, headers_to_col_nr, self.0.res_name.to_owned(), self.0.source.to_owned());
         Ok(updated_sheet)
     }
 }
 
-fn update_sheet(mut col_nr_to_col: HashMap<usize, DataCol>, mut header_to_col_nr: HashMap<String, usize>, expanded_data_sheets: &Vec<ExpandedDataSheet>, transformations: &Transformations, separator: &String) -> Result<(HashMap<usize, DataCol>, HashMap<String, usize>), HCLDataError> {
-    // for now only identify method uses multiple resources
+fn update_sheet(mut col_nr_to_col: HashMap<usize, DataCol>, mut header_to_col_nr: HashMap<String, usize>, source: &SheetSource, expanded_data_sheets: &Vec<ExpandedDataSheet>, transformations: &Transformations, separator: &String) -> Result<(HashMap<usize, DataCol>, HashMap<String, usize>), HCLDataError> {
+    // identify and join use multiple resources
     for identify_method in transformations.identify_methods.iter() {
         let expanded_sheet = get_correct_expanded_sheet(expanded_data_sheets, &identify_method.resource_name)?;
         let data_col:DataCol = identify_col(expanded_sheet, identify_method, &col_nr_to_col, &header_to_col_nr, separator)?;
         add_to_header_cols(&mut header_to_col_nr, &mut col_nr_to_col, data_col);
     }
+    for join_method in transformations.join_methods.iter() {
+        let expanded_sheet = get_correct_expanded_sheet(expanded_data_sheets, &join_method.resource)?;
+        let data_col = perform_join(join_method, source, expanded_sheet, &col_nr_to_col, &header_to_col_nr)?;
+        add_to_header_cols(&mut header_to_col_nr, &mut col_nr_to_col, data_col);
+    }
     Ok((col_nr_to_col, header_to_col_nr))
 }
 
@@ -122,7 +130,7 @@ fn get_correct_expanded_sheet<'a>(expanded_data_sheets: &'a Vec<ExpandedDataShee
             return Ok(expanded)
         }
     }
-    Err(HCLDataError::ParsingError(format!("Identify-methods: Cannot find expanded-data-sheet with res-name: '{}'", res_name)))
+    Err(HCLDataError::ParsingError(format!("Identify- or join-methods: Cannot find expanded-data-sheet with res-name: '{}'", res_name)))
 }
 
 
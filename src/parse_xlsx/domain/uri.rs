@@ -0,0 +1,52 @@
+use std::sync::LazyLock;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use regex::Regex;
+
+// characters that are never allowed in a URI; non-ascii characters are encoded as well
+const ILLEGAL: &AsciiSet = &CONTROLS.add(b' ').add(b'"').add(b'<').add(b'>').add(b'\\').add(b'^').add(b'`').add(b'{').add(b'|').add(b'}');
+// '[' and ']' are only allowed around IPv6-hosts, a second '#' only as part of the fragment
+const ILLEGAL_AFTER_AUTHORITY: &AsciiSet = &ILLEGAL.add(b'[').add(b']');
+const ILLEGAL_IN_FRAGMENT: &AsciiSet = &ILLEGAL_AFTER_AUTHORITY.add(b'#');
+// RFC 3986: scheme ":" hier-part [ "?" query ] [ "#" fragment ]
+static URI: LazyLock<Regex> = LazyLock::new(|| {
+    let pchar = r"(?:[A-Za-z0-9\-._~!$&'()*+,;=:@]|%[0-9A-Fa-f]{2})";
+    let userinfo = r"(?:[A-Za-z0-9\-._~!$&'()*+,;=:]|%[0-9A-Fa-f]{2})*";
+    let host = r"(?:\[[0-9A-Fa-f:.]+\]|(?:[A-Za-z0-9\-._~!$&'()*+,;=]|%[0-9A-Fa-f]{2})*)";
+    Regex::new(&format!(
+        r"^(?P<scheme>[A-Za-z][A-Za-z0-9+.\-]*):(?://(?:{userinfo}@)?(?P<host>{host})(?::[0-9]*)?(?:/{pchar}*)*|/?(?:{pchar}+(?:/{pchar}*)*)?)(?:\?(?:{pchar}|[/?])*)?(?:#(?:{pchar}|[/?])*)?$"
+    )).unwrap()
+});
+
+pub(crate) fn is_uri(value: &str) -> bool {
+    // http(s)-URIs need a host
+    match URI.captures(value) {
+        None => { false }
+        Some(captures) => {
+            let scheme = captures["scheme"].to_lowercase();
+            !(scheme == "http" || scheme == "https") || captures.name("host").is_some_and(|host| !host.as_str().is_empty())
+        }
+    }
+}
+
+pub(crate) fn encode_uri(value: &str) -> String {
+    // percent-encodes illegal characters, existing percent-encodings are kept
+    let authority_end = match value.find("://") {
+        None => { 0 }
+        Some(pos) => { value[pos + 3..].find(['/', '?', '#']).map(|end| pos + 3 + end).unwrap_or(value.len()) }
+    };
+    let mut encoded = String::new();
+    let mut in_fragment = false;
+    for (pos, character) in value.char_indices() {
+        if character == '%' {
+            let hex = value[pos + 1..].chars().take(2).filter(|digit| digit.is_ascii_hexdigit()).count();
+            encoded.push_str(if hex == 2 { "%" } else { "%25" });
+            continue;
+        }
+        let set = if in_fragment { ILLEGAL_IN_FRAGMENT } else if pos < authority_end { ILLEGAL } else { ILLEGAL_AFTER_AUTHORITY };
+        encoded.extend(utf8_percent_encode(character.encode_utf8(&mut [0; 4]), set));
+        if character == '#' {
+            in_fragment = true;
+        }
+    }
+    encoded
+}
@@ -1,6 +1,10 @@
 use std::fs;
+use std::fs::File;
+use std::io::BufReader;
 use std::path::{Path, PathBuf};
+use serde_json::Value;
 use crate::path_operations::errors::PathOpError;
+use crate::path_operations::path_operations::filter_paths_based_on_extension;
 
 pub fn canonicalize_path(path: &PathBuf, curr_folder: &&&Path) -> Result<PathBuf, PathOpError> {
     let path = match  path.is_relative() {
@@ -18,37 +22,85 @@ pub fn canonicalize_path(path: &PathBuf, curr_folder: &&&Path) -> Result<PathBuf
         }
     }
 }
-pub fn find_datamodel(hcl_path: PathBuf) -> Result<PathBuf, PathOpError> {
-    // look in folder hcl is in, else not found
-    let mut dir = hcl_path.to_owned();
-    dir.pop();
-    match find_json_datamodel(&dir) {
-        None => {
-            Err(PathOpError::WrongPath(format!("Looking for datamodel in folder with hcl-file '{:?}'; but couldn't find datamodel there.", dir)))
-        }
-        Some(dm_path) => {Ok(dm_path)}
+pub fn find_datamodel(folders: &[PathBuf], shortcode: &str) -> Result<PathBuf, PathOpError> {
+    // look in the given folders for exactly one project-json with the same shortcode
+    let mut searched: Vec<PathBuf> = vec![];
+    let mut found: Vec<(PathBuf, String)> = vec![];
+    for folder in folders.iter() {
+        if searched.contains(folder) {
+            continue;
+        }
+        searched.push(folder.to_owned());
+        for path in filter_paths_based_on_extension(folder, "json")? {
+            match project_shortcode(&path) {
+                None => {
+                    // not a project-json
+                    continue;
+                }
+                Some(project_shortcode) => {
+                    found.push((path, project_shortcode));
+                }
+            }
+        }
+    }
+    let matches = found.iter().filter(|(_, project_shortcode)| project_shortcode.eq(shortcode)).map(|(path, _)| path.to_owned()).collect::<Vec<PathBuf>>();
+    match matches.len() {
+        0 => {
+            Err(PathOpError::WrongPath(format!("Looking for datamodel with shortcode '{}' in folders '{:?}'; but couldn't find it. Found project-jsons with shortcodes: {:?}", shortcode, searched, found)))
+        }
+        1 => {
+            Ok(matches[0].to_owned())
+        }
+        _ => {
+            Err(PathOpError::WrongPath(format!("Looking for datamodel with shortcode '{}' in folders '{:?}'; found {} datamodels, but should find exactly one: {:?}", shortcode, searched, matches.len(), matches)))
+        }
     }
 }
 
-fn find_json_datamodel(curr_dir: &PathBuf) -> Option<PathBuf> {
-    let dir_reader = curr_dir.read_dir().expect("Expected to be able to read directory");
-    let paths = dir_reader.
-        map(|dir_entry|dir_entry).
-        filter(|dir_entry|dir_entry.is_ok())
-        .map(|dir_entry| dir_entry.unwrap().path())
-        .filter_map(|path|
-            if path.extension().map_or(false, |ext| ext == "json") {
-                Some(path)
-            } else {
-                None
-            }
-        )
-        .collect::<Vec<_>>();
-    if paths.len() != 1 {
-        // Success only if one and only one json-file exists in this folder
-        None
-    } else {
-        Some(paths.get(0).unwrap().to_owned())
-    }
+fn project_shortcode(path: &PathBuf) -> Option<String> {
+    // returns the shortcode if the file is a json with 'project.shortcode'
+    let file = File::open(path).ok()?;
+    let json: Value = serde_json::from_reader(BufReader::new(file)).ok()?;
+    json.get("project")?.get("shortcode")?.as_str().map(|shortcode| shortcode.to_string())
 }
 
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::PathBuf;
+    use crate::path_operations::canonicalize_path::find_datamodel;
+
+    fn folder(name: &str, files: Vec<(&str, &str)>) -> PathBuf {
+        let folder = std::env::temp_dir().join("excel2xml_find_datamodel").join(name);
+        let _ = fs::remove_dir_all(&folder);
+        fs::create_dir_all(&folder).unwrap();
+        for (file_name, content) in files {
+            fs::write(folder.join(file_name), content).unwrap();
+        }
+        folder
+    }
+
+    #[test]
+    fn test_find_datamodel() {
+        let hcl_folder = folder("hcl", vec![
+            ("project.json", r#"{"project": {"shortcode": "0804", "shortname": "letters"}}"#),
+            ("other_project.json", r#"{"project": {"shortcode": "0805"}}"#),
+            ("settings.json", r#"{"separator": ";"}"#),
+            ("broken.json", "{"),
+        ]);
+        let resources_folder = folder("resources", vec![
+            ("project_copy.json", r#"{"project": {"shortcode": "0805"}}"#),
+            ("names.json", r#"{"names": ["Goethe"]}"#),
+        ]);
+        let folders = vec![hcl_folder.to_owned(), resources_folder.to_owned()];
+        // one match
+        assert_eq!(find_datamodel(&folders, "0804").unwrap(), hcl_folder.join("project.json"));
+        // no match
+        assert!(find_datamodel(&folders, "0806").is_err());
+        // several matches across the hcl- and the resources-folder
+        assert!(find_datamodel(&folders, "0805").is_err());
+        // the same folder is only searched once
+        assert_eq!(find_datamodel(&[hcl_folder.to_owned(), hcl_folder.to_owned()], "0804").unwrap(), hcl_folder.join("project.json"));
+        assert_eq!(find_datamodel(&[resources_folder.to_owned(), resources_folder.to_owned()], "0805").unwrap(), resources_folder.join("project_copy.json"));
+    }
+}
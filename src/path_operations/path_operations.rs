@@ -1,19 +1,27 @@
 use std::path::{Path, PathBuf};
+use crate::parse_hcl::domain::command::ParseInfoCommand;
 use crate::parse_hcl::domain::command_path::CommandOrPath;
 use crate::path_operations::canonicalize_path::{canonicalize_path, find_datamodel};
 use crate::path_operations::errors::PathOpError;
 
-pub(crate) fn canonicalize_paths(dm_path: &CommandOrPath, folder_data_path: &PathBuf, curr_folder: &&Path) -> Result<(PathBuf, PathBuf), PathOpError> {
+pub(crate) fn canonicalize_paths(dm_path: &CommandOrPath, folder_data_path: &PathBuf, curr_folder: &&Path, shortcode: &Option<String>) -> Result<(PathBuf, PathBuf), PathOpError> {
     // should update the datamodel and data-folder path
+    let new_folder_data_path = canonicalize_path(folder_data_path, &curr_folder)?;
     let new_dm_path = match dm_path {
         CommandOrPath::Path(datamodel_path) => {
             canonicalize_path(datamodel_path, &curr_folder)?
         }
-        _ => {
-            panic!("remove this");
+        CommandOrPath::Command(ParseInfoCommand::FINDPaths) => {
+            // search the folder of the hcl-file and the resources-folder
+            let shortcode = match shortcode {
+                None => {
+                    return Err(PathOpError::WrongPath("Cannot find datamodel: 'cmd.find' needs the 'shortcode' of the project.".to_string()));
+                }
+                Some(shortcode) => {shortcode}
+            };
+            find_datamodel(&[curr_folder.to_path_buf(), new_folder_data_path.to_owned()], shortcode)?
         }
     };
-    let new_folder_data_path = canonicalize_path(folder_data_path, &curr_folder)?;
     Ok((new_folder_data_path, new_dm_path))
 }
 pub fn filter_paths_based_on_extension(dir: &PathBuf, extension_to_check: &str) -> Result<Vec<PathBuf>, PathOpError>{
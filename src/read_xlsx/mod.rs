@@ -1,5 +1,5 @@
 pub(crate) mod get_file;
 pub mod sheet;
 pub mod extract;
-mod errors;
+pub(crate) mod errors;
 
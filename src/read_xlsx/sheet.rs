@@ -9,12 +9,13 @@ pub struct Sheet {
     pub(crate) res_name: String,
     pub(crate) rel_path: String,
     pub(crate) sheet_info_nr: usize,
+    pub(crate) sheet_name: String,
     pub(crate) table: Range<Data>,
 }
 
 impl Sheet {
-    pub fn new(res_name: String, rel_path: String,sheet_info_nr: usize, table: Range<Data>) -> Self {
-        Sheet {res_name, rel_path, sheet_info_nr, table}
+    pub fn new(res_name: String, rel_path: String,sheet_info_nr: usize, sheet_name: String, table: Range<Data>) -> Self {
+        Sheet {res_name, rel_path, sheet_info_nr, sheet_name, table}
     }
 }
 pub fn sheets<P: AsRef<Path> + std::fmt::Debug>(folder_path: P, parse_information: &ParseInformation) -> Result<Vec<Sheet>, ReadXlsxError> {
@@ -40,15 +41,17 @@ pub fn sheets<P: AsRef<Path> + std::fmt::Debug>(folder_path: P, parse_informatio
             }
         };
         for (pos, worksheet) in read_xlsx(PathBuf::from(path.path()))?.iter().enumerate() {
-            let sheet_info = match workbook.sheet_infos.get(&(pos + 1)) {
+            let sheet_infos = match workbook.sheet_infos.get(&(pos + 1)) {
                 None => {
                     // worksheet doesn't exist in parse-information
                     continue }
-                Some(sheet_info) => {sheet_info}
+                Some(sheet_infos) => {sheet_infos}
             };
-
-            let sheet = Sheet::new(sheet_info.resource_name.to_owned(), workbook.rel_path.to_owned(), pos + 1, worksheet.1.to_owned());
-            sheets.push(sheet);
+            // every resource-mapping of the worksheet gets its own sheet
+            for sheet_info in sheet_infos.iter() {
+                let sheet = Sheet::new(sheet_info.resource_name.to_owned(), workbook.rel_path.to_owned(), pos + 1, worksheet.0.to_owned(), worksheet.1.to_owned());
+                sheets.push(sheet);
+            }
         }
     }
     if sheets.is_empty() {
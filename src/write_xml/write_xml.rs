@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use simple_xml_builder::XMLElement;
 use crate::parse_dm::domain::data_model::DataModel;
@@ -41,7 +41,7 @@ pub fn write_xml(data_containers: &Vec<DataContainer>, data_model: &DataModel) -
     let file = File::create(new_path.as_str())?;
     for data_container in data_containers {
         let restype = ":".to_string() + data_container.res_name.as_str();
-        add_resources(&data_container.resources, &hash_to_id_authors, restype, &data_model, &mut knora);
+        add_resources(&data_container.resources, &hash_to_id_authors, restype, &data_model, &data_container.richtext_propnames, &mut knora);
         // todo allow here returning single files
         // let path = new_path(&data_container.res_name);
     }
@@ -75,15 +75,15 @@ fn create_authorship_hash_map(data_containers: &Vec<DataContainer>) -> HashMap<S
     hash_to_id_authors
 }
 
-fn add_resources(resources: &Vec<Instance>, hash_to_id_authors: &HashMap<String, (String, Vec<String>)>, restype: String, data_model: &&DataModel, knora: &mut XMLElement) {
+fn add_resources(resources: &Vec<Instance>, hash_to_id_authors: &HashMap<String, (String, Vec<String>)>, restype: String, data_model: &&DataModel, richtext_propnames: &HashSet<String>, knora: &mut XMLElement) {
     for resource in resources {
         let mut xml_res = xml_resource(&resource, hash_to_id_authors, &restype);
-        add_values(&resource.dasch_value_fields, &mut xml_res, data_model);
+        add_values(&resource.dasch_value_fields, &mut xml_res, data_model, richtext_propnames);
         knora.add_child(xml_res);
     }
 }
 
-fn add_values(dasch_value_fields: &Vec<DaschValueField>, mut xml_res: &mut XMLElement, data_model: &&DataModel) {
+fn add_values(dasch_value_fields: &Vec<DaschValueField>, mut xml_res: &mut XMLElement, data_model: &&DataModel, richtext_propnames: &HashSet<String>) {
     for dasch_value_field in dasch_value_fields.iter() {
         let property_object = &data_model.properties.iter().find(|property| property.name.eq(&dasch_value_field.propname)).unwrap();
         let (xml_object, sub_xml_object) = xml_object_sub_object(&property_object.object);
@@ -94,14 +94,14 @@ fn add_values(dasch_value_fields: &Vec<DaschValueField>, mut xml_res: &mut XMLEl
             prop_container.add_attribute("list", property_object.h_list.as_ref().unwrap(), &standard_escape);
         }
         for dasch_value in dasch_value_field.values.iter() {
-            let prop_value = value(dasch_value, &sub_xml_object, property_object);
+            let prop_value = value(dasch_value, &sub_xml_object, property_object, richtext_propnames.contains(&dasch_value_field.propname));
             prop_container.add_child(prop_value);
         }
         xml_res.add_child(prop_container);
     }
 }
 
-fn value(dasch_value: &DaschValue, sub_xml_object: &String, property_object: &&Property) -> XMLElement {
+fn value(dasch_value: &DaschValue, sub_xml_object: &String, property_object: &&Property, is_sanitized: bool) -> XMLElement {
     let mut prop_value = XMLElement::new(&sub_xml_object);
     /*
 
@@ -119,6 +119,10 @@ fn value(dasch_value: &DaschValue, sub_xml_object: &String, property_object: &&P
         prop_value.add_attribute("encoding", dasch_value.encoding.as_ref().unwrap(), &standard_escape);
     }
     let escape_func: Box<fn(&str) -> String> = match property_object.gui_element {
+        GUIElement::RICHTEXT if is_sanitized => {
+            // to_richtext already returns well-formed xml with the tags that DSP accepts
+            Box::new(|input| input.to_string())
+        }
         GUIElement::RICHTEXT => {
             Box::new(|input|
                 input